@@ -9,13 +9,17 @@ pub mod ffi {
     #[derive(Debug)]
     pub struct Context<'alloc> {
         pub(crate) allocator: crate::A<'alloc>,
-        _marker: core::marker::PhantomData<&'alloc crate::A<'alloc>>,
+        pub(crate) _marker: core::marker::PhantomData<&'alloc crate::A<'alloc>>,
     }
 
     impl<'a> Context<'a> {
+        /// A context allocating with the global allocator of the library
         #[diplomat::attr(auto, constructor)]
-        pub const fn global() -> Box<Self, crate::A<'a>> {
-            todo!()
+        pub fn global() -> Box<Self> {
+            Box::new(Self {
+                allocator: crate::ContextAllocator::Global,
+                _marker: core::marker::PhantomData,
+            })
         }
     }
 
@@ -96,10 +100,15 @@ pub mod ffi {
         }
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq)]
     #[repr(C)]
     pub enum SolverFamily {
         /// Origami Simulator by Amanda Ghaessi
         OrigamiSimulator,
+        /// Kinematic rigid origami, with rigid faces rotated about the creases
+        RigidOrigami,
+        /// The static equilibrium of the Origami Simulator model
+        StaticEquilibrium,
     }
 
     #[repr(C)]
@@ -144,13 +153,21 @@ pub mod ffi {
     impl<'alloc> Context<'alloc> {
         pub fn create_solver_sync<'a>(
             &'a self,
-            _params: Parameters,
+            params: Parameters,
         ) -> Result<Box<crate::solver::ffi::Solver<'a>>, SolverCreationError> {
             use pollster::FutureExt as _;
-            let solver =
-                rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
-                    .block_on()
-                    .unwrap();
+
+            let backends =
+                rtori_core::os_solver::BackendFlags::from_bits_truncate(params.backend.value);
+
+            let solver = rtori_core::family::Family::from(params.family)
+                .create_in(backends, self.allocator)
+                .block_on()
+                .map_err(|e| match e {
+                    rtori_core::family::CreationError::NoBackendMatching => {
+                        SolverCreationError::NoBackendMatching
+                    }
+                })?;
 
             Ok(Box::new(crate::solver::ffi::Solver {
                 ctx: self,
@@ -159,3 +176,51 @@ pub mod ffi {
         }
     }
 }
+
+impl From<ffi::SolverFamily> for rtori_core::family::Family {
+    fn from(family: ffi::SolverFamily) -> Self {
+        match family {
+            ffi::SolverFamily::OrigamiSimulator => Self::OrigamiSimulator,
+            ffi::SolverFamily::RigidOrigami => Self::RigidOrigami,
+            ffi::SolverFamily::StaticEquilibrium => Self::StaticEquilibrium,
+        }
+    }
+}
+
+impl From<rtori_core::family::Family> for ffi::SolverFamily {
+    fn from(family: rtori_core::family::Family) -> Self {
+        match family {
+            rtori_core::family::Family::OrigamiSimulator => Self::OrigamiSimulator,
+            rtori_core::family::Family::RigidOrigami => Self::RigidOrigami,
+            rtori_core::family::Family::StaticEquilibrium => Self::StaticEquilibrium,
+        }
+    }
+}
+
+/// Creates a context whose solvers allocate through the given allocator,
+/// to be destroyed with `rtori_Context_destroy` once everything created from it has been.
+///
+/// The handles given to the host (the context included) are still allocated with the global allocator,
+/// as that's what they're freed with.
+///
+/// Returns null if `custom` is null.
+///
+/// # Safety
+/// `custom` must point to a valid [`crate::CustomAllocator`], which is copied:
+/// its functions must stay callable with its `ctx` for as long as the context lives.
+/// They must also be thread-safe, as they may be called from any thread, at the same time
+/// (the multi-threaded CPU backend allocates and frees from the threads of its pool).
+#[no_mangle]
+pub unsafe extern "C" fn rtori_Context_custom(
+    custom: *const crate::CustomAllocator,
+) -> *mut ffi::Context<'static> {
+    // SAFETY: the caller guarantees that it's either null or valid
+    let Some(custom) = (unsafe { custom.as_ref() }) else {
+        return core::ptr::null_mut();
+    };
+
+    Box::into_raw(Box::new(ffi::Context {
+        allocator: crate::ContextAllocator::Custom(*custom),
+        _marker: core::marker::PhantomData,
+    }))
+}
@@ -152,7 +152,7 @@ pub mod ffi {
         pub fn parse_bytes(
             ctx: &context::Context<'ctx>,
             bytes: &[DiplomatByte],
-        ) -> Result<Box<FoldFile<'ctx>, crate::A<'ctx>>, FoldFileParseError> {
+        ) -> Result<Box<FoldFile<'ctx>>, FoldFileParseError> {
             if bytes.len() == 0 {
                 return Err(FoldFileParseError {
                     status: FoldFileParseErrorKind::Empty,
@@ -167,19 +167,19 @@ pub mod ffi {
                 }
             })?;
 
-            Ok(Box::new_in(
-                FoldFile {
-                    inner: parsed,
-                    _marker: std::marker::PhantomData,
-                },
-                ctx.allocator,
-            ))
+            // Handles are freed by diplomat with the global allocator,
+            // the context only bounds the lifetime of the file
+            let _ = ctx;
+            Ok(Box::new(FoldFile {
+                inner: parsed,
+                _marker: std::marker::PhantomData,
+            }))
         }
 
         pub fn parse_str(
             ctx: &context::Context<'ctx>,
             str: &str,
-        ) -> Result<Box<FoldFile<'ctx>, crate::A<'ctx>>, FoldFileParseError> {
+        ) -> Result<Box<FoldFile<'ctx>>, FoldFileParseError> {
             if str.len() == 0 {
                 return Err(FoldFileParseError {
                     status: FoldFileParseErrorKind::Empty,
@@ -193,13 +193,13 @@ pub mod ffi {
                     error: DiplomatOption::from(Some(JSONParseError::from(inner))),
                 })?;
 
-            Ok(Box::new_in(
-                FoldFile {
-                    inner: parsed,
-                    _marker: std::marker::PhantomData,
-                },
-                ctx.allocator,
-            ))
+            // Handles are freed by diplomat with the global allocator,
+            // the context only bounds the lifetime of the file
+            let _ = ctx;
+            Ok(Box::new(FoldFile {
+                inner: parsed,
+                _marker: std::marker::PhantomData,
+            }))
         }
 
         pub fn query_metadata_string(
@@ -4,14 +4,17 @@ extern crate alloc;
 use alloc::alloc::Allocator;
 use core::ffi::c_void;
 
-#[cfg(feature = "allocator_api")]
 mod allocator {
+    use super::*;
+    use core::alloc::{AllocError, Layout};
+    use core::ptr::NonNull;
+
     type RawAlloc =
         unsafe extern "C" fn(ctx: *const c_void, size: usize, alignment: usize) -> *mut c_void;
     type RawDealloc =
         unsafe extern "C" fn(ctx: *const c_void, ptr: *mut c_void, size: usize, alignment: usize);
 
-    /// The two function pointed to must be thread-safe
+    /// The two function pointed to must be thread-safe, as they may be called from any thread
     #[derive(Debug, Clone, Copy, PartialEq)]
     #[repr(C)]
     pub struct CustomAllocator {
@@ -20,26 +23,24 @@ mod allocator {
         pub ctx: *const c_void,
     }
 
-    unsafe impl<'alloc> Allocator for &'alloc CustomAllocator {
-        fn allocate(
-            &self,
-            layout: alloc::Layout,
-        ) -> Result<std::ptr::NonNull<[u8]>, alloc::AllocError> {
+    // SAFETY: the host guarantees that the functions are thread-safe, `ctx` is only ever given back to them
+    unsafe impl Send for CustomAllocator {}
+    unsafe impl Sync for CustomAllocator {}
+
+    unsafe impl Allocator for CustomAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
             if layout.size() == 0 {
-                return Err(alloc::AllocError);
+                return Err(AllocError);
             }
 
             let ptr = unsafe { (self.alloc)(self.ctx, layout.size(), layout.align()) }.cast::<u8>();
 
-            let ptr_nonnull = std::ptr::NonNull::new(ptr).ok_or(alloc::AllocError)?;
+            let ptr_nonnull = NonNull::new(ptr).ok_or(AllocError)?;
 
-            return Ok(std::ptr::NonNull::slice_from_raw_parts(
-                ptr_nonnull,
-                layout.size(),
-            ));
+            Ok(NonNull::slice_from_raw_parts(ptr_nonnull, layout.size()))
         }
 
-        unsafe fn deallocate(&self, ptr: std::ptr::NonNull<u8>, layout: alloc::Layout) {
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
             if layout.size() == 0 {
                 panic!("Should not happen to deallocate a ZST when we should panic on alloc");
             }
@@ -55,51 +56,35 @@ mod allocator {
         }
     }
 
+    /// Where the allocations of a context (and of the solvers created from it) go
     #[derive(Debug, Clone, Copy, PartialEq)]
-    pub enum ContextAllocator<'alloc> {
-        Custom(&'alloc CustomAllocator),
+    pub enum ContextAllocator {
+        Custom(CustomAllocator),
         Global,
     }
 
-    unsafe impl<'alloc> Allocator for ContextAllocator<'alloc> {
-        fn allocate(
-            &self,
-            layout: alloc::Layout,
-        ) -> Result<std::ptr::NonNull<[u8]>, alloc::AllocError> {
+    unsafe impl Allocator for ContextAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
             match &self {
                 ContextAllocator::Custom(given) => given.allocate(layout),
-                ContextAllocator::Global => alloc::Global.allocate(layout),
+                ContextAllocator::Global => alloc::alloc::Global.allocate(layout),
             }
         }
 
-        unsafe fn deallocate(&self, ptr: std::ptr::NonNull<u8>, layout: alloc::Layout) {
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
             match &self {
                 // SAFETY: as long as the invariants are handled by the C code, this is fine
                 ContextAllocator::Custom(given) => unsafe { given.deallocate(ptr, layout) },
                 // SAFETY: just a passthrough
-                ContextAllocator::Global => unsafe { alloc::Global.deallocate(ptr, layout) },
+                ContextAllocator::Global => unsafe { alloc::alloc::Global.deallocate(ptr, layout) },
             }
         }
     }
-
-    /// An extension to allow c-language interface to use a custom allocator
-    pub extern "C" fn Context_custom(
-        custom: std::ptr::NonNull<crate::CustomAllocator>,
-    ) -> *const ffi::Context<'static> {
-        let allocator = crate::ContextAllocator::Custom(unsafe { &*(custom.as_ref()) });
-        let context = ffi::Context { allocator };
-
-        let boxed = Box::new_in(context, allocator);
-
-        return Box::into_raw(boxed);
-    }
 }
 
-#[cfg(feature = "allocator_api")]
-pub type A<'ctx> = std::alloc::Global;
+pub use allocator::{ContextAllocator, CustomAllocator};
 
-#[cfg(not(feature = "allocator_api"))]
-pub type A<'ctx> = std::alloc::Global;
+pub type A<'ctx> = ContextAllocator;
 
 pub mod context;
 pub mod fold;
@@ -1,3 +1,25 @@
+impl From<rtori_core::family::OperationError> for ffi::SolverOperationError {
+    fn from(error: rtori_core::family::OperationError) -> Self {
+        match error {
+            rtori_core::family::OperationError::NotLoaded => Self::NotLoaded,
+            rtori_core::family::OperationError::Unsupported(_) => Self::Unsupported,
+            rtori_core::family::OperationError::OutOfRange { .. } => Self::OutOfRange,
+            rtori_core::family::OperationError::InvalidValue => Self::InvalidValue,
+            rtori_core::family::OperationError::StepFailed { .. }
+            | rtori_core::family::OperationError::NotConverged => Self::Other,
+        }
+    }
+}
+
+impl From<rtori_core::os_solver::LoadError> for ffi::SolverLoadError {
+    fn from(error: rtori_core::os_solver::LoadError) -> Self {
+        match error {
+            rtori_core::os_solver::LoadError::UnsupportedBackend => Self::UnsupportedBackend,
+            _ => Self::InvalidFrame,
+        }
+    }
+}
+
 #[diplomat::bridge]
 #[diplomat::abi_rename = "rtori_{0}"]
 #[diplomat::attr(auto, namespace = "rtori")] // TODO: ::solver when https://github.com/rust-diplomat/diplomat/issues/591
@@ -17,19 +39,66 @@ pub mod ffi {
 
         /// Another error
         Other,
+
+        /// Attempted to address an element (such as a node) that doesn't exist in the loaded model
+        OutOfRange,
+
+        /// The slice length need to be a multiple of 3 for per-node vectors
+        SliceLengthNotMultipleOfThree,
+
+        /// The family of the solver doesn't support the operation
+        Unsupported,
+
+        /// The value is outside of the domain of the parameter (such as a mass that isn't strictly positive)
+        InvalidValue,
+    }
+
+    /// What a solver supports, see the `CAPABILITY_` constants
+    #[repr(C)]
+    pub struct SolverCapabilities {
+        pub value: u32,
+    }
+
+    impl SolverCapabilities {
+        pub const CAPABILITY_STEP: u32 = rtori_core::family::Capabilities::STEP.bits();
+        pub const CAPABILITY_EXTRACT_VELOCITY: u32 =
+            rtori_core::family::Capabilities::EXTRACT_VELOCITY.bits();
+        pub const CAPABILITY_EXTRACT_ERROR: u32 =
+            rtori_core::family::Capabilities::EXTRACT_ERROR.bits();
+        pub const CAPABILITY_FACE_STIFFNESS: u32 =
+            rtori_core::family::Capabilities::FACE_STIFFNESS.bits();
+        pub const CAPABILITY_GRAVITY: u32 = rtori_core::family::Capabilities::GRAVITY.bits();
+        pub const CAPABILITY_NODE_EXTERNAL_FORCES: u32 =
+            rtori_core::family::Capabilities::NODE_EXTERNAL_FORCES.bits();
+        pub const CAPABILITY_NODE_CONSTRAINTS: u32 =
+            rtori_core::family::Capabilities::NODE_CONSTRAINTS.bits();
+        pub const CAPABILITY_CREASE_STIFFNESS: u32 =
+            rtori_core::family::Capabilities::CREASE_STIFFNESS.bits();
+        pub const CAPABILITY_NODE_MASS: u32 = rtori_core::family::Capabilities::NODE_MASS.bits();
+        pub const CAPABILITY_NODE_FIXED: u32 = rtori_core::family::Capabilities::NODE_FIXED.bits();
+
+        pub fn contains(&self, capability: u32) -> bool {
+            self.value & capability == capability
+        }
     }
 
     #[derive(Debug)]
     #[repr(C)]
     pub enum SolverLoadError {
         NoSuchFrame,
+        /// The frame refers to a parent frame that doesn't exist, or its ancestry is cyclic
+        InvalidFrameParent,
+        /// The frame is malformed (such as indices referring to missing elements) and couldn't be imported
+        InvalidFrame,
+        /// The backend of the solver doesn't support loading models
+        UnsupportedBackend,
     }
 
     #[diplomat::opaque]
     #[derive(Debug)]
     pub struct Solver<'ctx> {
         pub(crate) ctx: &'ctx context::Context<'ctx>,
-        pub(crate) inner: rtori_core::os_solver::Solver,
+        pub(crate) inner: alloc::boxed::Box<dyn rtori_core::family::Solver, crate::A<'ctx>>,
     }
 
     impl<'ctx> Solver<'ctx> {
@@ -43,23 +112,204 @@ pub mod ffi {
             fold: &fold_ffi::FoldFile,
             frame_index: u16,
         ) -> Result<(), SolverLoadError> {
-            let frame = fold.inner.frame(frame_index);
-            match frame {
-                Some(frame) => {
-                    self.inner.load_fold_in(&frame.get(), self.ctx.allocator);
-                    Ok(())
-                }
-                None => Err(SolverLoadError::NoSuchFrame),
+            let frame = fold
+                .inner
+                .frame(frame_index)
+                .ok_or(SolverLoadError::NoSuchFrame)?;
+            let resolved = frame
+                .get()
+                .map_err(|_| SolverLoadError::InvalidFrameParent)?;
+
+            self.inner.load_fold(&resolved).map_err(Into::into)
+        }
+
+        #[diplomat::attr(auto, getter)]
+        pub fn family(&self) -> context::SolverFamily {
+            self.inner.family().into()
+        }
+
+        #[diplomat::attr(auto, getter)]
+        pub fn capabilities(&self) -> SolverCapabilities {
+            SolverCapabilities {
+                value: self.inner.capabilities().bits(),
             }
         }
 
+        /// The number of SIMD lanes processed at once by the CPU backends, chosen from the capabilities of the processor.
+        /// It is 1 for the families without SIMD backends.
+        #[diplomat::attr(auto, getter)]
+        pub fn lane_count(&self) -> u32 {
+            let solver: &dyn core::any::Any = &*self.inner;
+            solver
+                .downcast_ref::<rtori_core::os_solver::Solver<crate::A<'ctx>>>()
+                .map_or(1, |solver| solver.lane_width().lane_count() as u32)
+        }
+
+        /// The number of nodes of the loaded model.
+        /// Cuts and joins make it differ from the number of vertices of the FOLD frame, see `copy_node_source_vertex`.
+        pub fn node_count(&self) -> Result<u32, SolverOperationError> {
+            self.inner
+                .node_count()
+                .ok_or(SolverOperationError::NotLoaded)
+        }
+
+        /// Copy the index of the vertex of the FOLD frame each node comes from, starting at node `offset`,
+        /// so that the extracted results can be mapped back to the vertices.
+        /// A vertex split by a cut gives several nodes, and vertices welded by a join a single one.
+        pub fn copy_node_source_vertex(
+            &self,
+            dest: &mut [u32],
+            offset: u32,
+        ) -> Result<(), SolverOperationError> {
+            self.inner
+                .copy_node_source_vertex(dest, offset)
+                .map_err(Into::into)
+        }
+
+        /// Set the stiffness of the faces, which keeps the angles of each face close to their nominal values
+        pub fn set_face_stiffness(
+            &mut self,
+            face_stiffness: f32,
+        ) -> Result<(), SolverOperationError> {
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::FaceStiffness(face_stiffness))
+                .map_err(Into::into)
+        }
+
         /// For a 'steppable' solver, step the solver.
         /// Some solvers cannot be stepped, as they do not have any intermediary state.
         pub fn step(&mut self, step_count: u32) -> Result<(), SolverOperationError> {
-            self.inner.step(step_count).map_err(|e| match e {
-                rtori_core::os_solver::StepError::NotLoaded => SolverOperationError::NotLoaded,
-                _ => SolverOperationError::Other,
-            })
+            self.inner.step(step_count).map_err(Into::into)
+        }
+
+        /// Bring the model back to its rest state, as it was just after loading, without importing it again.
+        /// The parameters (such as the fold percentage) are kept.
+        pub fn reset(&mut self) -> Result<(), SolverOperationError> {
+            self.inner.reset().map_err(Into::into)
+        }
+
+        /// Set the external forces applied to the nodes starting at `offset`, given as three components per node.
+        /// They replace the static forces loaded from the FOLD file.
+        pub fn set_node_external_forces(
+            &mut self,
+            forces: &[f32],
+            offset: u32,
+        ) -> Result<(), SolverOperationError> {
+            if forces.len() % 3 != 0 {
+                return Err(SolverOperationError::SliceLengthNotMultipleOfThree);
+            }
+            let forces: &[rtori_core::model::Vector3F] = bytemuck::cast_slice(forces);
+
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::NodeExternalForces { forces, offset })
+                .map_err(Into::into)
+        }
+
+        /// Attach a node to the target position `(x, y, z)` with a critically damped spring of the given stiffness,
+        /// replacing its previous constraint if any.
+        /// The target is in the same space as the vertices of the FOLD file.
+        pub fn set_node_spring_constraint(
+            &mut self,
+            node_index: u32,
+            x: f32,
+            y: f32,
+            z: f32,
+            stiffness: f32,
+        ) -> Result<(), SolverOperationError> {
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::NodeConstraint {
+                    node_index,
+                    constraint: Some(rtori_core::model::NodeConstraint::Spring {
+                        target: rtori_core::model::Vector3F([x, y, z]),
+                        stiffness,
+                    }),
+                })
+                .map_err(Into::into)
+        }
+
+        /// Pin a node onto the target position `(x, y, z)`, replacing its previous constraint if any.
+        /// The target is in the same space as the vertices of the FOLD file.
+        pub fn set_node_pinned_constraint(
+            &mut self,
+            node_index: u32,
+            x: f32,
+            y: f32,
+            z: f32,
+        ) -> Result<(), SolverOperationError> {
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::NodeConstraint {
+                    node_index,
+                    constraint: Some(rtori_core::model::NodeConstraint::Pinned {
+                        target: rtori_core::model::Vector3F([x, y, z]),
+                    }),
+                })
+                .map_err(Into::into)
+        }
+
+        /// Detach a node from its target, if it was attached to one
+        pub fn remove_node_constraint(
+            &mut self,
+            node_index: u32,
+        ) -> Result<(), SolverOperationError> {
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::NodeConstraint {
+                    node_index,
+                    constraint: None,
+                })
+                .map_err(Into::into)
+        }
+
+        /// Detach every node from its target
+        pub fn clear_node_constraints(&mut self) -> Result<(), SolverOperationError> {
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::ClearNodeConstraints)
+                .map_err(Into::into)
+        }
+
+        /// Set the stiffness of a single crease, which must be finite and positive
+        pub fn set_crease_stiffness(
+            &mut self,
+            crease_index: u32,
+            stiffness: f32,
+        ) -> Result<(), SolverOperationError> {
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::CreaseStiffness {
+                    crease_index,
+                    stiffness,
+                })
+                .map_err(Into::into)
+        }
+
+        /// Set the mass of a single node, which must be finite and strictly positive
+        pub fn set_node_mass(
+            &mut self,
+            node_index: u32,
+            mass: f32,
+        ) -> Result<(), SolverOperationError> {
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::NodeMass { node_index, mass })
+                .map_err(Into::into)
+        }
+
+        /// Set whether a single node is fixed in place
+        pub fn set_node_fixed(
+            &mut self,
+            node_index: u32,
+            fixed: bool,
+        ) -> Result<(), SolverOperationError> {
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::NodeFixed { node_index, fixed })
+                .map_err(Into::into)
+        }
+
+        /// Set the acceleration applied to every node, scaled by its mass (zero by default).
+        /// It is kept for the models loaded afterwards.
+        pub fn set_gravity(&mut self, x: f32, y: f32, z: f32) -> Result<(), SolverOperationError> {
+            self.inner
+                .set_parameter(rtori_core::family::Parameter::Gravity(
+                    rtori_core::model::Vector3F([x, y, z]),
+                ))
+                .map_err(Into::into)
         }
     }
 
@@ -131,7 +381,10 @@ pub mod ffi {
     }
 
     impl<'ctx> Solver<'ctx> {
-        pub fn extract<'a>(&self, request: &mut ExtractBuilder<'a>) {
+        pub fn extract<'a>(
+            &self,
+            request: &mut ExtractBuilder<'a>,
+        ) -> Result<(), SolverOperationError> {
             let extract_flags = rtori_core::model::ExtractFlags::from_bits_truncate(
                 0 | if request.position.as_ref().map(|p| p.0.len()).unwrap_or(0) > 0 {
                     rtori_core::model::ExtractFlags::POSITION.bits()
@@ -148,26 +401,25 @@ pub mod ffi {
                 },
             );
 
-            {
-                use rtori_core::model::ExtractorDyn as _;
-                let extractor = self.inner.extract(extract_flags).unwrap();
+            self.inner
+                .extract(extract_flags, &mut |extractor| {
+                    if let Some((out, offset)) = request.position.as_mut() {
+                        let out: &mut [rtori_core::model::Vector3F] = bytemuck::cast_slice_mut(out);
 
-                if let Some((out, offset)) = request.position.as_mut() {
-                    let out: &mut [rtori_core::model::Vector3F] = bytemuck::cast_slice_mut(out);
+                        extractor.copy_node_position(out, *offset);
+                    }
 
-                    extractor.copy_node_position(out, *offset);
-                }
+                    if let Some((out, offset)) = request.velocity.as_mut() {
+                        let out: &mut [rtori_core::model::Vector3F] = bytemuck::cast_slice_mut(out);
 
-                if let Some((out, offset)) = request.velocity.as_mut() {
-                    let out: &mut [rtori_core::model::Vector3F] = bytemuck::cast_slice_mut(out);
+                        extractor.copy_node_velocity(out, *offset);
+                    }
 
-                    extractor.copy_node_velocity(out, *offset);
-                }
-
-                if let Some((out, offset)) = request.error.as_mut() {
-                    extractor.copy_node_error(out, *offset);
-                }
-            }
+                    if let Some((out, offset)) = request.error.as_mut() {
+                        extractor.copy_node_error(out, *offset);
+                    }
+                })
+                .map_err(Into::into)
         }
     }
 }
@@ -5,13 +5,20 @@ use criterion::{criterion_group, criterion_main, Criterion};
 
 extern crate alloc;
 use pollster::FutureExt as _;
+use rtori_core::os_solver::LaneWidth;
 use rtori_os_fold_importer::{import_in, supplement::transform_in};
 
 const SIMPLE_FOLD: &'static str = include_str!("../../fold/testdata/simple.fold");
 const THIRTEEN_HORNS_FOLD: &'static str =
     include_str!("../../fold/testdata/13-horns-123-vertices-augmented-triangulated.fold");
 
-fn bench_source(c: &mut Criterion, name: &str, fold_str: &str, step_count: u32) {
+fn bench_source(
+    c: &mut Criterion,
+    name: &str,
+    fold_str: &str,
+    step_count: u32,
+    lane_width: LaneWidth,
+) {
     let parsed_input = serde_json::from_str::<fold::File>(fold_str)
         .expect("source deserialization (json/fold file) failed");
 
@@ -20,8 +27,11 @@ fn bench_source(c: &mut Criterion, name: &str, fold_str: &str, step_count: u32)
         rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
             .block_on()
             .unwrap();
+    solver.set_lane_width(lane_width).unwrap();
 
-    solver.load_fold_in(&parsed_input.key_frame, allocator);
+    solver
+        .load_fold_in(&parsed_input.key_frame, allocator)
+        .unwrap();
 
     let mut group = c.benchmark_group("stepping");
     group.sample_size(500);
@@ -38,6 +48,7 @@ fn simple_benchmark(c: &mut Criterion) {
             &format!("step_simple_{STEP_COUNT}_step"),
             SIMPLE_FOLD,
             STEP_COUNT,
+            LaneWidth::detect(),
         );
     }
     {
@@ -47,9 +58,28 @@ fn simple_benchmark(c: &mut Criterion) {
             &format!("step_thirteen_horns_{STEP_COUNT}_step"),
             THIRTEEN_HORNS_FOLD,
             STEP_COUNT,
+            LaneWidth::detect(),
         );
     }
 }
 
-criterion_group!(benches, simple_benchmark);
+/// Compares the lane widths supported by the running processor, e.g. to check that the wider ones do use
+/// the wider registers (X8 and X16 should then step faster than X4)
+fn lane_width_benchmark(c: &mut Criterion) {
+    const STEP_COUNT: u32 = 1;
+    for lane_width in LaneWidth::ALL.into_iter().filter(|w| w.is_supported()) {
+        bench_source(
+            c,
+            &format!(
+                "step_thirteen_horns_{STEP_COUNT}_step_x{}",
+                lane_width.lane_count()
+            ),
+            THIRTEEN_HORNS_FOLD,
+            STEP_COUNT,
+            lane_width,
+        );
+    }
+}
+
+criterion_group!(benches, simple_benchmark, lane_width_benchmark);
 criterion_main!(benches);
@@ -0,0 +1,356 @@
+//! Solver families, and the [`Solver`] trait through which any of them can be driven.
+//!
+//! Adding a family means implementing [`Solver`] for it, and registering it in [`Family`].
+use alloc::boxed::Box;
+use core::alloc::Allocator;
+
+use bitflags::bitflags;
+
+use crate::os_solver::BackendFlags;
+pub use crate::os_solver::LoadError;
+
+/// The registry of the solver families that can be created
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Family {
+    /// Origami Simulator by Amanda Ghaessi: a dynamic bar-and-hinge model, see [`crate::os_solver`]
+    OrigamiSimulator,
+    /// A kinematic solver for rigid origami: faces are rigid panels rotated about the creases, see [`crate::rigid_solver`]
+    RigidOrigami,
+    /// The static equilibrium of the Origami Simulator model, found with Newton-Raphson iterations, see [`crate::static_solver`]
+    StaticEquilibrium,
+}
+
+impl Family {
+    /// Every registered family
+    pub const ALL: &'static [Family] = &[
+        Family::OrigamiSimulator,
+        Family::RigidOrigami,
+        Family::StaticEquilibrium,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::OrigamiSimulator => "Origami Simulator",
+            Self::RigidOrigami => "Rigid Origami",
+            Self::StaticEquilibrium => "Static Equilibrium",
+        }
+    }
+
+    /// What the solvers of the family support, whatever their backend
+    pub const fn capabilities(self) -> Capabilities {
+        match self {
+            Self::OrigamiSimulator => Capabilities::STEP
+                .union(Capabilities::EXTRACT_VELOCITY)
+                .union(Capabilities::EXTRACT_ERROR)
+                .union(Capabilities::FOLD_PERCENTAGE)
+                .union(Capabilities::FACE_STIFFNESS)
+                .union(Capabilities::CREASE_TARGET_FOLD_ANGLE)
+                .union(Capabilities::GRAVITY)
+                .union(Capabilities::NODE_EXTERNAL_FORCES)
+                .union(Capabilities::NODE_CONSTRAINTS)
+                .union(Capabilities::CREASE_STIFFNESS)
+                .union(Capabilities::NODE_MASS)
+                .union(Capabilities::NODE_FIXED),
+            Self::RigidOrigami => Capabilities::EXTRACT_ERROR
+                .union(Capabilities::FOLD_PERCENTAGE)
+                .union(Capabilities::CREASE_TARGET_FOLD_ANGLE),
+            Self::StaticEquilibrium => Capabilities::EXTRACT_ERROR
+                .union(Capabilities::FOLD_PERCENTAGE)
+                .union(Capabilities::FACE_STIFFNESS)
+                .union(Capabilities::CREASE_TARGET_FOLD_ANGLE)
+                .union(Capabilities::GRAVITY)
+                .union(Capabilities::NODE_EXTERNAL_FORCES)
+                .union(Capabilities::CREASE_STIFFNESS)
+                .union(Capabilities::NODE_MASS)
+                .union(Capabilities::NODE_FIXED),
+        }
+    }
+
+    /// Creates a solver of the family running on one of the given backends,
+    /// with the solver and its buffers allocated in `allocator`
+    pub async fn create_in<A>(
+        self,
+        backends: BackendFlags,
+        allocator: A,
+    ) -> Result<Box<dyn Solver, A>, CreationError>
+    where
+        A: Allocator + Clone + core::fmt::Debug + 'static,
+    {
+        match self {
+            Self::OrigamiSimulator => {
+                let solver = crate::os_solver::Solver::create_in(backends, allocator.clone())
+                    .await
+                    .map_err(|()| CreationError::NoBackendMatching)?;
+                Ok(Box::new_in(solver, allocator))
+            }
+            Self::RigidOrigami => {
+                // Only runs on the CPU, on a single thread
+                if !backends.intersects(BackendFlags::CPU_ANY) {
+                    return Err(CreationError::NoBackendMatching);
+                }
+                let solver = crate::rigid_solver::Solver::new_in(allocator.clone());
+                Ok(Box::new_in(solver, allocator))
+            }
+            Self::StaticEquilibrium => {
+                // Only runs on the CPU, on a single thread
+                if !backends.intersects(BackendFlags::CPU_ANY) {
+                    return Err(CreationError::NoBackendMatching);
+                }
+                let solver = crate::static_solver::Solver::new_in(allocator.clone());
+                Ok(Box::new_in(solver, allocator))
+            }
+        }
+    }
+}
+
+bitflags! {
+    /// What a solver supports beyond loading a model and extracting the node positions
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    #[repr(C)]
+    pub struct Capabilities: u32 {
+        /// The solver has an intermediary state, which is advanced by [`Solver::step`]
+        const STEP = 1 << 0;
+        const EXTRACT_VELOCITY = 1 << 1;
+        const EXTRACT_ERROR = 1 << 2;
+
+        /// [`Parameter::FoldPercentage`]
+        const FOLD_PERCENTAGE = 1 << 8;
+        /// [`Parameter::FaceStiffness`]
+        const FACE_STIFFNESS = 1 << 9;
+        /// [`Parameter::CreaseTargetFoldAngle`]
+        const CREASE_TARGET_FOLD_ANGLE = 1 << 10;
+        /// [`Parameter::Gravity`]
+        const GRAVITY = 1 << 11;
+        /// [`Parameter::NodeExternalForces`]
+        const NODE_EXTERNAL_FORCES = 1 << 12;
+        /// [`Parameter::NodeConstraint`] and [`Parameter::ClearNodeConstraints`]
+        const NODE_CONSTRAINTS = 1 << 13;
+        /// [`Parameter::CreaseStiffness`]
+        const CREASE_STIFFNESS = 1 << 14;
+        /// [`Parameter::NodeMass`]
+        const NODE_MASS = 1 << 15;
+        /// [`Parameter::NodeFixed`]
+        const NODE_FIXED = 1 << 16;
+    }
+}
+
+/// A parameter of the loaded model, changed without reloading it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Parameter<'a> {
+    /// How far the creases are folded towards their target fold angle, from 0 to 1
+    FoldPercentage(f32),
+    /// The stiffness pulling each face's angles back towards their nominal values
+    FaceStiffness(f32),
+    /// The angle (in radians) a single crease is folded towards at a fold percentage of 1
+    CreaseTargetFoldAngle { crease_index: u32, angle: f32 },
+    /// The acceleration applied to every node, scaled by its mass, kept across loads
+    Gravity(rtori_os_model::Vector3F),
+    /// The external forces applied to the nodes starting at `offset`
+    NodeExternalForces {
+        forces: &'a [rtori_os_model::Vector3F],
+        offset: u32,
+    },
+    /// Attaches a node to a target position (replacing its previous constraint), or detaches it with `None`
+    NodeConstraint {
+        node_index: u32,
+        constraint: Option<rtori_os_model::NodeConstraint>,
+    },
+    /// Detaches every node from its target
+    ClearNodeConstraints,
+    /// The stiffness of a single crease, finite and positive
+    CreaseStiffness { crease_index: u32, stiffness: f32 },
+    /// The mass of a single node, finite and strictly positive
+    NodeMass { node_index: u32, mass: f32 },
+    /// Whether a single node is fixed in place
+    NodeFixed { node_index: u32, fixed: bool },
+}
+
+impl Parameter<'_> {
+    /// The capability a solver needs to support the parameter
+    pub const fn capability(&self) -> Capabilities {
+        match self {
+            Self::FoldPercentage(_) => Capabilities::FOLD_PERCENTAGE,
+            Self::FaceStiffness(_) => Capabilities::FACE_STIFFNESS,
+            Self::CreaseTargetFoldAngle { .. } => Capabilities::CREASE_TARGET_FOLD_ANGLE,
+            Self::Gravity(_) => Capabilities::GRAVITY,
+            Self::NodeExternalForces { .. } => Capabilities::NODE_EXTERNAL_FORCES,
+            Self::NodeConstraint { .. } | Self::ClearNodeConstraints => {
+                Capabilities::NODE_CONSTRAINTS
+            }
+            Self::CreaseStiffness { .. } => Capabilities::CREASE_STIFFNESS,
+            Self::NodeMass { .. } => Capabilities::NODE_MASS,
+            Self::NodeFixed { .. } => Capabilities::NODE_FIXED,
+        }
+    }
+}
+
+/// A solver of any family, as created by [`Family::create_in`]
+pub trait Solver: core::fmt::Debug + core::any::Any {
+    fn family(&self) -> Family;
+
+    fn capabilities(&self) -> Capabilities {
+        self.family().capabilities()
+    }
+
+    /// Loads a model, replacing the current one.
+    /// When loading fails, the previously loaded model (if any) is kept.
+    fn load_fold(&mut self, frame: &fold::FrameCore) -> Result<(), LoadError>;
+
+    /// The number of nodes of the loaded model, if there is one
+    fn node_count(&self) -> Option<u32>;
+
+    /// For each node of the loaded model, the vertex of the FOLD frame it comes from,
+    /// or `None` when they're the same (when the frame has neither cut nor join edges, or nothing is loaded)
+    fn node_sources(&self) -> Option<&[fold::VertexIndex]>;
+
+    /// Copies the vertex of the FOLD frame each node comes from, starting at node `offset`.
+    ///
+    /// Cuts split vertices into several nodes and joins weld them into one,
+    /// so that the results of the nodes can be mapped back to the vertices of the frame.
+    fn copy_node_source_vertex(
+        &self,
+        to: &mut [fold::VertexIndex],
+        offset: u32,
+    ) -> Result<(), OperationError> {
+        let count = self.node_count().ok_or(OperationError::NotLoaded)?;
+        let end = offset as usize + to.len();
+        if end > count as usize {
+            return Err(OperationError::OutOfRange {
+                index: end as u32 - 1,
+                count,
+            });
+        }
+
+        match self.node_sources() {
+            Some(sources) => to.copy_from_slice(&sources[offset as usize..end]),
+            None => to
+                .iter_mut()
+                .zip(offset..)
+                .for_each(|(vertex, node)| *vertex = node),
+        }
+        Ok(())
+    }
+
+    /// Advances the solver by the given number of steps (see [`Capabilities::STEP`])
+    fn step(&mut self, step_count: u32) -> Result<(), OperationError>;
+
+    /// Brings the model back to its state just after loading, keeping the parameters
+    fn reset(&mut self) -> Result<(), OperationError>;
+
+    fn set_parameter(&mut self, parameter: Parameter<'_>) -> Result<(), OperationError>;
+
+    /// Calls `with` with an extractor of the loaded model, able to copy out what `extract_flags` asks for
+    fn extract(
+        &self,
+        extract_flags: rtori_os_model::ExtractFlags,
+        with: &mut dyn FnMut(&dyn rtori_os_model::ExtractorDyn<'_>),
+    ) -> Result<(), OperationError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreationError {
+    /// None of the given backends is available for the family
+    NoBackendMatching,
+}
+
+impl core::fmt::Display for CreationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NoBackendMatching => write!(f, "no backend matching the given flags"),
+        }
+    }
+}
+
+impl core::error::Error for CreationError {}
+
+/// Why an operation on a [`Solver`] failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationError {
+    NotLoaded,
+    /// The solver doesn't have the capability the operation requires
+    Unsupported(Capabilities),
+    /// The element at `index` doesn't exist, as there are only `count` of them
+    OutOfRange {
+        index: u32,
+        count: u32,
+    },
+    /// Stepping failed, at the given step of the call
+    StepFailed {
+        local_step_number: u32,
+    },
+    /// No equilibrium was found for the new parameters
+    NotConverged,
+    /// The value is outside of the domain of the parameter (such as a mass that isn't strictly positive)
+    InvalidValue,
+}
+
+impl core::fmt::Display for OperationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotLoaded => write!(f, "no model is loaded"),
+            Self::Unsupported(capabilities) => {
+                write!(f, "the solver doesn't support {capabilities:?}")
+            }
+            Self::OutOfRange { index, count } => {
+                write!(
+                    f,
+                    "element {index} doesn't exist, as there are only {count} of them"
+                )
+            }
+            Self::StepFailed { local_step_number } => {
+                write!(f, "step #{local_step_number} failed")
+            }
+            Self::NotConverged => write!(f, "no equilibrium was found"),
+            Self::InvalidValue => write!(f, "the value is outside of the domain of the parameter"),
+        }
+    }
+}
+
+impl core::error::Error for OperationError {}
+
+impl From<crate::os_solver::StepError> for OperationError {
+    fn from(error: crate::os_solver::StepError) -> Self {
+        match error {
+            crate::os_solver::StepError::NotLoaded => Self::NotLoaded,
+            crate::os_solver::StepError::Other { local_step_number } => {
+                Self::StepFailed { local_step_number }
+            }
+        }
+    }
+}
+
+impl From<crate::os_solver::ParameterError> for OperationError {
+    fn from(error: crate::os_solver::ParameterError) -> Self {
+        match error {
+            crate::os_solver::ParameterError::NotLoaded => Self::NotLoaded,
+            crate::os_solver::ParameterError::OutOfRange { index, count } => {
+                Self::OutOfRange { index, count }
+            }
+            crate::os_solver::ParameterError::InvalidValue => Self::InvalidValue,
+        }
+    }
+}
+
+impl From<crate::os_solver::ExtractError> for OperationError {
+    fn from(error: crate::os_solver::ExtractError) -> Self {
+        match error {
+            crate::os_solver::ExtractError::NotLoaded => Self::NotLoaded,
+        }
+    }
+}
+
+/// Copies the vertex each node of the imported model comes from (see [`Solver::node_sources`]) in `allocator`
+pub(crate) fn node_sources_in<SA, A>(
+    supplement: &crate::fold_importer::supplement::FoldSupplement<SA>,
+    allocator: A,
+) -> Option<alloc::vec::Vec<fold::VertexIndex, A>>
+where
+    SA: Allocator,
+    A: Allocator,
+{
+    supplement.vertices_source().map(|sources| {
+        let mut copied = alloc::vec::Vec::with_capacity_in(sources.len(), allocator);
+        copied.extend_from_slice(sources);
+        copied
+    })
+}
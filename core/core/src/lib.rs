@@ -3,33 +3,18 @@
 
 use core::alloc::Allocator;
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+pub mod family;
 pub mod os_solver;
+pub mod rigid_solver;
+pub mod static_solver;
 
 pub use fold;
 
 pub use rtori_os_fold_importer as fold_importer;
 pub use rtori_os_model as model;
 
-pub enum SolverKind {
-    OS(os_solver::Solver),
-}
-
-pub enum SolverPreprocessedData<'a, A>
-where
-    A: Allocator,
-{
-    /// Preprocessing data for the Origami Simulator family of solvers
-    OS(fold_importer::supplement::SupplementedInput<'a, A>),
-}
-
-pub struct Solver<'ctx, A>
-where
-    A: Allocator,
-{
-    context: &'ctx Context<A>,
-    inner: SolverKind,
-}
-
 pub struct Context<A> {
     allocator: A,
 }
@@ -40,11 +25,31 @@ impl<A: Allocator> Context<A> {
     }
 }
 
-impl<A> Context<A> {
+impl<A> Context<A>
+where
+    A: Allocator + Clone + 'static,
+{
+    /// Creates a solver whose buffers are allocated in the context's allocator
     pub async fn create_os_solver(
         &self,
         backends: os_solver::BackendFlags,
-    ) -> Result<os_solver::Solver, ()> {
-        os_solver::Solver::create(backends).await
+    ) -> Result<os_solver::Solver<A>, ()> {
+        os_solver::Solver::create_in(backends, self.allocator.clone()).await
+    }
+
+    /// Creates a solver of the given family, allocated (with its buffers) in the context's allocator
+    pub async fn create_solver(
+        &self,
+        family: family::Family,
+        backends: os_solver::BackendFlags,
+    ) -> Result<alloc::boxed::Box<dyn family::Solver, A>, family::CreationError>
+    where
+        A: core::fmt::Debug,
+    {
+        family.create_in(backends, self.allocator.clone()).await
+    }
+
+    pub fn allocator(&self) -> &A {
+        &self.allocator
     }
 }
@@ -2,8 +2,6 @@ use core::alloc::Allocator;
 
 #[cfg(feature = "gpu")]
 use rtori_core_wgpu as os_wgpu;
-#[cfg(feature = "cpu")]
-use rtori_os_simd as os_cpu;
 
 use bitflags::bitflags;
 pub use rtori_os_fold_importer as fold_importer;
@@ -13,6 +11,7 @@ bitflags! {
     #[repr(C)]
     pub struct BackendFlags: u8 {
         const CPU = 1 << 0;
+        const CPU_MT = 1 << 1;
 
         const GPU_METAL = 1 << 3;
         const GPU_VULKAN = 1 << 4;
@@ -20,7 +19,8 @@ bitflags! {
         const GPU_WEBGPU = 1 << 6;
 
         const GPU_ANY = BackendFlags::GPU_METAL.bits() | BackendFlags::GPU_VULKAN.bits() | BackendFlags::GPU_DX12.bits() | BackendFlags::GPU_WEBGPU.bits();
-        const ANY = BackendFlags::GPU_ANY.bits() | BackendFlags::CPU.bits();
+        const CPU_ANY = BackendFlags::CPU.bits() | BackendFlags::CPU_MT.bits();
+        const ANY = BackendFlags::GPU_ANY.bits() | BackendFlags::CPU_ANY.bits();
     }
 }
 
@@ -30,119 +30,255 @@ impl Default for BackendFlags {
     }
 }
 
+/// How the integration timestep of the solver is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TimeStep {
+    /// The timestep is computed from the model at load time, from the natural frequency of its beams
+    #[default]
+    Automatic,
+    /// The given timestep is used, whatever the model
+    Fixed(f32),
+}
+
+#[cfg(feature = "cpu")]
+mod cpu;
+#[cfg(feature = "cpu")]
+pub use cpu::{LaneWidth, Residuals};
+
+mod snapshot;
+pub use snapshot::RestoreError;
+
+mod timing;
+pub use timing::Clock;
+#[cfg(feature = "std")]
+pub use timing::StdClock;
+
+/// When [`Solver::solve_until`] stops stepping.
+///
+/// Equilibrium is reached once every threshold given is met, those left to `None` aren't checked.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolveCriteria {
+    /// The maximum number of steps to do
+    pub max_steps: u32,
+    /// The highest speed a node may have at equilibrium
+    pub max_velocity: Option<f32>,
+    /// The longest distance a node may travel during the last step at equilibrium
+    pub max_displacement: Option<f32>,
+    /// The highest kinetic energy the model may have at equilibrium
+    pub max_kinetic_energy: Option<f32>,
+    /// The maximum wall-clock time to spend stepping, as measured by the clock given to [`Solver::solve_until`]
+    pub time_budget: Option<core::time::Duration>,
+}
+
+impl SolveCriteria {
+    /// Criteria only limiting the number of steps, to be completed with thresholds
+    pub const fn with_max_steps(max_steps: u32) -> Self {
+        Self {
+            max_steps,
+            max_velocity: None,
+            max_displacement: None,
+            max_kinetic_energy: None,
+            time_budget: None,
+        }
+    }
+
+    /// Whether the given residuals are at equilibrium.
+    /// Without any threshold, equilibrium is never reached.
+    pub fn is_met(&self, residuals: &Residuals) -> bool {
+        let thresholds = [
+            (self.max_velocity, residuals.max_velocity),
+            (self.max_displacement, residuals.max_displacement),
+            (self.max_kinetic_energy, residuals.kinetic_energy),
+        ];
+
+        thresholds.iter().any(|(threshold, _)| threshold.is_some())
+            && thresholds
+                .iter()
+                .all(|(threshold, value)| threshold.is_none_or(|threshold| *value <= threshold))
+    }
+}
+
+/// Why [`Solver::solve_until`] stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// Every threshold of the criteria was met
+    Converged,
+    /// The maximum number of steps was done before converging
+    MaxSteps,
+    /// The wall-clock budget was spent before converging
+    TimeBudget,
+}
+
+/// What [`Solver::solve_until`] did
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolveReport {
+    /// The number of steps done
+    pub steps: u32,
+    /// The residuals after the last step
+    pub residuals: Residuals,
+    pub stop_reason: StopReason,
+}
+
+/// What [`Solver::step_for`] did
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimedStepReport {
+    /// The number of steps done
+    pub steps: u32,
+    /// The simulated time (in seconds) requested but not covered by the steps done, to be carried over to the next call.
+    /// It is negative when the last step went past the requested time.
+    pub remaining_simulated_time: f32,
+    /// The wall-clock time a step is estimated to take, learned from the previous steps
+    pub step_cost: core::time::Duration,
+}
+
 #[derive(Debug)]
-pub enum Solver {
+enum Backend<A>
+where
+    A: Allocator + 'static,
+{
     #[cfg(feature = "cpu")]
-    CPU(
-        Option<
-            os_cpu::owned::OwnedRunner<
-                'static,
-                { os_cpu::MIN_LANE_COUNT_32 },
-                alloc::alloc::Global,
-            >,
-        >,
-    ),
+    CPU(Option<cpu::CPURunner<A>>),
+    /// Multi-threaded CPU backend, splitting each stage between the threads of the pool, spawned once with the backend
+    #[cfg(feature = "cpu_mt")]
+    CPUMt(Option<cpu::CPURunner<A>>, rayon::ThreadPool),
     #[cfg(feature = "gpu")]
     GPU(GPURunner),
 }
 
+/// A solver of the Origami Simulator family, whose buffers are allocated in `A`
+#[derive(Debug)]
+pub struct Solver<A = alloc::alloc::Global>
+where
+    A: Allocator + 'static,
+{
+    backend: Backend<A>,
+    /// Where the buffers of the loaded models are allocated
+    allocator: A,
+    time_step: TimeStep,
+    /// The timestep computed for the currently loaded model, used with [`TimeStep::Automatic`]
+    automatic_dt: Option<f32>,
+    /// The lane width used by the CPU backends for the next loads
+    lane_width: LaneWidth,
+    /// The wall-clock cost of a step of the currently loaded model, used by [`Solver::step_for`]
+    step_cost: timing::StepCost,
+    /// The size of the currently loaded model, without the padding of the backend
+    model_size: Option<rtori_os_model::ModelSize>,
+    /// The acceleration applied to every node, scaled by its mass, kept across loads
+    gravity: rtori_os_model::Vector3F,
+    /// For each node of the currently loaded model, the vertex of the FOLD frame it comes from (see [`Solver::node_sources`])
+    node_sources: Option<alloc::vec::Vec<fold::VertexIndex, A>>,
+}
+
 impl Solver {
     pub async fn create(backends: BackendFlags) -> Result<Self, ()> {
-        #[cfg(feature = "gpu")]
-        if backends.intersects(BackendFlags::GPU_ANY) {
-            let wgpu_backends = if backends.contains(BackendFlags::GPU_ANY) {
-                os_wgpu::wgpu::Backends::all()
-            } else {
-                let mut output = os_wgpu::wgpu::Backends::empty();
-                #[cfg(feature = "dx12")]
-                if backends.contains(BackendFlags::GPU_DX12) {
-                    output |= os_wgpu::wgpu::Backends::DX12;
-                }
-                #[cfg(feature = "vulkan")]
-                if backends.contains(BackendFlags::GPU_VULKAN) {
-                    output |= os_wgpu::wgpu::Backends::VULKAN;
-                }
-                #[cfg(feature = "metal")]
-                if backends.contains(BackendFlags::GPU_METAL) {
-                    output |= os_wgpu::wgpu::Backends::METAL;
-                }
-                #[cfg(feature = "webgpu")]
-                if backends.contains(BackendFlags::GPU_WEBGPU) {
-                    output |= os_wgpu::wgpu::Backends::BROWSER_WEBGPU;
-                }
-                output
-            };
-
-            return GPURunner::create(wgpu_backends)
-                .await
-                .map(|inner| Self::GPU(inner));
-        }
-
-        #[cfg(feature = "cpu")]
-        if backends.intersects(BackendFlags::CPU) {
-            return Ok(Solver::CPU(None));
-        }
-
-        // Invalid
-        Err(())
+        Self::create_in(backends, alloc::alloc::Global).await
     }
+}
 
-    pub fn load_preprocessed_in<I, PA, A>(
+impl<A> Solver<A>
+where
+    A: Allocator + Clone + 'static,
+{
+    /// Creates a solver allocating the buffers of the models it loads in `allocator`
+    pub async fn create_in(backends: BackendFlags, allocator: A) -> Result<Self, ()> {
+        Backend::create(backends).await.map(|backend| Self {
+            backend,
+            allocator,
+            time_step: TimeStep::default(),
+            automatic_dt: None,
+            lane_width: LaneWidth::detect(),
+            step_cost: timing::StepCost::default(),
+            model_size: None,
+            gravity: rtori_os_model::Vector3F::default(),
+            node_sources: None,
+        })
+    }
+    /// Loads an already preprocessed model, replacing the current one.
+    /// When loading fails, the previously loaded model (if any) is kept.
+    ///
+    /// The temporary allocations of the import are done in `allocator`, the buffers of the model in the solver's.
+    pub fn load_preprocessed_in<I, PA, SA>(
         &mut self,
         preprocessed: &fold_importer::InputWithCreaseGeometry<'_, I, PA>,
-        allocator: A,
-    ) where
+        allocator: SA,
+    ) -> Result<(), LoadError>
+    where
         I: fold_importer::input::ImportInput,
         PA: Allocator,
-        A: Allocator + Clone,
+        SA: Allocator + Clone,
     {
-        match self {
-            Self::CPU(runner) => {
-                let size = preprocessed.compute_size();
-                let mut owned_runner = os_cpu::owned::OwnedRunner::with_size(&size);
-                {
-                    let runner = owned_runner.runner_mut();
-                    let mut loader = os_cpu::Loader::new(runner);
-                    preprocessed
-                        .load(&mut loader, Default::default(), allocator)
-                        .unwrap();
-                }
-                *runner = Some(owned_runner);
-            }
-            _ => unimplemented!(),
+        let config = fold_importer::ImportConfig::default();
+        let slot = match &mut self.backend {
+            #[cfg(feature = "cpu")]
+            Backend::CPU(runner) => runner,
+            #[cfg(feature = "cpu_mt")]
+            Backend::CPUMt(runner, _) => runner,
+            #[allow(unreachable_patterns)]
+            _ => return Err(LoadError::UnsupportedBackend),
         };
+        let runner = slot.insert(cpu::CPURunner::load(
+            self.lane_width,
+            preprocessed,
+            config,
+            self.allocator.clone(),
+            allocator,
+        )?);
+
+        self.automatic_dt = runner.compute_dt();
+        self.apply_time_step();
+        self.apply_gravity();
+        self.step_cost = timing::StepCost::default();
+        self.model_size = Some(preprocessed.compute_size());
+        self.node_sources = None;
+        Ok(())
     }
 
-    pub fn load_transformed_in<IA, A>(
+    pub fn load_transformed_in<IA, SA>(
         &mut self,
         transformed: &fold_importer::supplement::SupplementedInput<'_, IA>,
-        allocator: A,
-    ) where
+        allocator: SA,
+    ) -> Result<(), LoadError>
+    where
         IA: Allocator,
-        A: Allocator + Clone,
+        SA: Allocator + Clone,
     {
         let preprocessed =
-            fold_importer::InputWithCreaseGeometry::process(transformed, allocator.clone())
-                .unwrap();
+            fold_importer::InputWithCreaseGeometry::process(transformed, allocator.clone())?;
+
+        self.load_preprocessed_in(&preprocessed, allocator)?;
+        self.node_sources =
+            crate::family::node_sources_in(transformed.transformed, self.allocator.clone());
+        Ok(())
+    }
 
-        self.load_preprocessed_in(&preprocessed, allocator)
+    /// For each node of the loaded model, the vertex of the FOLD frame it comes from,
+    /// or `None` when they're the same (as the frame has neither cut nor join edges).
+    ///
+    /// Models loaded with [`Solver::load_preprocessed_in`] are assumed to match their source.
+    pub fn node_sources(&self) -> Option<&[fold::VertexIndex]> {
+        self.node_sources.as_deref()
     }
 
-    pub fn load_fold_in<A: Allocator>(&mut self, fold: &fold::FrameCore, allocator: A)
+    pub fn load_fold_in<SA>(
+        &mut self,
+        fold: &fold::FrameCore,
+        allocator: SA,
+    ) -> Result<(), LoadError>
     where
-        A: Allocator + Clone,
+        SA: Allocator + Clone,
     {
-        let transformed = rtori_os_fold_importer::supplement::transform_in(fold, allocator.clone())
-            .expect("Transformation into importation input failed");
+        let transformed =
+            rtori_os_fold_importer::supplement::transform_in(fold, allocator.clone())?;
 
         let transformed_input = transformed.with_fold(fold);
 
-        self.load_transformed_in(&transformed_input, allocator);
+        self.load_transformed_in(&transformed_input, allocator)
     }
 
     pub fn step(&mut self, step_count: u32) -> Result<(), StepError> {
-        match self {
-            Self::CPU(runner) => {
+        match &mut self.backend {
+            #[cfg(feature = "cpu")]
+            Backend::CPU(runner) => {
                 let runner = runner.as_mut().ok_or(StepError::NotLoaded)?;
                 (0..step_count).try_for_each(|step_number| {
                     runner.step().map_err(|_| StepError::Other {
@@ -150,28 +286,504 @@ impl Solver {
                     })
                 })
             }
+            #[cfg(feature = "cpu_mt")]
+            Backend::CPUMt(runner, pool) => {
+                let runner = runner.as_mut().ok_or(StepError::NotLoaded)?;
+                (0..step_count).try_for_each(|step_number| {
+                    runner.step_mt(pool).map_err(|_| StepError::Other {
+                        local_step_number: step_number,
+                    })
+                })
+            }
+            #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
     }
 
+    /// Brings the loaded model back to its rest state, as it was just after loading, without importing it again.
+    /// The parameters (such as the fold percentage) are kept.
+    pub fn reset(&mut self) -> Result<(), StepError> {
+        self.backend
+            .cpu_runner_mut()
+            .ok_or(StepError::NotLoaded)
+            .map(|runner| runner.reset())
+    }
+
+    /// Steps until equilibrium is reached, or until a limit of `criteria` is hit.
+    ///
+    /// The time budget of `criteria` is measured by `clock`, which isn't read when there is none.
+    pub fn solve_until<C: Clock>(
+        &mut self,
+        criteria: SolveCriteria,
+        clock: C,
+    ) -> Result<SolveReport, StepError> {
+        let budget = criteria
+            .time_budget
+            .map(|time_budget| (time_budget, clock.now()));
+
+        let mut report = SolveReport {
+            steps: 0,
+            residuals: self.residuals().map_err(|_| StepError::NotLoaded)?,
+            stop_reason: StopReason::MaxSteps,
+        };
+
+        while report.steps < criteria.max_steps {
+            if budget.is_some_and(|(time_budget, start)| {
+                clock.now().saturating_sub(start) >= time_budget
+            }) {
+                report.stop_reason = StopReason::TimeBudget;
+                return Ok(report);
+            }
+
+            self.step(1).map_err(|error| match error {
+                StepError::Other { .. } => StepError::Other {
+                    local_step_number: report.steps,
+                },
+                error => error,
+            })?;
+            report.steps += 1;
+            report.residuals = self.residuals().map_err(|_| StepError::NotLoaded)?;
+
+            if criteria.is_met(&report.residuals) {
+                report.stop_reason = StopReason::Converged;
+                return Ok(report);
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Runs as many steps as fit in `wall_time`, as measured by `clock`, without simulating more than `simulated_time` seconds.
+    /// Passing [`f32::INFINITY`] as `simulated_time` only bounds the stepping by the wall-clock time.
+    ///
+    /// The cost of a step is learned across calls, so that a step which would exceed `wall_time` isn't started.
+    pub fn step_for<C: Clock>(
+        &mut self,
+        wall_time: core::time::Duration,
+        simulated_time: f32,
+        clock: C,
+    ) -> Result<TimedStepReport, StepError> {
+        let start = clock.now();
+        let dt = self.dt().ok_or(StepError::NotLoaded)?;
+
+        let mut report = TimedStepReport {
+            steps: 0,
+            remaining_simulated_time: simulated_time,
+            step_cost: self.step_cost.estimate(),
+        };
+
+        // A step is done as long as it brings the simulated time closer to the requested one
+        while report.remaining_simulated_time >= dt / 2.0 {
+            let step_start = clock.now();
+            if step_start.saturating_sub(start) + self.step_cost.estimate() > wall_time {
+                break;
+            }
+
+            self.step(1).map_err(|error| match error {
+                StepError::Other { .. } => StepError::Other {
+                    local_step_number: report.steps,
+                },
+                error => error,
+            })?;
+            self.step_cost
+                .record(clock.now().saturating_sub(step_start));
+
+            report.steps += 1;
+            report.remaining_simulated_time -= dt;
+        }
+
+        report.step_cost = self.step_cost.estimate();
+        Ok(report)
+    }
+
+    /// The residuals of the last step, to know how far the model is from equilibrium
+    pub fn residuals(&self) -> Result<Residuals, ExtractError> {
+        self.backend
+            .cpu_runner()
+            .ok_or(ExtractError::NotLoaded)
+            .map(|runner| runner.residuals())
+    }
+
     pub fn extract(
         &self,
         extract_flags: rtori_os_model::ExtractFlags,
     ) -> Result<Extractor<'_>, ExtractError> {
-        match self {
-            Self::CPU(runner) => runner
-                .as_ref()
-                .ok_or(ExtractError::NotLoaded)
-                .map(|runner| Extractor::CPU(runner.extract(extract_flags))),
+        self.backend
+            .cpu_runner()
+            .ok_or(ExtractError::NotLoaded)
+            .map(|runner| runner.extract(extract_flags))
+    }
+
+    /// Captures the state of the simulation (the step count, the parameters and the memorable buffers) into a self-describing blob,
+    /// to be given back to [`Solver::restore`].
+    ///
+    /// The blob doesn't depend on the lane width, so that it can be restored by a solver running on another machine.
+    /// It doesn't contain the model itself, which has to be loaded before restoring.
+    pub fn snapshot(&self) -> Result<alloc::vec::Vec<u8>, ExtractError> {
+        let size = self.model_size.ok_or(ExtractError::NotLoaded)?;
+        let runner = self.backend.cpu_runner().ok_or(ExtractError::NotLoaded)?;
+
+        let mut current = snapshot::MemorableBuffers::with_size(&size);
+        let mut previous = snapshot::MemorableBuffers::with_size(&size);
+        for (buffer, half) in [
+            (cpu::StepBuffer::Current, &mut current),
+            (cpu::StepBuffer::Previous, &mut previous),
+        ] {
+            runner.copy_memorable_out(
+                buffer,
+                &mut half.node_position_offset,
+                &mut half.node_velocity,
+                &mut half.crease_fold_angle,
+            );
+        }
+        let mut parameters = snapshot::ParameterBuffers::with_size(&size);
+        runner.copy_parameters_out(parameters.as_slices_mut());
+
+        Ok(snapshot::Snapshot {
+            size,
+            lane_count: runner.lane_width().lane_count() as u32,
+            steps: runner.steps(),
+            fold_percentage: runner.fold_percentage(),
+            dt: runner.dt(),
+            face_stiffness: runner.face_stiffness(),
+            calculate_face_strain: runner.calculate_face_strain(),
+            gravity: self.gravity,
+            current,
+            previous,
+            parameters,
+        }
+        .encode())
+    }
+
+    /// Restores the state captured by [`Solver::snapshot`], from a solver of any lane width.
+    /// The model the snapshot was taken from must be loaded, its parameters and the gravity are then restored as well.
+    ///
+    /// The timestep of the snapshot is used until the next load or call to [`Solver::set_time_step`].
+    pub fn restore(&mut self, blob: &[u8]) -> Result<(), RestoreError> {
+        let loaded = self.model_size.ok_or(RestoreError::NotLoaded)?;
+        let snapshot = snapshot::Snapshot::decode(blob)?;
+        if snapshot.size != loaded {
+            return Err(RestoreError::SizeMismatch {
+                snapshot: snapshot.size,
+                loaded,
+            });
+        }
+
+        let runner = self
+            .backend
+            .cpu_runner_mut()
+            .ok_or(RestoreError::NotLoaded)?;
+
+        for (buffer, half) in [
+            (cpu::StepBuffer::Current, &snapshot.current),
+            (cpu::StepBuffer::Previous, &snapshot.previous),
+        ] {
+            runner.copy_memorable_in(
+                buffer,
+                &half.node_position_offset,
+                &half.node_velocity,
+                &half.crease_fold_angle,
+            );
         }
+
+        runner.copy_parameters_in(snapshot.parameters.as_slices());
+        runner.update_damping(fold_importer::ImportConfig::default().damping_percentage);
+        self.automatic_dt = runner.compute_dt();
+
+        runner.set_steps(snapshot.steps);
+        runner.set_fold_percentage(snapshot.fold_percentage);
+        runner.set_dt(snapshot.dt);
+        runner.set_face_stiffness(snapshot.face_stiffness);
+        runner.set_calculate_face_strain(snapshot.calculate_face_strain);
+
+        self.gravity = snapshot.gravity;
+        self.apply_gravity();
+
+        Ok(())
     }
 
     pub fn set_fold_percentage(&mut self, fold_percentage: f32) -> Result<(), ()> {
-        match self {
-            Self::CPU(runner) => runner
-                .as_mut()
-                .ok_or(())
-                .map(|runner| runner.set_fold_percentage(fold_percentage)),
+        self.backend
+            .cpu_runner_mut()
+            .ok_or(())
+            .map(|runner| runner.set_fold_percentage(fold_percentage))
+    }
+
+    /// Sets the stiffness of a single crease, without reloading the model.
+    /// The damping of the crease is derived again from its stiffness, as when loading.
+    ///
+    /// Fails with [`ParameterError::InvalidValue`] if the stiffness isn't finite and positive.
+    pub fn set_crease_stiffness(
+        &mut self,
+        crease_index: u32,
+        stiffness: f32,
+    ) -> Result<(), ParameterError> {
+        if !(stiffness.is_finite() && stiffness >= 0.0) {
+            return Err(ParameterError::InvalidValue);
+        }
+
+        self.set_parameter(
+            crease_index,
+            |size| size.creases,
+            |loader| loader.copy_crease_stiffness(&[stiffness], crease_index),
+        )?;
+        self.update_crease_derived_parameters(crease_index);
+        Ok(())
+    }
+
+    /// Sets the angle (in radians) a single crease is folded towards at a fold percentage of 1, without reloading the model
+    pub fn set_crease_target_fold_angle(
+        &mut self,
+        crease_index: u32,
+        target_fold_angle: f32,
+    ) -> Result<(), ParameterError> {
+        self.set_parameter(
+            crease_index,
+            |size| size.creases,
+            |loader| loader.copy_crease_target_fold_angle(&[target_fold_angle], crease_index),
+        )
+    }
+
+    /// Sets the mass of a single node, without reloading the model.
+    /// The damping of the beams & creases of that node and the automatic timestep are derived again from the masses, as when loading.
+    ///
+    /// Fails with [`ParameterError::InvalidValue`] if the mass isn't strictly positive.
+    pub fn set_node_mass(&mut self, node_index: u32, mass: f32) -> Result<(), ParameterError> {
+        if !(mass.is_finite() && mass > 0.0) {
+            return Err(ParameterError::InvalidValue);
+        }
+
+        self.set_parameter(
+            node_index,
+            |size| size.nodes,
+            |loader| loader.copy_node_mass(&[mass], node_index),
+        )?;
+        self.update_node_derived_parameters(node_index);
+        Ok(())
+    }
+
+    /// Sets whether a single node is fixed in place, without reloading the model
+    pub fn set_node_fixed(&mut self, node_index: u32, fixed: bool) -> Result<(), ParameterError> {
+        self.set_parameter(
+            node_index,
+            |size| size.nodes,
+            |loader| loader.copy_node_fixed(&[fixed], node_index),
+        )
+    }
+
+    /// Sets the external force applied to a single node, without reloading the model
+    pub fn set_node_external_force(
+        &mut self,
+        node_index: u32,
+        force: rtori_os_model::Vector3F,
+    ) -> Result<(), ParameterError> {
+        self.set_parameter(
+            node_index,
+            |size| size.nodes,
+            |loader| loader.copy_node_external_forces(&[force], node_index),
+        )
+    }
+
+    /// Sets the external forces applied to the nodes starting at `offset`, without reloading the model
+    pub fn set_node_external_forces(
+        &mut self,
+        forces: &[rtori_os_model::Vector3F],
+        offset: u32,
+    ) -> Result<(), ParameterError> {
+        // Checking the last node written covers the whole range
+        let last = u32::try_from(forces.len().saturating_sub(1))
+            .map_or(u32::MAX, |len| offset.saturating_add(len));
+        self.set_parameter(
+            last,
+            |size| size.nodes,
+            |loader| loader.copy_node_external_forces(forces, offset),
+        )
+    }
+
+    /// Attaches a node to a target position, replacing its previous constraint if any.
+    /// Constraints can be changed before every step, and are kept by [`Solver::reset`].
+    /// The stiffness of a spring is clamped to what the timestep can integrate, see [`rtori_os_model::NodeConstraint::Spring`].
+    pub fn set_node_constraint(
+        &mut self,
+        node_index: u32,
+        constraint: rtori_os_model::NodeConstraint,
+    ) -> Result<(), ParameterError> {
+        self.constrain_node(node_index, Some(constraint))
+    }
+
+    /// Detaches a node from its target, if it was attached to one
+    pub fn remove_node_constraint(&mut self, node_index: u32) -> Result<(), ParameterError> {
+        self.constrain_node(node_index, None)
+    }
+
+    /// Detaches every node from its target
+    pub fn clear_node_constraints(&mut self) -> Result<(), ParameterError> {
+        self.backend
+            .cpu_runner_mut()
+            .ok_or(ParameterError::NotLoaded)
+            .map(|runner| runner.clear_node_constraints())
+    }
+
+    fn constrain_node(
+        &mut self,
+        node_index: u32,
+        constraint: Option<rtori_os_model::NodeConstraint>,
+    ) -> Result<(), ParameterError> {
+        let count = self.model_size.ok_or(ParameterError::NotLoaded)?.nodes;
+        if node_index >= count {
+            return Err(ParameterError::OutOfRange {
+                index: node_index,
+                count,
+            });
+        }
+
+        self.backend
+            .cpu_runner_mut()
+            .ok_or(ParameterError::NotLoaded)
+            .map(|runner| runner.set_node_constraint(node_index, constraint))
+    }
+
+    /// Sets a parameter of the element at `index`, once checked against the element count given by `count`
+    fn set_parameter(
+        &mut self,
+        index: u32,
+        count: fn(&rtori_os_model::ModelSize) -> u32,
+        set: impl FnOnce(&mut dyn rtori_os_model::LoaderDyn<'_>),
+    ) -> Result<(), ParameterError> {
+        let count = count(&self.model_size.ok_or(ParameterError::NotLoaded)?);
+        if index >= count {
+            return Err(ParameterError::OutOfRange { index, count });
+        }
+
+        self.backend
+            .cpu_runner_mut()
+            .ok_or(ParameterError::NotLoaded)
+            .map(|runner| runner.with_loader(set))
+    }
+
+    /// Sets the stiffness pulling each face's angles back towards their nominal values.
+    /// It must be finite and positive (zero disabling the face forces).
+    pub fn set_face_stiffness(&mut self, face_stiffness: f32) -> Result<(), ParameterError> {
+        if !(face_stiffness.is_finite() && face_stiffness >= 0.0) {
+            return Err(ParameterError::InvalidValue);
+        }
+
+        self.backend
+            .cpu_runner_mut()
+            .ok_or(ParameterError::NotLoaded)
+            .map(|runner| runner.set_face_stiffness(face_stiffness))
+    }
+
+    /// Sets whether the extracted node error combines the face strain with the beam strain
+    pub fn set_calculate_face_strain(
+        &mut self,
+        calculate_face_strain: bool,
+    ) -> Result<(), ParameterError> {
+        self.backend
+            .cpu_runner_mut()
+            .ok_or(ParameterError::NotLoaded)
+            .map(|runner| runner.set_calculate_face_strain(calculate_face_strain))
+    }
+
+    /// The lane width used by the CPU backends: that of the loaded model if there is one,
+    /// otherwise the one that will be used for the next load
+    pub fn lane_width(&self) -> LaneWidth {
+        self.backend
+            .cpu_runner()
+            .map(|runner| runner.lane_width())
+            .unwrap_or(self.lane_width)
+    }
+
+    /// Sets the lane width used by the CPU backends for subsequent loads.
+    /// By default, the widest supported by the processor is used.
+    ///
+    /// Fails if the processor doesn't support the given width.
+    pub fn set_lane_width(&mut self, lane_width: LaneWidth) -> Result<(), ()> {
+        if !lane_width.is_supported() {
+            return Err(());
+        }
+
+        self.lane_width = lane_width;
+        Ok(())
+    }
+
+    pub fn time_step(&self) -> TimeStep {
+        self.time_step
+    }
+
+    /// Sets how the timestep is chosen.
+    /// It is applied immediately if a model is loaded, and kept for subsequent loads.
+    ///
+    /// Fails with [`ParameterError::InvalidValue`] if a fixed timestep isn't strictly positive.
+    pub fn set_time_step(&mut self, time_step: TimeStep) -> Result<(), ParameterError> {
+        if let TimeStep::Fixed(dt) = time_step {
+            if !(dt.is_finite() && dt > 0.0) {
+                return Err(ParameterError::InvalidValue);
+            }
+        }
+
+        self.time_step = time_step;
+        self.apply_time_step();
+        Ok(())
+    }
+
+    /// The timestep currently used for integration, if a model is loaded
+    pub fn dt(&self) -> Option<f32> {
+        self.backend.cpu_runner().map(|runner| runner.dt())
+    }
+
+    /// The acceleration applied to every node, scaled by its mass
+    pub fn gravity(&self) -> rtori_os_model::Vector3F {
+        self.gravity
+    }
+
+    /// Sets the acceleration applied to every node, scaled by its mass (zero by default).
+    /// It is applied immediately if a model is loaded, and kept for subsequent loads.
+    pub fn set_gravity(&mut self, gravity: rtori_os_model::Vector3F) {
+        self.gravity = gravity;
+        self.apply_gravity();
+    }
+
+    /// Derives the damping of a crease from its current stiffness, as when loading
+    fn update_crease_derived_parameters(&mut self, crease_index: u32) {
+        let config = fold_importer::ImportConfig::default();
+        if let Some(runner) = self.backend.cpu_runner_mut() {
+            runner.update_crease_damping(crease_index, config.damping_percentage);
+        }
+    }
+
+    /// Derives the damping of the beams & creases of a node and the automatic timestep from its current mass, as when loading
+    fn update_node_derived_parameters(&mut self, node_index: u32) {
+        let config = fold_importer::ImportConfig::default();
+        let Some(runner) = self.backend.cpu_runner_mut() else {
+            return;
+        };
+
+        runner.update_node_damping(node_index, config.damping_percentage);
+        self.automatic_dt = runner.compute_dt();
+        self.apply_time_step();
+    }
+
+    fn apply_gravity(&mut self) {
+        let gravity = self.gravity;
+        if let Some(runner) = self.backend.cpu_runner_mut() {
+            runner.set_gravity(gravity);
+        }
+    }
+
+    fn apply_time_step(&mut self) {
+        let dt = match self.time_step {
+            TimeStep::Fixed(dt) => Some(dt),
+            TimeStep::Automatic => self.automatic_dt,
+        };
+
+        // Without any timestep computed (for instance, when there are no beams), the backend's default is kept
+        let Some(dt) = dt else {
+            return;
+        };
+
+        if let Some(runner) = self.backend.cpu_runner_mut() {
+            runner.set_dt(dt);
         }
     }
 
@@ -181,15 +793,102 @@ impl Solver {
     }*/
 }
 
+impl<A> Backend<A>
+where
+    A: Allocator + 'static,
+{
+    /// The loaded runner of the CPU backends, whether single- or multi-threaded
+    fn cpu_runner(&self) -> Option<&cpu::CPURunner<A>> {
+        match self {
+            #[cfg(feature = "cpu")]
+            Self::CPU(runner) => runner.as_ref(),
+            #[cfg(feature = "cpu_mt")]
+            Self::CPUMt(runner, _) => runner.as_ref(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    fn cpu_runner_mut(&mut self) -> Option<&mut cpu::CPURunner<A>> {
+        match self {
+            #[cfg(feature = "cpu")]
+            Self::CPU(runner) => runner.as_mut(),
+            #[cfg(feature = "cpu_mt")]
+            Self::CPUMt(runner, _) => runner.as_mut(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    async fn create(backends: BackendFlags) -> Result<Self, ()> {
+        #[cfg(feature = "gpu")]
+        if backends.intersects(BackendFlags::GPU_ANY) {
+            let wgpu_backends = if backends.contains(BackendFlags::GPU_ANY) {
+                os_wgpu::wgpu::Backends::all()
+            } else {
+                let mut output = os_wgpu::wgpu::Backends::empty();
+                #[cfg(feature = "dx12")]
+                if backends.contains(BackendFlags::GPU_DX12) {
+                    output |= os_wgpu::wgpu::Backends::DX12;
+                }
+                #[cfg(feature = "vulkan")]
+                if backends.contains(BackendFlags::GPU_VULKAN) {
+                    output |= os_wgpu::wgpu::Backends::VULKAN;
+                }
+                #[cfg(feature = "metal")]
+                if backends.contains(BackendFlags::GPU_METAL) {
+                    output |= os_wgpu::wgpu::Backends::METAL;
+                }
+                #[cfg(feature = "webgpu")]
+                if backends.contains(BackendFlags::GPU_WEBGPU) {
+                    output |= os_wgpu::wgpu::Backends::BROWSER_WEBGPU;
+                }
+                output
+            };
+
+            return GPURunner::create(wgpu_backends)
+                .await
+                .map(|inner| Self::GPU(inner));
+        }
+
+        // The single-threaded backend comes first, so that the multi-threaded one is only chosen when asked for alone
+        #[cfg(feature = "cpu")]
+        if backends.intersects(BackendFlags::CPU) {
+            return Ok(Self::CPU(None));
+        }
+
+        #[cfg(feature = "cpu_mt")]
+        if backends.intersects(BackendFlags::CPU_MT) {
+            // One thread per available core
+            let pool = rayon::ThreadPoolBuilder::new().build().map_err(|_| ())?;
+            return Ok(Self::CPUMt(None, pool));
+        }
+
+        // Invalid
+        Err(())
+    }
+}
+
 pub enum Extractor<'borrow> {
-    CPU(rtori_os_simd::Extractor<'borrow, { rtori_os_simd::MIN_LANE_COUNT_32 }>),
+    CPUX4(rtori_os_simd::Extractor<'borrow, 4>),
+    CPUX8(rtori_os_simd::Extractor<'borrow, 8>),
+    CPUX16(rtori_os_simd::Extractor<'borrow, 16>),
+}
+
+/// Calls the same expression on the extractor, whatever its lane width
+macro_rules! dispatch_extractor {
+    ($self:expr, $inner:ident => $expr:expr) => {
+        match $self {
+            Extractor::CPUX4($inner) => $expr,
+            Extractor::CPUX8($inner) => $expr,
+            Extractor::CPUX16($inner) => $expr,
+        }
+    };
 }
 
 impl rtori_os_model::ExtractorDyn<'_> for Extractor<'_> {
     fn count_nodes(&self) -> usize {
-        match self {
-            Self::CPU(inner) => inner.count_nodes(),
-        }
+        dispatch_extractor!(self, inner => inner.count_nodes())
     }
 
     fn copy_node_position(
@@ -197,9 +896,7 @@ impl rtori_os_model::ExtractorDyn<'_> for Extractor<'_> {
         to: &mut [rtori_os_model::Vector3F],
         from: rtori_os_model::NodeIndex,
     ) -> bool {
-        match self {
-            Self::CPU(inner) => inner.copy_node_position(to, from),
-        }
+        dispatch_extractor!(self, inner => inner.copy_node_position(to, from))
     }
 
     fn copy_node_velocity(
@@ -207,23 +904,162 @@ impl rtori_os_model::ExtractorDyn<'_> for Extractor<'_> {
         to: &mut [rtori_os_model::Vector3F],
         from: rtori_os_model::NodeIndex,
     ) -> bool {
-        match self {
-            Self::CPU(inner) => inner.copy_node_velocity(to, from),
-        }
+        dispatch_extractor!(self, inner => inner.copy_node_velocity(to, from))
     }
 
     fn copy_node_error(&self, to: &mut [f32], from: rtori_os_model::NodeIndex) -> bool {
+        dispatch_extractor!(self, inner => inner.copy_node_error(to, from))
+    }
+}
+
+impl<A> crate::family::Solver for Solver<A>
+where
+    A: Allocator + Clone + core::fmt::Debug + 'static,
+{
+    fn family(&self) -> crate::family::Family {
+        crate::family::Family::OrigamiSimulator
+    }
+
+    fn load_fold(&mut self, frame: &fold::FrameCore) -> Result<(), LoadError> {
+        self.load_fold_in(frame, self.allocator.clone())
+    }
+
+    fn node_count(&self) -> Option<u32> {
+        self.model_size.map(|size| size.nodes)
+    }
+
+    fn node_sources(&self) -> Option<&[fold::VertexIndex]> {
+        Solver::node_sources(self)
+    }
+
+    fn step(&mut self, step_count: u32) -> Result<(), crate::family::OperationError> {
+        Solver::step(self, step_count).map_err(Into::into)
+    }
+
+    fn reset(&mut self) -> Result<(), crate::family::OperationError> {
+        Solver::reset(self).map_err(Into::into)
+    }
+
+    fn set_parameter(
+        &mut self,
+        parameter: crate::family::Parameter<'_>,
+    ) -> Result<(), crate::family::OperationError> {
+        use crate::family::{OperationError, Parameter};
+
+        match parameter {
+            Parameter::FoldPercentage(fold_percentage) => self
+                .set_fold_percentage(fold_percentage)
+                .map_err(|()| OperationError::NotLoaded),
+            Parameter::FaceStiffness(face_stiffness) => {
+                self.set_face_stiffness(face_stiffness).map_err(Into::into)
+            }
+            Parameter::CreaseTargetFoldAngle {
+                crease_index,
+                angle,
+            } => self
+                .set_crease_target_fold_angle(crease_index, angle)
+                .map_err(Into::into),
+            Parameter::Gravity(gravity) => {
+                self.set_gravity(gravity);
+                Ok(())
+            }
+            Parameter::NodeExternalForces { forces, offset } => self
+                .set_node_external_forces(forces, offset)
+                .map_err(Into::into),
+            Parameter::NodeConstraint {
+                node_index,
+                constraint,
+            } => self
+                .constrain_node(node_index, constraint)
+                .map_err(Into::into),
+            Parameter::ClearNodeConstraints => self.clear_node_constraints().map_err(Into::into),
+            Parameter::CreaseStiffness {
+                crease_index,
+                stiffness,
+            } => self
+                .set_crease_stiffness(crease_index, stiffness)
+                .map_err(Into::into),
+            Parameter::NodeMass { node_index, mass } => {
+                self.set_node_mass(node_index, mass).map_err(Into::into)
+            }
+            Parameter::NodeFixed { node_index, fixed } => {
+                self.set_node_fixed(node_index, fixed).map_err(Into::into)
+            }
+        }
+    }
+
+    fn extract(
+        &self,
+        extract_flags: rtori_os_model::ExtractFlags,
+        with: &mut dyn FnMut(&dyn rtori_os_model::ExtractorDyn<'_>),
+    ) -> Result<(), crate::family::OperationError> {
+        let extractor = Solver::extract(self, extract_flags)?;
+        with(&extractor);
+        Ok(())
+    }
+}
+
+/// Why a model couldn't be loaded
+#[derive(Debug, Clone)]
+pub enum LoadError {
+    /// The FOLD frame is missing required fields, or they are inconsistent
+    Transform(fold_importer::supplement::TransformError),
+    /// The creases couldn't be extracted from the model
+    Preprocessing(fold_importer::PreprocessingError),
+    /// The model couldn't be loaded into the backend
+    Import(fold_importer::ImportError),
+    /// The backend doesn't support loading models yet
+    UnsupportedBackend,
+}
+
+impl core::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::CPU(inner) => inner.copy_node_error(to, from),
+            Self::Transform(e) => write!(f, "invalid FOLD frame: {e}"),
+            Self::Preprocessing(e) => write!(f, "preprocessing error: {e}"),
+            Self::Import(e) => write!(f, "import error: {e}"),
+            Self::UnsupportedBackend => write!(f, "the backend doesn't support loading models"),
         }
     }
 }
 
+impl core::error::Error for LoadError {}
+
+impl From<fold_importer::supplement::TransformError> for LoadError {
+    fn from(error: fold_importer::supplement::TransformError) -> Self {
+        Self::Transform(error)
+    }
+}
+
+impl From<fold_importer::PreprocessingError> for LoadError {
+    fn from(error: fold_importer::PreprocessingError) -> Self {
+        Self::Preprocessing(error)
+    }
+}
+
+impl From<fold_importer::ImportError> for LoadError {
+    fn from(error: fold_importer::ImportError) -> Self {
+        Self::Import(error)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ExtractError {
     NotLoaded,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterError {
+    NotLoaded,
+    /// The element at `index` doesn't exist, as there are only `count` of them
+    OutOfRange {
+        index: u32,
+        count: u32,
+    },
+    /// The value is outside of the domain of the parameter (such as a mass or timestep that isn't strictly positive)
+    InvalidValue,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum StepError {
     NotLoaded,
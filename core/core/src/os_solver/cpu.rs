@@ -0,0 +1,210 @@
+//! The CPU runners, whose SIMD lane width is chosen at runtime
+
+use core::alloc::Allocator;
+
+pub use os_cpu::{LaneWidth, Parameters, ParametersMut, Residuals, StepBuffer};
+use rtori_os_simd as os_cpu;
+
+use super::fold_importer;
+
+type OwnedRunner<const L: usize, A> = os_cpu::owned::OwnedRunner<'static, L, A>;
+
+#[derive(Debug)]
+pub(super) enum CPURunner<A>
+where
+    A: Allocator + 'static,
+{
+    X4(OwnedRunner<4, A>),
+    X8(OwnedRunner<8, A>),
+    X16(OwnedRunner<16, A>),
+}
+
+/// Calls the same expression on the runner, whatever its lane width
+macro_rules! dispatch {
+    ($self:expr, $runner:ident => $expr:expr) => {
+        match $self {
+            CPURunner::X4($runner) => $expr,
+            CPURunner::X8($runner) => $expr,
+            CPURunner::X16($runner) => $expr,
+        }
+    };
+}
+
+impl<A> CPURunner<A>
+where
+    A: Allocator + 'static,
+{
+    /// Creates a runner of the given lane width, whose buffers are allocated in `allocator`,
+    /// and loads the preprocessed input into it (using `scratch_allocator` for the temporary allocations)
+    pub fn load<I, PA, SA>(
+        lane_width: LaneWidth,
+        preprocessed: &fold_importer::InputWithCreaseGeometry<'_, I, PA>,
+        config: fold_importer::ImportConfig,
+        allocator: A,
+        scratch_allocator: SA,
+    ) -> Result<Self, fold_importer::ImportError>
+    where
+        I: fold_importer::input::ImportInput,
+        PA: Allocator,
+        SA: Allocator + Clone,
+    {
+        let size = preprocessed.compute_size();
+        let mut runner = match lane_width {
+            LaneWidth::X4 => Self::X4(OwnedRunner::with_size_in(&size, allocator)),
+            LaneWidth::X8 => Self::X8(OwnedRunner::with_size_in(&size, allocator)),
+            LaneWidth::X16 => Self::X16(OwnedRunner::with_size_in(&size, allocator)),
+        };
+
+        dispatch!(&mut runner, runner => {
+            let mut loader = os_cpu::Loader::new(runner.runner_mut());
+            preprocessed.load(&mut loader, config, scratch_allocator)?;
+        });
+
+        Ok(runner)
+    }
+
+    /// Gives access to the loaded model, to change some of its parameters
+    pub fn with_loader<R>(
+        &mut self,
+        f: impl FnOnce(&mut dyn rtori_os_model::LoaderDyn<'_>) -> R,
+    ) -> R {
+        dispatch!(self, runner => f(&mut os_cpu::Loader::new(runner.runner_mut())))
+    }
+
+    pub fn lane_width(&self) -> LaneWidth {
+        match self {
+            Self::X4(_) => LaneWidth::X4,
+            Self::X8(_) => LaneWidth::X8,
+            Self::X16(_) => LaneWidth::X16,
+        }
+    }
+
+    pub fn steps(&self) -> u64 {
+        dispatch!(self, runner => runner.steps())
+    }
+
+    pub fn set_steps(&mut self, steps: u64) {
+        dispatch!(self, runner => runner.set_steps(steps))
+    }
+
+    pub fn reset(&mut self) {
+        dispatch!(self, runner => runner.reset())
+    }
+
+    pub fn set_node_constraint(
+        &mut self,
+        node_index: u32,
+        constraint: Option<rtori_os_model::NodeConstraint>,
+    ) {
+        dispatch!(self, runner => runner.set_node_constraint(node_index, constraint))
+    }
+
+    pub fn clear_node_constraints(&mut self) {
+        dispatch!(self, runner => runner.clear_node_constraints())
+    }
+
+    pub fn copy_memorable_out(
+        &self,
+        buffer: StepBuffer,
+        node_position_offset: &mut [rtori_os_model::Vector3F],
+        node_velocity: &mut [rtori_os_model::Vector3F],
+        crease_fold_angle: &mut [f32],
+    ) {
+        dispatch!(self, runner => runner.copy_memorable_out(buffer, node_position_offset, node_velocity, crease_fold_angle))
+    }
+
+    pub fn copy_memorable_in(
+        &mut self,
+        buffer: StepBuffer,
+        node_position_offset: &[rtori_os_model::Vector3F],
+        node_velocity: &[rtori_os_model::Vector3F],
+        crease_fold_angle: &[f32],
+    ) {
+        dispatch!(self, runner => runner.copy_memorable_in(buffer, node_position_offset, node_velocity, crease_fold_angle))
+    }
+
+    pub fn copy_parameters_out(&self, to: ParametersMut<'_>) {
+        dispatch!(self, runner => runner.copy_parameters_out(to))
+    }
+
+    pub fn copy_parameters_in(&mut self, from: Parameters<'_>) {
+        dispatch!(self, runner => runner.copy_parameters_in(from))
+    }
+
+    pub fn step(&mut self) -> Result<(), ()> {
+        dispatch!(self, runner => runner.step_dispatched())
+    }
+
+    #[cfg(feature = "cpu_mt")]
+    pub fn step_mt(&mut self, pool: &rayon::ThreadPool) -> Result<(), ()> {
+        dispatch!(self, runner => runner.step_mt(pool))
+    }
+
+    pub fn extract(&self, extract_flags: rtori_os_model::ExtractFlags) -> super::Extractor<'_> {
+        match self {
+            Self::X4(runner) => super::Extractor::CPUX4(runner.extract(extract_flags)),
+            Self::X8(runner) => super::Extractor::CPUX8(runner.extract(extract_flags)),
+            Self::X16(runner) => super::Extractor::CPUX16(runner.extract(extract_flags)),
+        }
+    }
+
+    pub fn residuals(&self) -> Residuals {
+        dispatch!(self, runner => runner.residuals())
+    }
+
+    pub fn fold_percentage(&self) -> f32 {
+        dispatch!(self, runner => runner.fold_percentage())
+    }
+
+    pub fn set_fold_percentage(&mut self, fold_percentage: f32) {
+        dispatch!(self, runner => runner.set_fold_percentage(fold_percentage))
+    }
+
+    pub fn face_stiffness(&self) -> f32 {
+        dispatch!(self, runner => runner.face_stiffness())
+    }
+
+    pub fn set_face_stiffness(&mut self, face_stiffness: f32) {
+        dispatch!(self, runner => runner.set_face_stiffness(face_stiffness))
+    }
+
+    pub fn calculate_face_strain(&self) -> bool {
+        dispatch!(self, runner => runner.calculate_face_strain())
+    }
+
+    pub fn set_calculate_face_strain(&mut self, calculate_face_strain: bool) {
+        dispatch!(self, runner => runner.set_calculate_face_strain(calculate_face_strain))
+    }
+
+    pub fn gravity(&self) -> rtori_os_model::Vector3F {
+        dispatch!(self, runner => runner.gravity())
+    }
+
+    pub fn set_gravity(&mut self, gravity: rtori_os_model::Vector3F) {
+        dispatch!(self, runner => runner.set_gravity(gravity))
+    }
+
+    pub fn dt(&self) -> f32 {
+        dispatch!(self, runner => runner.dt())
+    }
+
+    pub fn compute_dt(&self) -> Option<f32> {
+        dispatch!(self, runner => runner.compute_dt())
+    }
+
+    pub fn update_damping(&mut self, damping_percentage: f32) {
+        dispatch!(self, runner => runner.update_damping(damping_percentage))
+    }
+
+    pub fn update_crease_damping(&mut self, crease_index: u32, damping_percentage: f32) {
+        dispatch!(self, runner => runner.update_crease_damping(crease_index, damping_percentage))
+    }
+
+    pub fn update_node_damping(&mut self, node_index: u32, damping_percentage: f32) {
+        dispatch!(self, runner => runner.update_node_damping(node_index, damping_percentage))
+    }
+
+    pub fn set_dt(&mut self, dt: f32) {
+        dispatch!(self, runner => runner.set_dt(dt))
+    }
+}
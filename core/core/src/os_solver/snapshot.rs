@@ -0,0 +1,382 @@
+//! The binary format of [`super::Solver::snapshot`]
+//!
+//! A snapshot is made of a header, followed by the memorable buffers stored per element (rather than per group of lanes),
+//! so that it can be restored whatever the lane width of the solver.
+//! All values are little-endian:
+//!
+//! - the magic `RTORISNP`, then the format version as a `u32`
+//! - the lane count of the solver the snapshot was taken from, as a `u32`
+//! - the [`ModelSize`], as six `u32` (nodes, creases, faces, node creases, node beams then node faces)
+//! - the number of steps done, as a `u64`
+//! - the fold percentage, the timestep and the face stiffness as `f32`, then whether to calculate the face strain as a `u8`
+//! - the gravity, as three `f32`
+//! - the node position offsets after the last step then before it, as three `f32` per node
+//! - the node velocities, laid out the same way
+//! - the crease fold angles after the last step then before it, as one `f32` per crease
+//! - the node masses as one `f32` per node, whether they are fixed as one `u8` per node, then their external forces
+//!   as three `f32` per node
+//! - the node constraint targets as three `f32` per node, then their stiffnesses as one `f32` per node
+//!   (zero when unconstrained, infinite when pinned)
+//! - the crease stiffnesses then target fold angles, as one `f32` per crease
+//! - the face stiffnesses, as one `f32` per face
+
+use alloc::vec::Vec;
+
+use rtori_os_model::{ModelSize, Vector3F};
+
+const MAGIC: [u8; 8] = *b"RTORISNP";
+const VERSION: u32 = 2;
+
+/// Why a snapshot couldn't be restored
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestoreError {
+    /// No model is loaded in the solver
+    NotLoaded,
+    /// The blob isn't a snapshot: it doesn't start with the expected magic, it is truncated or too long,
+    /// or its timestep, fold percentage, face stiffness or gravity is out of range
+    InvalidFormat,
+    /// The snapshot was written with the format version {version}, which isn't supported
+    UnsupportedVersion { version: u32 },
+    /// The snapshot was taken from a model of size {snapshot:?}, but the loaded one is of size {loaded:?}
+    SizeMismatch {
+        snapshot: ModelSize,
+        loaded: ModelSize,
+    },
+}
+
+impl core::fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotLoaded => write!(f, "no model is loaded in the solver"),
+            Self::InvalidFormat => write!(f, "the blob isn't a snapshot: it doesn't start with the expected magic, it is truncated or too long, or its parameters are out of range"),
+            Self::UnsupportedVersion { version }
+                => write!(f, "the snapshot was written with the format version {version}, which isn't supported"),
+            Self::SizeMismatch { snapshot, loaded }
+                => write!(f, "the snapshot was taken from a model of size {snapshot:?}, but the loaded one is of size {loaded:?}"),
+        }
+    }
+}
+impl core::error::Error for RestoreError {}
+
+/// One half of the buffers carried over from one step to the next
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct MemorableBuffers {
+    pub node_position_offset: Vec<Vector3F>,
+    pub node_velocity: Vec<Vector3F>,
+    pub crease_fold_angle: Vec<f32>,
+}
+
+impl MemorableBuffers {
+    pub fn with_size(size: &ModelSize) -> Self {
+        let mut output = Self::default();
+        output
+            .node_position_offset
+            .resize(size.nodes as usize, Vector3F::default());
+        output
+            .node_velocity
+            .resize(size.nodes as usize, Vector3F::default());
+        output.crease_fold_angle.resize(size.creases as usize, 0.0);
+        output
+    }
+}
+
+/// The per-element parameters that can be changed after loading
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct ParameterBuffers {
+    pub node_mass: Vec<f32>,
+    pub node_fixed: Vec<bool>,
+    pub node_external_forces: Vec<Vector3F>,
+    pub node_constraint_target: Vec<Vector3F>,
+    pub node_constraint_stiffness: Vec<f32>,
+    pub crease_k: Vec<f32>,
+    pub crease_target_fold_angle: Vec<f32>,
+    pub face_k: Vec<f32>,
+}
+
+impl ParameterBuffers {
+    pub fn with_size(size: &ModelSize) -> Self {
+        let (nodes, creases, faces) = (
+            size.nodes as usize,
+            size.creases as usize,
+            size.faces as usize,
+        );
+        let mut output = Self::default();
+        output.node_mass.resize(nodes, 0.0);
+        output.node_fixed.resize(nodes, false);
+        output
+            .node_external_forces
+            .resize(nodes, Vector3F::default());
+        output
+            .node_constraint_target
+            .resize(nodes, Vector3F::default());
+        output.node_constraint_stiffness.resize(nodes, 0.0);
+        output.crease_k.resize(creases, 0.0);
+        output.crease_target_fold_angle.resize(creases, 0.0);
+        output.face_k.resize(faces, 0.0);
+        output
+    }
+
+    pub fn as_slices(&self) -> super::cpu::Parameters<'_> {
+        super::cpu::Parameters {
+            node_mass: &self.node_mass,
+            node_fixed: &self.node_fixed,
+            node_external_forces: &self.node_external_forces,
+            node_constraint_target: &self.node_constraint_target,
+            node_constraint_stiffness: &self.node_constraint_stiffness,
+            crease_k: &self.crease_k,
+            crease_target_fold_angle: &self.crease_target_fold_angle,
+            face_k: &self.face_k,
+        }
+    }
+
+    pub fn as_slices_mut(&mut self) -> super::cpu::ParametersMut<'_> {
+        super::cpu::ParametersMut {
+            node_mass: &mut self.node_mass,
+            node_fixed: &mut self.node_fixed,
+            node_external_forces: &mut self.node_external_forces,
+            node_constraint_target: &mut self.node_constraint_target,
+            node_constraint_stiffness: &mut self.node_constraint_stiffness,
+            crease_k: &mut self.crease_k,
+            crease_target_fold_angle: &mut self.crease_target_fold_angle,
+            face_k: &mut self.face_k,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Snapshot {
+    pub size: ModelSize,
+    /// Only informative, as the buffers don't depend on it
+    pub lane_count: u32,
+    pub steps: u64,
+    pub fold_percentage: f32,
+    pub dt: f32,
+    pub face_stiffness: f32,
+    pub calculate_face_strain: bool,
+    pub gravity: Vector3F,
+    /// The values after the last step
+    pub current: MemorableBuffers,
+    /// The values before the last step
+    pub previous: MemorableBuffers,
+    pub parameters: ParameterBuffers,
+}
+
+impl Snapshot {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        let mut write = |bytes: &[u8]| output.extend_from_slice(bytes);
+
+        write(&MAGIC);
+        write(&VERSION.to_le_bytes());
+        write(&self.lane_count.to_le_bytes());
+        for count in size_fields(&self.size) {
+            write(&count.to_le_bytes());
+        }
+        write(&self.steps.to_le_bytes());
+        write(&self.fold_percentage.to_le_bytes());
+        write(&self.dt.to_le_bytes());
+        write(&self.face_stiffness.to_le_bytes());
+        write(&[u8::from(self.calculate_face_strain)]);
+        self.gravity.0.iter().for_each(|c| write(&c.to_le_bytes()));
+
+        let halves = [&self.current, &self.previous];
+        for offsets in halves.map(|half| &half.node_position_offset) {
+            offsets
+                .iter()
+                .flat_map(|offset| offset.0)
+                .for_each(|c| write(&c.to_le_bytes()));
+        }
+        for velocities in halves.map(|half| &half.node_velocity) {
+            velocities
+                .iter()
+                .flat_map(|velocity| velocity.0)
+                .for_each(|c| write(&c.to_le_bytes()));
+        }
+        for fold_angles in halves.map(|half| &half.crease_fold_angle) {
+            fold_angles.iter().for_each(|a| write(&a.to_le_bytes()));
+        }
+
+        let parameters = &self.parameters;
+        parameters
+            .node_mass
+            .iter()
+            .for_each(|mass| write(&mass.to_le_bytes()));
+        parameters
+            .node_fixed
+            .iter()
+            .for_each(|fixed| write(&[u8::from(*fixed)]));
+        for vectors in [
+            &parameters.node_external_forces,
+            &parameters.node_constraint_target,
+        ] {
+            vectors
+                .iter()
+                .flat_map(|vector| vector.0)
+                .for_each(|c| write(&c.to_le_bytes()));
+        }
+        for scalars in [
+            &parameters.node_constraint_stiffness,
+            &parameters.crease_k,
+            &parameters.crease_target_fold_angle,
+            &parameters.face_k,
+        ] {
+            scalars.iter().for_each(|v| write(&v.to_le_bytes()));
+        }
+
+        output
+    }
+
+    pub fn decode(blob: &[u8]) -> Result<Self, RestoreError> {
+        let mut reader = Reader(blob);
+
+        if reader.take::<8>()? != MAGIC {
+            return Err(RestoreError::InvalidFormat);
+        }
+
+        let version = reader.u32()?;
+        if version != VERSION {
+            return Err(RestoreError::UnsupportedVersion { version });
+        }
+
+        let lane_count = reader.u32()?;
+        let size = ModelSize {
+            nodes: reader.u32()?,
+            creases: reader.u32()?,
+            faces: reader.u32()?,
+            node_creases: reader.u32()?,
+            node_beams: reader.u32()?,
+            node_faces: reader.u32()?,
+        };
+        let steps = u64::from_le_bytes(reader.take()?);
+        let fold_percentage = reader.f32()?;
+        let dt = reader.f32()?;
+        let face_stiffness = reader.f32()?;
+        let calculate_face_strain = reader.bool()?;
+        let gravity = reader.vector3f()?;
+
+        // These are handed to the runner as-is, so a corrupted blob mustn't slip a NaN into the simulation
+        if !(dt.is_finite() && dt > 0.0)
+            || !fold_percentage.is_finite()
+            || !(face_stiffness.is_finite() && face_stiffness >= 0.0)
+            || !gravity.0.iter().all(|c| c.is_finite())
+        {
+            return Err(RestoreError::InvalidFormat);
+        }
+
+        // Checked beforehand, so that a corrupted size doesn't trigger a huge allocation
+        let (nodes, creases, faces) = (
+            u64::from(size.nodes),
+            u64::from(size.creases),
+            u64::from(size.faces),
+        );
+        let memorable_size = 2 * (nodes * 2 * 3 + creases) * 4;
+        let parameters_size = nodes * (4 + 1 + 3 * 4 + 3 * 4 + 4) + creases * 2 * 4 + faces * 4;
+        let expected_remaining = memorable_size + parameters_size;
+        if reader.0.len() as u64 != expected_remaining {
+            return Err(RestoreError::InvalidFormat);
+        }
+
+        let mut current = MemorableBuffers::with_size(&size);
+        let mut previous = MemorableBuffers::with_size(&size);
+        for half in [&mut current, &mut previous] {
+            for offset in half.node_position_offset.iter_mut() {
+                *offset = reader.vector3f()?;
+            }
+        }
+        for half in [&mut current, &mut previous] {
+            for velocity in half.node_velocity.iter_mut() {
+                *velocity = reader.vector3f()?;
+            }
+        }
+        for half in [&mut current, &mut previous] {
+            for fold_angle in half.crease_fold_angle.iter_mut() {
+                *fold_angle = reader.f32()?;
+            }
+        }
+
+        let mut parameters = ParameterBuffers::with_size(&size);
+        for mass in parameters.node_mass.iter_mut() {
+            *mass = reader.f32()?;
+        }
+        for fixed in parameters.node_fixed.iter_mut() {
+            *fixed = reader.bool()?;
+        }
+        for vectors in [
+            &mut parameters.node_external_forces,
+            &mut parameters.node_constraint_target,
+        ] {
+            for vector in vectors.iter_mut() {
+                *vector = reader.vector3f()?;
+            }
+        }
+        for scalars in [
+            &mut parameters.node_constraint_stiffness,
+            &mut parameters.crease_k,
+            &mut parameters.crease_target_fold_angle,
+            &mut parameters.face_k,
+        ] {
+            for scalar in scalars.iter_mut() {
+                *scalar = reader.f32()?;
+            }
+        }
+
+        Ok(Self {
+            size,
+            lane_count,
+            steps,
+            fold_percentage,
+            dt,
+            face_stiffness,
+            calculate_face_strain,
+            gravity,
+            current,
+            previous,
+            parameters,
+        })
+    }
+}
+
+fn size_fields(size: &ModelSize) -> [u32; 6] {
+    [
+        size.nodes,
+        size.creases,
+        size.faces,
+        size.node_creases,
+        size.node_beams,
+        size.node_faces,
+    ]
+}
+
+/// Reads the blob from the start, failing once it runs out
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], RestoreError> {
+        let (head, tail) = self
+            .0
+            .split_first_chunk::<N>()
+            .ok_or(RestoreError::InvalidFormat)?;
+        self.0 = tail;
+        Ok(*head)
+    }
+
+    fn u32(&mut self) -> Result<u32, RestoreError> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, RestoreError> {
+        self.take().map(f32::from_le_bytes)
+    }
+
+    fn bool(&mut self) -> Result<bool, RestoreError> {
+        match self.take::<1>()? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(RestoreError::InvalidFormat),
+        }
+    }
+
+    fn vector3f(&mut self) -> Result<Vector3F, RestoreError> {
+        Ok(Vector3F([self.f32()?, self.f32()?, self.f32()?]))
+    }
+}
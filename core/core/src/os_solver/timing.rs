@@ -0,0 +1,69 @@
+//! Wall-clock measurements for [`super::Solver::step_for`] and the time budget of [`super::Solver::solve_until`]
+
+use core::time::Duration;
+
+/// A monotonic source of time.
+/// It is provided by the host, so that time-budgeted stepping doesn't depend on the standard library.
+pub trait Clock {
+    /// The time elapsed since an arbitrary (but fixed) origin
+    fn now(&self) -> Duration;
+}
+
+/// A [`Clock`] based on [`std::time::Instant`]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct StdClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl StdClock {
+    pub fn new() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for StdClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+/// The wall-clock cost of a step, learned as a running average of the measured ones
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct StepCost(Option<Duration>);
+
+impl StepCost {
+    /// The weight of the previous estimate relative to a new measurement
+    const HISTORY_WEIGHT: u32 = 7;
+
+    /// The current estimate, zero until a step has been measured
+    pub fn estimate(&self) -> Duration {
+        self.0.unwrap_or(Duration::ZERO)
+    }
+
+    pub fn record(&mut self, measured: Duration) {
+        self.0 = Some(match self.0 {
+            None => measured,
+            Some(estimate) => {
+                (estimate * Self::HISTORY_WEIGHT + measured) / (Self::HISTORY_WEIGHT + 1)
+            }
+        });
+    }
+}
@@ -0,0 +1,790 @@
+//! A kinematic solver for rigid origami.
+//!
+//! The faces are rigid panels, placed by rotating each of them about a crease by its fold angle,
+//! starting from the root of a spanning tree of the faces.
+//! The creases left out of the tree close loops: when the fold angles aren't compatible with rigid folding,
+//! the faces around a loop disagree on where their nodes are, which is reported as the node error.
+//!
+//! Folds can be left free (see [`Solver::set_crease_driving`]),
+//! in which case their angle is solved for to close the loops, given those of the driving creases.
+use alloc::vec::Vec;
+use core::alloc::Allocator;
+
+use glam::{DAffine3, DVec3};
+
+use crate::family::{LoadError, OperationError};
+use crate::fold_importer;
+use rtori_os_model::Vector3F;
+
+/// The maximum number of iterations done to solve the angles of the free creases
+const MAX_ITERATIONS: usize = 64;
+/// The residual distance (in the units of the model) under which the loops are considered closed
+const CLOSURE_TOLERANCE: f64 = 1e-9;
+/// The damping of the Gauss-Newton iterations, relative to the diagonal of the normal equations
+const DAMPING: f64 = 1e-3;
+/// The largest change (in radians) of the angle of a free crease in a single iteration
+const MAX_ANGLE_CHANGE: f64 = 0.25;
+
+/// How the fold angle of a crease is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreaseKind {
+    /// A facet (or join), kept at its rest angle so that the faces around it make a rigid panel
+    Rigid,
+    /// A fold whose angle goes from its rest angle to its target angle as the fold percentage rises
+    Driving,
+    /// A fold whose angle is solved for to close the loops
+    Free,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Crease {
+    nodes: [u32; 2],
+    faces: [u32; 2],
+    kind: CreaseKind,
+    /// The fold angle of the crease in the loaded geometry
+    rest_fold_angle: f64,
+    target_fold_angle: f64,
+    fold_angle: f64,
+}
+
+/// How a face is placed relative to a neighbour, by rotating it about the crease they share
+#[derive(Debug, Clone, Copy)]
+struct Link {
+    /// The face the rotation is relative to
+    parent: u32,
+    crease: u32,
+    /// A point of the crease in the loaded geometry
+    point: DVec3,
+    /// The direction of the crease, oriented so that a positive rotation folds it towards valley
+    axis: DVec3,
+}
+
+impl Link {
+    /// The rotation of the face about the crease, in the loaded geometry
+    fn rotation(&self, crease: &Crease) -> DAffine3 {
+        DAffine3::from_translation(self.point)
+            * DAffine3::from_axis_angle(self.axis, crease.fold_angle - crease.rest_fold_angle)
+            * DAffine3::from_translation(-self.point)
+    }
+
+    /// The axis and a point of the crease once the parent face is placed by `parent`
+    fn placed(&self, parent: &DAffine3) -> (DVec3, DVec3) {
+        (
+            parent.matrix3 * self.axis,
+            parent.transform_point3(self.point),
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Model<A>
+where
+    A: Allocator,
+{
+    rest_positions: Vec<DVec3, A>,
+    faces: Vec<[u32; 3], A>,
+    creases: Vec<Crease, A>,
+    /// The faces, each after its parent in the spanning tree
+    order: Vec<u32, A>,
+    /// For every face, how it's placed relative to its parent in the spanning tree (none for the roots)
+    tree_links: Vec<Option<Link>, A>,
+    /// The creases left out of the spanning tree, relative to their first face
+    loop_links: Vec<Link, A>,
+    /// For every node, the first face placing it (none for the nodes outside of any face)
+    node_faces: Vec<Option<u32>, A>,
+    /// Where each face is moved from the loaded geometry
+    transforms: Vec<DAffine3, A>,
+    /// As with the Origami Simulator solvers, the offsets of the nodes from their loaded positions
+    position_offsets: Vec<Vector3F, A>,
+    errors: Vec<f32, A>,
+    /// For each node, the vertex of the FOLD frame it comes from (see [`Solver::node_sources`])
+    node_sources: Option<Vec<fold::VertexIndex, A>>,
+}
+
+/// A rigid origami solver, whose buffers are allocated in `A`
+#[derive(Debug)]
+pub struct Solver<A = alloc::alloc::Global>
+where
+    A: Allocator + 'static,
+{
+    allocator: A,
+    model: Option<Model<A>>,
+    /// How far the driving creases are folded from their rest angle towards their target, kept across loads
+    fold_percentage: f32,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self::new_in(alloc::alloc::Global)
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> Solver<A>
+where
+    A: Allocator + Clone + 'static,
+{
+    /// Creates a solver allocating the buffers of the models it loads in `allocator`
+    pub fn new_in(allocator: A) -> Self {
+        Self {
+            allocator,
+            model: None,
+            fold_percentage: 0.0,
+        }
+    }
+
+    /// Loads a model, replacing the current one.
+    /// When loading fails, the previously loaded model (if any) is kept.
+    ///
+    /// The temporary allocations of the import are done in `allocator`, the buffers of the model in the solver's.
+    pub fn load_fold_in<SA>(
+        &mut self,
+        fold: &fold::FrameCore,
+        allocator: SA,
+    ) -> Result<(), LoadError>
+    where
+        SA: Allocator + Clone,
+    {
+        let mut model = Model::load_in(fold, self.allocator.clone(), allocator)?;
+        model.reset(self.fold_percentage);
+        model.solve(self.fold_percentage);
+        self.model = Some(model);
+        Ok(())
+    }
+
+    /// Brings the free creases back to their angle just after loading, keeping the parameters
+    pub fn reset(&mut self) -> Result<(), OperationError> {
+        let model = self.model.as_mut().ok_or(OperationError::NotLoaded)?;
+        model.reset(self.fold_percentage);
+        model.solve(self.fold_percentage);
+        Ok(())
+    }
+
+    pub fn fold_percentage(&self) -> f32 {
+        self.fold_percentage
+    }
+
+    /// Sets how far the driving creases are folded, from their rest angle at 0 to their target angle at 1.
+    /// It is kept for subsequent loads.
+    pub fn set_fold_percentage(&mut self, fold_percentage: f32) {
+        self.fold_percentage = fold_percentage;
+        if let Some(model) = self.model.as_mut() {
+            model.solve(fold_percentage);
+        }
+    }
+
+    /// Sets the angle (in radians) a single crease is folded towards at a fold percentage of 1
+    pub fn set_crease_target_fold_angle(
+        &mut self,
+        crease_index: u32,
+        target_fold_angle: f32,
+    ) -> Result<(), OperationError> {
+        let fold_percentage = self.fold_percentage;
+        let model = self.model.as_mut().ok_or(OperationError::NotLoaded)?;
+        model.crease_mut(crease_index)?.target_fold_angle = f64::from(target_fold_angle);
+        model.solve(fold_percentage);
+        Ok(())
+    }
+
+    /// Sets whether the angle of a fold is driven by the fold percentage, or solved for to close the loops.
+    /// Every fold is driving after loading. Facets and joins stay rigid whatever is asked.
+    pub fn set_crease_driving(
+        &mut self,
+        crease_index: u32,
+        driving: bool,
+    ) -> Result<(), OperationError> {
+        let fold_percentage = self.fold_percentage;
+        let model = self.model.as_mut().ok_or(OperationError::NotLoaded)?;
+        let crease = model.crease_mut(crease_index)?;
+        crease.kind = match (crease.kind, driving) {
+            (CreaseKind::Rigid, _) => CreaseKind::Rigid,
+            (_, true) => CreaseKind::Driving,
+            (_, false) => CreaseKind::Free,
+        };
+        model.solve(fold_percentage);
+        Ok(())
+    }
+
+    pub fn crease_kind(&self, crease_index: u32) -> Option<CreaseKind> {
+        self.crease(crease_index).map(|crease| crease.kind)
+    }
+
+    /// The current fold angle (in radians) of a crease, positive towards valley
+    pub fn fold_angle(&self, crease_index: u32) -> Option<f32> {
+        self.crease(crease_index)
+            .map(|crease| crease.fold_angle as f32)
+    }
+
+    /// The number of nodes of the loaded model, if there is one
+    pub fn node_count(&self) -> Option<u32> {
+        self.model
+            .as_ref()
+            .map(|model| model.rest_positions.len() as u32)
+    }
+
+    /// For each node of the loaded model, the vertex of the FOLD frame it comes from,
+    /// or `None` when they're the same (as the frame has neither cut nor join edges)
+    pub fn node_sources(&self) -> Option<&[fold::VertexIndex]> {
+        self.model
+            .as_ref()
+            .and_then(|model| model.node_sources.as_deref())
+    }
+
+    /// The number of creases of the loaded model, if there is one
+    pub fn crease_count(&self) -> Option<u32> {
+        self.model.as_ref().map(|model| model.creases.len() as u32)
+    }
+
+    /// The largest distance between two placements of the same node by different faces,
+    /// which is zero when the loops close (up to the precision of the solver)
+    pub fn closure_error(&self) -> Option<f32> {
+        self.model
+            .as_ref()
+            .map(|model| model.errors.iter().copied().fold(0.0, f32::max))
+    }
+
+    pub fn extract(&self) -> Result<Extractor<'_>, OperationError> {
+        self.model
+            .as_ref()
+            .ok_or(OperationError::NotLoaded)
+            .map(|model| Extractor {
+                position_offsets: &model.position_offsets,
+                errors: &model.errors,
+            })
+    }
+
+    fn crease(&self, crease_index: u32) -> Option<&Crease> {
+        self.model
+            .as_ref()
+            .and_then(|model| model.creases.get(crease_index as usize))
+    }
+}
+
+impl<A> Model<A>
+where
+    A: Allocator + Clone,
+{
+    fn load_in<SA>(fold: &fold::FrameCore, allocator: A, scratch: SA) -> Result<Self, LoadError>
+    where
+        SA: Allocator + Clone,
+    {
+        use fold_importer::input::{FoldAssignment, ImportInput, Proxy};
+
+        let supplement = fold_importer::supplement::transform_in(fold, scratch.clone())?;
+        let input = supplement.with_fold(fold);
+
+        let vertices = input.vertices_coords();
+        let mut rest_positions = Vec::with_capacity_in(vertices.count(), allocator.clone());
+        rest_positions.extend(
+            vertices
+                .iter()
+                .map(|vertex| DVec3::from(vertex.map(f64::from))),
+        );
+
+        let faces_vertices = input.faces_vertices();
+        let mut faces = Vec::with_capacity_in(faces_vertices.count(), allocator.clone());
+        faces.extend(faces_vertices.iter());
+
+        let edges_vertices = input.edges_vertices();
+        let edges_assignment = input.edges_assignment();
+        let mut creases = Vec::new_in(allocator.clone());
+        for (crease_index, crease) in fold_importer::creases::extract_creases(&input).enumerate() {
+            let crease = crease.map_err(fold_importer::PreprocessingError::ExtractCreasesError)?;
+            let invalid_crease = fold_importer::PreprocessingError::InvalidCrease {
+                crease_index: crease_index as u32,
+                edge_index: crease.edge_index,
+            };
+            let nodes = edges_vertices
+                .get(crease.edge_index as usize)
+                .ok_or(invalid_crease)?;
+            let kind = match edges_assignment.get(crease.edge_index as usize) {
+                Some(FoldAssignment::Mountain | FoldAssignment::Valley) => CreaseKind::Driving,
+                Some(_) => CreaseKind::Rigid,
+                None => return Err(invalid_crease.into()),
+            };
+
+            let crease_faces = crease.faces.map(|face| face.face_index);
+            let rest_fold_angle = fold_angle(
+                &rest_positions,
+                crease_faces.map(|face_index| faces[face_index as usize]),
+                nodes,
+            );
+            creases.push(Crease {
+                nodes,
+                faces: crease_faces,
+                kind,
+                rest_fold_angle,
+                target_fold_angle: f64::from(crease.fold_angle),
+                fold_angle: rest_fold_angle,
+            });
+        }
+
+        // The creases around each face, to walk from face to face
+        let mut face_creases = Vec::with_capacity_in(faces.len(), scratch.clone());
+        face_creases.resize(faces.len(), Vec::new_in(scratch.clone()));
+        for (crease_index, crease) in creases.iter().enumerate() {
+            for face_index in crease.faces {
+                face_creases[face_index as usize].push(crease_index as u32);
+            }
+        }
+
+        // Breadth-first, so that the chains of rotations (and the errors they accumulate) stay short
+        let link = |crease_index: u32, side: usize| {
+            let crease = &creases[crease_index as usize];
+            let (point, axis) = fold_axis(
+                &rest_positions,
+                &faces[crease.faces[side] as usize],
+                crease.nodes,
+            );
+            Link {
+                parent: crease.faces[side],
+                crease: crease_index,
+                point,
+                axis,
+            }
+        };
+        let mut order = Vec::with_capacity_in(faces.len(), allocator.clone());
+        let mut tree_links = Vec::with_capacity_in(faces.len(), allocator.clone());
+        tree_links.resize(faces.len(), None);
+        let mut visited = Vec::with_capacity_in(faces.len(), scratch.clone());
+        visited.resize(faces.len(), false);
+        let mut in_tree = Vec::with_capacity_in(creases.len(), scratch.clone());
+        in_tree.resize(creases.len(), false);
+        for root in 0..faces.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            order.push(root as u32);
+
+            let mut next = order.len() - 1;
+            while let Some(&parent) = order.get(next) {
+                next += 1;
+                for &crease_index in &face_creases[parent as usize] {
+                    let crease = &creases[crease_index as usize];
+                    let side = usize::from(crease.faces[0] != parent);
+                    let child = crease.faces[1 - side] as usize;
+                    if visited[child] {
+                        continue;
+                    }
+                    visited[child] = true;
+                    in_tree[crease_index as usize] = true;
+                    tree_links[child] = Some(link(crease_index, side));
+                    order.push(child as u32);
+                }
+            }
+        }
+
+        let mut loop_links = Vec::new_in(allocator.clone());
+        loop_links.extend(
+            (0..creases.len() as u32)
+                .filter(|crease_index| !in_tree[*crease_index as usize])
+                .map(|crease_index| link(crease_index, 0)),
+        );
+
+        let mut node_faces = Vec::with_capacity_in(rest_positions.len(), allocator.clone());
+        node_faces.resize(rest_positions.len(), None);
+        for &face_index in &order {
+            for node in faces[face_index as usize] {
+                node_faces[node as usize].get_or_insert(face_index);
+            }
+        }
+
+        let mut transforms = Vec::with_capacity_in(faces.len(), allocator.clone());
+        transforms.resize(faces.len(), DAffine3::IDENTITY);
+        let mut position_offsets = Vec::with_capacity_in(rest_positions.len(), allocator.clone());
+        position_offsets.resize(rest_positions.len(), Vector3F::default());
+        let mut errors = Vec::with_capacity_in(rest_positions.len(), allocator.clone());
+        errors.resize(rest_positions.len(), 0.0);
+        let node_sources = crate::family::node_sources_in(&supplement, allocator);
+
+        Ok(Self {
+            rest_positions,
+            faces,
+            creases,
+            order,
+            tree_links,
+            loop_links,
+            node_faces,
+            transforms,
+            position_offsets,
+            errors,
+            node_sources,
+        })
+    }
+
+    fn crease_mut(&mut self, crease_index: u32) -> Result<&mut Crease, OperationError> {
+        let count = self.creases.len() as u32;
+        self.creases
+            .get_mut(crease_index as usize)
+            .ok_or(OperationError::OutOfRange {
+                index: crease_index,
+                count,
+            })
+    }
+
+    /// Brings the free creases to the angle they would have if they were driving, as a starting point for solving
+    fn reset(&mut self, fold_percentage: f32) {
+        for crease in self.creases.iter_mut() {
+            if crease.kind == CreaseKind::Free {
+                crease.fold_angle = driven_fold_angle(crease, fold_percentage);
+            }
+        }
+    }
+
+    /// Computes the angles of the creases for the given fold percentage, then places the faces and the nodes.
+    /// The free creases start from their current angle, so that they follow the driving ones as they move.
+    fn solve(&mut self, fold_percentage: f32) {
+        for crease in self.creases.iter_mut() {
+            match crease.kind {
+                CreaseKind::Rigid => crease.fold_angle = crease.rest_fold_angle,
+                CreaseKind::Driving => {
+                    crease.fold_angle = driven_fold_angle(crease, fold_percentage)
+                }
+                CreaseKind::Free => {}
+            }
+        }
+
+        self.solve_free_creases();
+        self.place_faces();
+        self.place_nodes();
+    }
+
+    /// Places the faces, each relative to its parent in the spanning tree
+    fn place_faces(&mut self) {
+        for &face_index in &self.order {
+            let face_index = face_index as usize;
+            self.transforms[face_index] = match &self.tree_links[face_index] {
+                None => DAffine3::IDENTITY,
+                Some(link) => {
+                    self.transforms[link.parent as usize]
+                        * link.rotation(&self.creases[link.crease as usize])
+                }
+            };
+        }
+    }
+
+    /// The position of a node, as placed by the first face it belongs to
+    fn node_position(&self, node_index: usize) -> DVec3 {
+        let rest = self.rest_positions[node_index];
+        self.node_faces[node_index].map_or(rest, |face_index| {
+            self.transforms[face_index as usize].transform_point3(rest)
+        })
+    }
+
+    /// Places each node with the first face it belongs to, and measures how far the others put it
+    fn place_nodes(&mut self) {
+        for node_index in 0..self.rest_positions.len() {
+            let offset = self.node_position(node_index) - self.rest_positions[node_index];
+            self.position_offsets[node_index] = Vector3F(offset.as_vec3().to_array());
+            self.errors[node_index] = 0.0;
+        }
+
+        for &face_index in &self.order {
+            let transform = &self.transforms[face_index as usize];
+            for node in self.faces[face_index as usize] {
+                let node = node as usize;
+                let placed = transform.transform_point3(self.rest_positions[node]);
+                let distance = placed.distance(self.node_position(node)) as f32;
+                self.errors[node] = self.errors[node].max(distance);
+            }
+        }
+    }
+
+    /// Solves the angles of the free creases so that the faces agree across the creases closing loops,
+    /// with damped Gauss-Newton iterations
+    fn solve_free_creases(&mut self) {
+        let allocator = self.faces.allocator().clone();
+
+        // The column of each free crease in the jacobian
+        let mut columns = Vec::with_capacity_in(self.creases.len(), allocator.clone());
+        let mut free_creases = Vec::new_in(allocator.clone());
+        for (crease_index, crease) in self.creases.iter().enumerate() {
+            if crease.kind == CreaseKind::Free {
+                columns.push(Some(free_creases.len()));
+                free_creases.push(crease_index);
+            } else {
+                columns.push(None);
+            }
+        }
+
+        let unknowns = free_creases.len();
+        // Three nodes per face, three coordinates per node
+        let rows = 9 * self.loop_links.len();
+        if unknowns == 0 || rows == 0 {
+            return;
+        }
+
+        let mut residuals = Vec::with_capacity_in(rows, allocator.clone());
+        let mut jacobian = Vec::with_capacity_in(rows * unknowns, allocator.clone());
+        let mut normal = Vec::with_capacity_in(unknowns * unknowns, allocator.clone());
+        let mut step = Vec::with_capacity_in(unknowns, allocator);
+        for _ in 0..MAX_ITERATIONS {
+            self.place_faces();
+
+            residuals.clear();
+            residuals.resize(rows, 0.0);
+            jacobian.clear();
+            jacobian.resize(rows * unknowns, 0.0);
+            for (loop_index, link) in self.loop_links.iter().enumerate() {
+                let crease = &self.creases[link.crease as usize];
+                let [parent, child] = crease.faces.map(|face_index| face_index as usize);
+                let by_parent = self.transforms[parent] * link.rotation(crease);
+                let by_child = self.transforms[child];
+
+                for (vertex_number, node) in self.faces[child].into_iter().enumerate() {
+                    let row = 9 * loop_index + 3 * vertex_number;
+                    let rest = self.rest_positions[node as usize];
+                    let from_child = by_child.transform_point3(rest);
+                    let from_parent = by_parent.transform_point3(rest);
+                    residuals[row..row + 3].copy_from_slice(&(from_child - from_parent).to_array());
+
+                    // Rotating about a crease moves the point around its (placed) axis
+                    let mut add =
+                        |crease_index: u32, axis: DVec3, point: DVec3, placed: DVec3, sign: f64| {
+                            if let Some(column) = columns[crease_index as usize] {
+                                let derivative = axis.cross(placed - point) * sign;
+                                for (axis_index, value) in
+                                    derivative.to_array().into_iter().enumerate()
+                                {
+                                    jacobian[(row + axis_index) * unknowns + column] += value;
+                                }
+                            }
+                        };
+                    for (face_index, placed, sign) in
+                        [(child, from_child, 1.0), (parent, from_parent, -1.0)]
+                    {
+                        let mut face_index = face_index;
+                        while let Some(tree_link) = &self.tree_links[face_index] {
+                            let (axis, point) =
+                                tree_link.placed(&self.transforms[tree_link.parent as usize]);
+                            add(tree_link.crease, axis, point, placed, sign);
+                            face_index = tree_link.parent as usize;
+                        }
+                    }
+                    let (axis, point) = link.placed(&self.transforms[parent]);
+                    add(link.crease, axis, point, from_parent, -1.0);
+                }
+            }
+
+            if residuals
+                .iter()
+                .all(|residual| residual.abs() < CLOSURE_TOLERANCE)
+            {
+                break;
+            }
+
+            // The normal equations, (JᵀJ + λ diag(JᵀJ)) δ = -Jᵀr
+            normal.clear();
+            normal.resize(unknowns * unknowns, 0.0);
+            step.clear();
+            step.resize(unknowns, 0.0);
+            for row in 0..rows {
+                let jacobian_row = &jacobian[row * unknowns..(row + 1) * unknowns];
+                for (i, value_i) in jacobian_row.iter().enumerate() {
+                    step[i] -= value_i * residuals[row];
+                    for (j, value_j) in jacobian_row.iter().enumerate() {
+                        normal[i * unknowns + j] += value_i * value_j;
+                    }
+                }
+            }
+            for i in 0..unknowns {
+                normal[i * unknowns + i] += DAMPING * normal[i * unknowns + i] + f64::EPSILON;
+            }
+            solve_linear(&mut normal, &mut step, unknowns);
+
+            for (column, crease_index) in free_creases.iter().enumerate() {
+                self.creases[*crease_index].fold_angle +=
+                    step[column].clamp(-MAX_ANGLE_CHANGE, MAX_ANGLE_CHANGE);
+            }
+        }
+    }
+}
+
+/// The angle of a driving crease at the given fold percentage
+fn driven_fold_angle(crease: &Crease, fold_percentage: f32) -> f64 {
+    crease.rest_fold_angle
+        + f64::from(fold_percentage) * (crease.target_fold_angle - crease.rest_fold_angle)
+}
+
+/// The node of a triangular face that isn't on the crease
+fn complement_vertex(face: &[u32; 3], crease_nodes: [u32; 2]) -> u32 {
+    face.iter()
+        .copied()
+        .find(|node| !crease_nodes.contains(node))
+        .unwrap_or(face[0])
+}
+
+fn face_normal(positions: &[DVec3], face: &[u32; 3]) -> DVec3 {
+    let [a, b, c] = face.map(|node| positions[node as usize]);
+    (b - a).cross(c - a).normalize_or_zero()
+}
+
+/// A point and the direction of a crease, oriented so that rotating the other face
+/// about it by a positive angle folds the crease towards valley, as seen from `face`
+fn fold_axis(positions: &[DVec3], face: &[u32; 3], crease_nodes: [u32; 2]) -> (DVec3, DVec3) {
+    let [a, b] = crease_nodes.map(|node| positions[node as usize]);
+    let direction = (b - a).normalize_or_zero();
+    let inwards = positions[complement_vertex(face, crease_nodes) as usize] - a;
+    if direction.cross(inwards).dot(face_normal(positions, face)) > 0.0 {
+        (a, -direction)
+    } else {
+        (a, direction)
+    }
+}
+
+/// The fold angle of a crease between two faces (positive towards valley, zero when flat)
+fn fold_angle(positions: &[DVec3], faces: [[u32; 3]; 2], crease_nodes: [u32; 2]) -> f64 {
+    let (_, axis) = fold_axis(positions, &faces[0], crease_nodes);
+    let [first, second] = faces.map(|face| face_normal(positions, &face));
+    libm::atan2(first.cross(second).dot(axis), first.dot(second))
+}
+
+/// Solves `matrix · x = rhs` in place (leaving `x` in `rhs`) by gaussian elimination with partial pivoting.
+/// Singular directions are left at zero.
+fn solve_linear(matrix: &mut [f64], rhs: &mut [f64], size: usize) {
+    for pivot in 0..size {
+        let best = (pivot..size)
+            .max_by(|a, b| {
+                matrix[a * size + pivot]
+                    .abs()
+                    .total_cmp(&matrix[b * size + pivot].abs())
+            })
+            .unwrap_or(pivot);
+        if best != pivot {
+            for column in 0..size {
+                matrix.swap(pivot * size + column, best * size + column);
+            }
+            rhs.swap(pivot, best);
+        }
+
+        let diagonal = matrix[pivot * size + pivot];
+        if diagonal.abs() < f64::EPSILON {
+            continue;
+        }
+        for row in pivot + 1..size {
+            let factor = matrix[row * size + pivot] / diagonal;
+            if factor == 0.0 {
+                continue;
+            }
+            for column in pivot..size {
+                matrix[row * size + column] -= factor * matrix[pivot * size + column];
+            }
+            rhs[row] -= factor * rhs[pivot];
+        }
+    }
+
+    for pivot in (0..size).rev() {
+        let diagonal = matrix[pivot * size + pivot];
+        if diagonal.abs() < f64::EPSILON {
+            rhs[pivot] = 0.0;
+            continue;
+        }
+        let sum: f64 = (pivot + 1..size)
+            .map(|column| matrix[pivot * size + column] * rhs[column])
+            .sum();
+        rhs[pivot] = (rhs[pivot] - sum) / diagonal;
+    }
+}
+
+/// Gives access to the positions of the nodes, and to how far the faces disagree on them
+#[derive(Debug, Clone, Copy)]
+pub struct Extractor<'a> {
+    position_offsets: &'a [Vector3F],
+    errors: &'a [f32],
+}
+
+fn copy_from<T: Copy>(source: &[T], to: &mut [T], from: rtori_os_model::NodeIndex) -> bool {
+    let Some(source) = source.get(from as usize..) else {
+        return false;
+    };
+    let count = to.len().min(source.len());
+    to[..count].copy_from_slice(&source[..count]);
+    true
+}
+
+impl rtori_os_model::ExtractorDyn<'_> for Extractor<'_> {
+    fn count_nodes(&self) -> usize {
+        self.position_offsets.len()
+    }
+
+    /// As with the Origami Simulator solvers, these are the offsets from the loaded positions
+    fn copy_node_position(&self, to: &mut [Vector3F], from: rtori_os_model::NodeIndex) -> bool {
+        copy_from(self.position_offsets, to, from)
+    }
+
+    /// A kinematic solver has no velocity
+    fn copy_node_velocity(&self, _to: &mut [Vector3F], _from: rtori_os_model::NodeIndex) -> bool {
+        false
+    }
+
+    /// The loop-closure error: the largest distance between two placements of the node by different faces
+    fn copy_node_error(&self, to: &mut [f32], from: rtori_os_model::NodeIndex) -> bool {
+        copy_from(self.errors, to, from)
+    }
+}
+
+impl<A> crate::family::Solver for Solver<A>
+where
+    A: Allocator + Clone + core::fmt::Debug + 'static,
+{
+    fn family(&self) -> crate::family::Family {
+        crate::family::Family::RigidOrigami
+    }
+
+    fn load_fold(&mut self, frame: &fold::FrameCore) -> Result<(), LoadError> {
+        self.load_fold_in(frame, self.allocator.clone())
+    }
+
+    fn node_count(&self) -> Option<u32> {
+        Solver::node_count(self)
+    }
+
+    fn node_sources(&self) -> Option<&[fold::VertexIndex]> {
+        Solver::node_sources(self)
+    }
+
+    fn step(&mut self, _step_count: u32) -> Result<(), OperationError> {
+        Err(OperationError::Unsupported(
+            crate::family::Capabilities::STEP,
+        ))
+    }
+
+    fn reset(&mut self) -> Result<(), OperationError> {
+        Solver::reset(self)
+    }
+
+    fn set_parameter(
+        &mut self,
+        parameter: crate::family::Parameter<'_>,
+    ) -> Result<(), OperationError> {
+        use crate::family::Parameter;
+
+        match parameter {
+            Parameter::FoldPercentage(fold_percentage) => {
+                self.set_fold_percentage(fold_percentage);
+                Ok(())
+            }
+            Parameter::CreaseTargetFoldAngle {
+                crease_index,
+                angle,
+            } => self.set_crease_target_fold_angle(crease_index, angle),
+            parameter => Err(OperationError::Unsupported(parameter.capability())),
+        }
+    }
+
+    fn extract(
+        &self,
+        _extract_flags: rtori_os_model::ExtractFlags,
+        with: &mut dyn FnMut(&dyn rtori_os_model::ExtractorDyn<'_>),
+    ) -> Result<(), OperationError> {
+        let extractor = Solver::extract(self)?;
+        with(&extractor);
+        Ok(())
+    }
+}
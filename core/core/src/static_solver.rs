@@ -0,0 +1,346 @@
+//! The static equilibrium of the Origami Simulator model, found by [`rtori_os_static`].
+//!
+//! There are no steps: the equilibrium is solved for whenever a parameter changes,
+//! starting from the previous one (the fold percentage being raised in increments).
+//! When no equilibrium is found, the model is left where the iterations stopped, and the next change starts from there.
+use alloc::vec::Vec;
+use core::alloc::Allocator;
+
+use crate::family::{Capabilities, LoadError, OperationError};
+use crate::fold_importer;
+use rtori_os_model::Vector3F;
+
+pub use rtori_os_static::{SolveConfig, SolveError, SolveReport};
+
+/// A static solver, whose buffers are allocated in `A`
+#[derive(Debug)]
+pub struct Solver<A = alloc::alloc::Global>
+where
+    A: Allocator + 'static,
+{
+    allocator: A,
+    model: Option<rtori_os_static::Solver<A>>,
+    /// For each node of the loaded model, the vertex of the FOLD frame it comes from (see [`Solver::node_sources`])
+    node_sources: Option<Vec<fold::VertexIndex, A>>,
+    /// The outcome of the last solve, if a model was loaded since
+    last_solve: Option<Result<SolveReport, SolveError>>,
+    config: SolveConfig,
+
+    /* Parameters, kept across loads */
+    fold_percentage: f32,
+    face_stiffness: f32,
+    gravity: Vector3F,
+}
+
+impl Solver {
+    pub fn new() -> Self {
+        Self::new_in(alloc::alloc::Global)
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> Solver<A>
+where
+    A: Allocator + Clone + 'static,
+{
+    /// Creates a solver allocating the buffers of the models it loads in `allocator`
+    pub fn new_in(allocator: A) -> Self {
+        Self {
+            allocator,
+            model: None,
+            node_sources: None,
+            last_solve: None,
+            config: SolveConfig::default(),
+            fold_percentage: 0.0,
+            face_stiffness: 1.0,
+            gravity: Vector3F::default(),
+        }
+    }
+
+    /// Loads a model, replacing the current one, and solves for its equilibrium at the current parameters.
+    /// When loading fails, the previously loaded model (if any) is kept.
+    ///
+    /// Not finding the equilibrium doesn't fail the load: see [`Solver::last_solve`].
+    /// The temporary allocations of the import are done in `allocator`, the buffers of the model in the solver's.
+    pub fn load_fold_in<SA>(
+        &mut self,
+        fold: &fold::FrameCore,
+        allocator: SA,
+    ) -> Result<(), LoadError>
+    where
+        SA: Allocator + Clone,
+    {
+        let transformed = fold_importer::supplement::transform_in(fold, allocator.clone())?;
+        let transformed_input = transformed.with_fold(fold);
+        let preprocessed =
+            fold_importer::InputWithCreaseGeometry::process(&transformed_input, allocator.clone())?;
+
+        let mut model = rtori_os_static::Solver::with_size_in(
+            &preprocessed.compute_size(),
+            self.allocator.clone(),
+        );
+        preprocessed.load(
+            &mut model.load(),
+            fold_importer::ImportConfig::default(),
+            allocator,
+        )?;
+        model.set_face_stiffness(self.face_stiffness);
+        model.set_gravity(self.gravity);
+
+        self.model = Some(model);
+        self.node_sources = crate::family::node_sources_in(&transformed, self.allocator.clone());
+        // Reported through `last_solve`
+        let _ = self.solve();
+        Ok(())
+    }
+
+    /// Brings the model back to its state just after loading, and solves for its equilibrium again
+    pub fn reset(&mut self) -> Result<(), OperationError> {
+        self.model
+            .as_mut()
+            .ok_or(OperationError::NotLoaded)?
+            .reset();
+        self.solve()
+    }
+
+    /// How the equilibrium is searched for, in the next solves
+    pub fn solve_config(&self) -> &SolveConfig {
+        &self.config
+    }
+
+    pub fn set_solve_config(&mut self, config: SolveConfig) {
+        self.config = config;
+    }
+
+    /// The outcome of the last solve, if a model is loaded
+    pub fn last_solve(&self) -> Option<&Result<SolveReport, SolveError>> {
+        self.last_solve.as_ref()
+    }
+
+    pub fn fold_percentage(&self) -> f32 {
+        self.fold_percentage
+    }
+
+    /// Sets how far the creases are folded towards their target fold angle, and solves for the new equilibrium.
+    /// It is kept for subsequent loads.
+    pub fn set_fold_percentage(&mut self, fold_percentage: f32) -> Result<(), OperationError> {
+        self.fold_percentage = fold_percentage;
+        self.solve()
+    }
+
+    /// Sets the stiffness pulling each face's angles back towards their nominal values, and solves for the new equilibrium.
+    /// It must be finite and positive, and is kept for subsequent loads.
+    pub fn set_face_stiffness(&mut self, face_stiffness: f32) -> Result<(), OperationError> {
+        if !(face_stiffness.is_finite() && face_stiffness >= 0.0) {
+            return Err(OperationError::InvalidValue);
+        }
+
+        self.face_stiffness = face_stiffness;
+        if let Some(model) = self.model.as_mut() {
+            model.set_face_stiffness(face_stiffness);
+        }
+        self.solve()
+    }
+
+    /// Sets the acceleration applied to every node (scaled by its mass), and solves for the new equilibrium.
+    /// It is kept for subsequent loads.
+    pub fn set_gravity(&mut self, gravity: Vector3F) -> Result<(), OperationError> {
+        self.gravity = gravity;
+        if let Some(model) = self.model.as_mut() {
+            model.set_gravity(gravity);
+        }
+        self.solve()
+    }
+
+    /// Sets the angle (in radians) a single crease is folded towards at a fold percentage of 1,
+    /// and solves for the new equilibrium
+    pub fn set_crease_target_fold_angle(
+        &mut self,
+        crease_index: u32,
+        target_fold_angle: f32,
+    ) -> Result<(), OperationError> {
+        self.set_parameter(
+            crease_index,
+            |size| size.creases,
+            |loader| loader.copy_crease_target_fold_angle(&[target_fold_angle], crease_index),
+        )
+    }
+
+    /// Sets the stiffness of a single crease, and solves for the new equilibrium.
+    /// It must be finite and positive.
+    pub fn set_crease_stiffness(
+        &mut self,
+        crease_index: u32,
+        stiffness: f32,
+    ) -> Result<(), OperationError> {
+        if !(stiffness.is_finite() && stiffness >= 0.0) {
+            return Err(OperationError::InvalidValue);
+        }
+
+        self.set_parameter(
+            crease_index,
+            |size| size.creases,
+            |loader| loader.copy_crease_stiffness(&[stiffness], crease_index),
+        )
+    }
+
+    /// Sets the mass of a single node, and solves for the new equilibrium.
+    /// It must be finite and strictly positive.
+    pub fn set_node_mass(&mut self, node_index: u32, mass: f32) -> Result<(), OperationError> {
+        if !(mass.is_finite() && mass > 0.0) {
+            return Err(OperationError::InvalidValue);
+        }
+
+        self.set_parameter(
+            node_index,
+            |size| size.nodes,
+            |loader| loader.copy_node_mass(&[mass], node_index),
+        )
+    }
+
+    /// Sets whether a single node is fixed in place, and solves for the new equilibrium
+    pub fn set_node_fixed(&mut self, node_index: u32, fixed: bool) -> Result<(), OperationError> {
+        self.set_parameter(
+            node_index,
+            |size| size.nodes,
+            |loader| loader.copy_node_fixed(&[fixed], node_index),
+        )
+    }
+
+    /// Sets the external forces applied to the nodes starting at `offset`, and solves for the new equilibrium
+    pub fn set_node_external_forces(
+        &mut self,
+        forces: &[Vector3F],
+        offset: u32,
+    ) -> Result<(), OperationError> {
+        // Checking the last node written covers the whole range
+        let last = u32::try_from(forces.len().saturating_sub(1))
+            .map_or(u32::MAX, |len| offset.saturating_add(len));
+        self.set_parameter(
+            last,
+            |size| size.nodes,
+            |loader| loader.copy_node_external_forces(forces, offset),
+        )
+    }
+
+    /// The number of nodes of the loaded model, if there is one
+    pub fn node_count(&self) -> Option<u32> {
+        self.model.as_ref().map(|model| model.size().nodes)
+    }
+
+    /// For each node of the loaded model, the vertex of the FOLD frame it comes from,
+    /// or `None` when they're the same (as the frame has neither cut nor join edges)
+    pub fn node_sources(&self) -> Option<&[fold::VertexIndex]> {
+        self.node_sources.as_deref()
+    }
+
+    /// The force the supports apply to each fixed node (zero for the other nodes), if a model is loaded
+    pub fn node_reaction(&self) -> Option<&[Vector3F]> {
+        self.model.as_ref().map(|model| model.node_reaction())
+    }
+
+    pub fn extract(&self) -> Result<rtori_os_static::Extractor<'_, A>, OperationError> {
+        self.model
+            .as_ref()
+            .ok_or(OperationError::NotLoaded)
+            .map(|model| model.extract())
+    }
+
+    fn set_parameter(
+        &mut self,
+        index: u32,
+        count: impl FnOnce(&rtori_os_model::ModelSize) -> u32,
+        f: impl FnOnce(&mut dyn rtori_os_model::LoaderDyn<'_>),
+    ) -> Result<(), OperationError> {
+        let model = self.model.as_mut().ok_or(OperationError::NotLoaded)?;
+        let count = count(&model.size());
+        if index >= count {
+            return Err(OperationError::OutOfRange { index, count });
+        }
+
+        f(&mut model.load());
+        self.solve()
+    }
+
+    /// Solves for the equilibrium at the current parameters, if a model is loaded
+    fn solve(&mut self) -> Result<(), OperationError> {
+        let Some(model) = self.model.as_mut() else {
+            return Ok(());
+        };
+
+        let result = model.solve(self.fold_percentage, &self.config);
+        self.last_solve = Some(result);
+        result.map(|_| ()).map_err(|_| OperationError::NotConverged)
+    }
+}
+
+impl<A> crate::family::Solver for Solver<A>
+where
+    A: Allocator + Clone + core::fmt::Debug + 'static,
+{
+    fn family(&self) -> crate::family::Family {
+        crate::family::Family::StaticEquilibrium
+    }
+
+    fn load_fold(&mut self, frame: &fold::FrameCore) -> Result<(), LoadError> {
+        self.load_fold_in(frame, self.allocator.clone())
+    }
+
+    fn node_count(&self) -> Option<u32> {
+        Solver::node_count(self)
+    }
+
+    fn node_sources(&self) -> Option<&[fold::VertexIndex]> {
+        Solver::node_sources(self)
+    }
+
+    fn step(&mut self, _step_count: u32) -> Result<(), OperationError> {
+        Err(OperationError::Unsupported(Capabilities::STEP))
+    }
+
+    fn reset(&mut self) -> Result<(), OperationError> {
+        Solver::reset(self)
+    }
+
+    fn set_parameter(
+        &mut self,
+        parameter: crate::family::Parameter<'_>,
+    ) -> Result<(), OperationError> {
+        use crate::family::Parameter;
+
+        match parameter {
+            Parameter::FoldPercentage(fold_percentage) => self.set_fold_percentage(fold_percentage),
+            Parameter::FaceStiffness(face_stiffness) => self.set_face_stiffness(face_stiffness),
+            Parameter::CreaseTargetFoldAngle {
+                crease_index,
+                angle,
+            } => self.set_crease_target_fold_angle(crease_index, angle),
+            Parameter::Gravity(gravity) => self.set_gravity(gravity),
+            Parameter::NodeExternalForces { forces, offset } => {
+                self.set_node_external_forces(forces, offset)
+            }
+            Parameter::CreaseStiffness {
+                crease_index,
+                stiffness,
+            } => self.set_crease_stiffness(crease_index, stiffness),
+            Parameter::NodeMass { node_index, mass } => self.set_node_mass(node_index, mass),
+            Parameter::NodeFixed { node_index, fixed } => self.set_node_fixed(node_index, fixed),
+            parameter => Err(OperationError::Unsupported(parameter.capability())),
+        }
+    }
+
+    fn extract(
+        &self,
+        _extract_flags: rtori_os_model::ExtractFlags,
+        with: &mut dyn FnMut(&dyn rtori_os_model::ExtractorDyn<'_>),
+    ) -> Result<(), OperationError> {
+        let extractor = Solver::extract(self)?;
+        with(&extractor);
+        Ok(())
+    }
+}
@@ -49,14 +49,16 @@ fn test_onestep(fold_file: std::path::PathBuf) {
             .block_on()
             .unwrap();
 
-    solver.load_fold_in(&parsed_input.key_frame, allocator);
+    solver
+        .load_fold_in(&parsed_input.key_frame, allocator)
+        .unwrap();
     solver.set_fold_percentage(fold_ratio).unwrap();
 
     solver.step(1).expect(&format!("Step failed"));
 
     let mut positions = Vec::new();
     positions.resize(
-        parsed_input.frame(0).unwrap().get().vertices.count(),
+        parsed_input.frame(0).unwrap().get().unwrap().vertices.count(),
         rtori_os_model::Vector3F([6.9f32, 42.0f32, 6009.0f32]),
     );
 
@@ -94,18 +96,20 @@ fn test_stability(fold_file: std::path::PathBuf) {
     let parsed_input = serde_json::from_reader::<_, fold::File>(file)
         .expect("{full_name}: source deserialization (json/fold file) failed");
 
-    solver.load_fold_in(&parsed_input.key_frame, allocator);
+    solver
+        .load_fold_in(&parsed_input.key_frame, allocator)
+        .unwrap();
     solver.set_fold_percentage(fold_ratio).unwrap();
 
     let mut positions_front = Vec::new();
     positions_front.resize(
-        parsed_input.frame(0).unwrap().get().vertices.count(),
+        parsed_input.frame(0).unwrap().get().unwrap().vertices.count(),
         rtori_os_model::Vector3F([6.9f32, 42.0f32, 6009.0f32]),
     );
 
     let mut positions_back = Vec::new();
     positions_back.resize(
-        parsed_input.frame(0).unwrap().get().vertices.count(),
+        parsed_input.frame(0).unwrap().get().unwrap().vertices.count(),
         rtori_os_model::Vector3F([6.9f32, 42.0f32, 6009.0f32]),
     );
 
@@ -173,3 +177,1663 @@ fn test_stability(fold_file: std::path::PathBuf) {
 
     println!("{full_name}: Success after {MAXIMUM_ITERATIONS} steps");
 }
+
+/// Computes the three angles of the triangle `[a, b, c]`, at `a`, `b` & `c` respectively
+fn triangle_angles(positions: &[rtori_os_model::Vector3F], face: &[u32]) -> [f32; 3] {
+    let [a, b, c] = [0, 1, 2].map(|i| positions[face[i] as usize].0);
+    let sub = |lhs: [f32; 3], rhs: [f32; 3]| [lhs[0] - rhs[0], lhs[1] - rhs[1], lhs[2] - rhs[2]];
+    let angle = |lhs: [f32; 3], rhs: [f32; 3]| {
+        let dot: f32 = lhs.iter().zip(rhs).map(|(l, r)| l * r).sum();
+        let norm = |v: [f32; 3]| v.iter().map(|c| c * c).sum::<f32>().sqrt();
+        (dot / (norm(lhs) * norm(rhs))).clamp(-1.0, 1.0).acos()
+    };
+
+    [
+        angle(sub(b, a), sub(c, a)),
+        angle(sub(a, b), sub(c, b)),
+        angle(sub(a, c), sub(b, c)),
+    ]
+}
+
+/// test_face_angles folds a pattern fully, and checks that the faces are not sheared: their angles should
+/// stay close to the nominal ones. With soft beams, the face forces are what keeps them so.
+#[test]
+fn test_face_angles() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FOLDED: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_100.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FOLDED)
+        .expect("source deserialization (json/fold file) failed");
+
+    let nominal_positions = parsed_input
+        .key_frame
+        .vertices
+        .coords
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|coords| rtori_os_model::Vector3F([coords[0], coords[1], coords[2]]))
+        .collect::<Vec<_>>();
+    let faces = parsed_input.key_frame.faces.vertices.as_ref().unwrap();
+
+    // Folds the pattern fully, and returns the largest difference between an angle of a face and its nominal value
+    let max_shear = |axial_stiffness: Option<f32>, face_stiffness: f32, dt: Option<f32>| {
+        let mut frame = parsed_input.key_frame.clone();
+        frame.edges.axial_stiffness =
+            axial_stiffness.map(|stiffness| vec![Some(stiffness); frame.edges.count()]);
+
+        let mut solver =
+            rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+                .block_on()
+                .unwrap();
+        if let Some(dt) = dt {
+            solver
+                .set_time_step(rtori_core::os_solver::TimeStep::Fixed(dt))
+                .unwrap();
+        }
+        solver.load_fold_in(&frame, alloc::alloc::Global).unwrap();
+        solver.set_fold_percentage(1.0).unwrap();
+        solver.set_face_stiffness(face_stiffness).unwrap();
+        solver.step(2_000).expect("Step failed");
+
+        // The extracted positions are offsets from the nominal positions
+        let mut offsets = Vec::new();
+        offsets.resize(frame.vertices.count(), rtori_os_model::Vector3F([0.0; 3]));
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut offsets[..], 0);
+
+        let positions = nominal_positions
+            .iter()
+            .zip(&offsets)
+            .map(|(nominal, offset)| {
+                rtori_os_model::Vector3F([0, 1, 2].map(|i| nominal.0[i] + offset.0[i]))
+            })
+            .collect::<Vec<_>>();
+
+        let shear = faces
+            .iter()
+            .flat_map(|face| {
+                let nominal = triangle_angles(&nominal_positions, face);
+                let current = triangle_angles(&positions, face);
+                nominal
+                    .into_iter()
+                    .zip(current)
+                    .map(|(nominal, current)| (nominal - current).abs())
+            })
+            .fold(0.0f32, f32::max);
+        (shear, solver.dt().unwrap())
+    };
+
+    const ANGLE_TOLERANCE: f32 = 0.1; // in radians, a bit less than 6 degrees
+    let (shear, dt) = max_shear(None, 1.0, None);
+    assert!(
+        shear < ANGLE_TOLERANCE,
+        "a face angle moved by {shear} from its nominal value"
+    );
+
+    // With beams ten times softer (the timestep being kept as is, so that it stays stable), the faces shear more
+    // when nothing but the beams hold them
+    let (soft_shear, _) = max_shear(Some(2.0), 1.0, Some(dt));
+    let (unheld_shear, _) = max_shear(Some(2.0), 0.0, Some(dt));
+    assert!(
+        soft_shear < unheld_shear,
+        "the face forces should reduce the shear, but it is {soft_shear} with them and {unheld_shear} without"
+    );
+}
+
+/// test_multithreaded checks that the multi-threaded CPU backend gives the same results as the single-threaded one
+#[cfg(feature = "cpu_mt")]
+#[apply(pair_test)]
+fn test_multithreaded(fold_file: std::path::PathBuf) {
+    initialize_tracing();
+
+    let (_, fold_ratio) = parse_path(fold_file.as_ref());
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .open(fold_file)
+        .unwrap();
+    let parsed_input = serde_json::from_reader::<_, fold::File>(file)
+        .expect("source deserialization (json/fold file) failed");
+
+    let run = |backend| {
+        let mut solver = rtori_core::os_solver::Solver::create(backend)
+            .block_on()
+            .unwrap();
+        solver
+            .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+            .unwrap();
+        solver.set_fold_percentage(fold_ratio).unwrap();
+        solver.step(100).expect("Step failed");
+
+        let mut positions = Vec::new();
+        positions.resize(
+            parsed_input.key_frame.vertices.count(),
+            rtori_os_model::Vector3F([0.0; 3]),
+        );
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+    };
+
+    let single_threaded = run(rtori_core::os_solver::BackendFlags::CPU);
+    let multi_threaded = run(rtori_core::os_solver::BackendFlags::CPU_MT);
+
+    assert_eq!(single_threaded, multi_threaded);
+}
+
+/// test_lane_widths checks that every lane width supported by the processor gives the same results
+#[apply(pair_test)]
+fn test_lane_widths(fold_file: std::path::PathBuf) {
+    initialize_tracing();
+
+    let (_, fold_ratio) = parse_path(fold_file.as_ref());
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .open(fold_file)
+        .unwrap();
+    let parsed_input = serde_json::from_reader::<_, fold::File>(file)
+        .expect("source deserialization (json/fold file) failed");
+
+    let run = |lane_width| {
+        let mut solver =
+            rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+                .block_on()
+                .unwrap();
+        solver.set_lane_width(lane_width).unwrap();
+        solver
+            .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+            .unwrap();
+        assert_eq!(solver.lane_width(), lane_width);
+
+        solver.set_fold_percentage(fold_ratio).unwrap();
+        solver.step(100).expect("Step failed");
+
+        let mut positions = Vec::new();
+        positions.resize(
+            parsed_input.key_frame.vertices.count(),
+            rtori_os_model::Vector3F([0.0; 3]),
+        );
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+    };
+
+    let reference = run(rtori_core::os_solver::LaneWidth::X4);
+    for lane_width in rtori_core::os_solver::LaneWidth::ALL
+        .into_iter()
+        .filter(|lane_width| lane_width.is_supported())
+    {
+        // The instructions used differ between widths (e.g. fused multiply-adds), so the results may differ slightly
+        const TOLERANCE: f32 = 1e-4;
+        for (i, (expected, got)) in reference.iter().zip(run(lane_width)).enumerate() {
+            assert!(
+                expected.0.iter().zip(got.0).all(|(e, g)| (e - g).abs() < TOLERANCE),
+                "{lane_width}: vertex {i} is at {got:?} but should be at {expected:?}"
+            );
+        }
+    }
+}
+
+/// test_reference checks that the CPU backend gives the same results as the scalar reference solver,
+/// which follows the same steps without any vectorization
+#[apply(pair_test)]
+fn test_reference(fold_file: std::path::PathBuf) {
+    initialize_tracing();
+
+    let (name, fold_ratio) = parse_path(fold_file.as_ref());
+    let full_name = format!("{name} ({:.1}%)", fold_ratio * 100.0);
+
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .open(fold_file)
+        .unwrap();
+    let parsed_input = serde_json::from_reader::<_, fold::File>(file)
+        .expect("source deserialization (json/fold file) failed");
+    let frame = &parsed_input.key_frame;
+
+    let allocator = alloc::alloc::Global;
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    solver.load_fold_in(frame, allocator).unwrap();
+    solver.set_fold_percentage(fold_ratio).unwrap();
+
+    let mut reference = {
+        let transformed =
+            transform_in(frame, allocator).expect("Transformation into importation input failed");
+        let transformed_input = transformed.with_fold(frame);
+        let preprocessed =
+            rtori_os_fold_importer::InputWithCreaseGeometry::process(&transformed_input, allocator)
+                .expect("preprocessing failed");
+
+        let mut runner = rtori_os_reference::Runner::with_size(&preprocessed.compute_size());
+        preprocessed
+            .load(&mut runner.load(), Default::default(), allocator)
+            .expect("import failed");
+        runner
+    };
+    reference.set_dt(solver.dt().unwrap());
+    reference.set_fold_percentage(fold_ratio);
+
+    const STEP_COUNT: u32 = 100;
+    solver.step(STEP_COUNT).expect("Step failed");
+    for _ in 0..STEP_COUNT {
+        reference.step();
+    }
+
+    let extract = |extractor: &dyn rtori_os_model::ExtractorDyn<'_>| {
+        let mut positions = Vec::new();
+        positions.resize(frame.vertices.count(), rtori_os_model::Vector3F([0.0; 3]));
+        extractor.copy_node_position(&mut positions[..], 0);
+        positions
+    };
+    let got = extract(
+        &solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed"),
+    );
+    let expected = extract(&reference.extract(rtori_os_model::ExtractFlags::POSITION));
+
+    // The SIMD kernels use approximations of the trigonometric functions (see SLEEF), so the results differ slightly
+    const TOLERANCE: f32 = 1e-3;
+    for (i, (expected, got)) in expected.iter().zip(got).enumerate() {
+        assert!(
+            expected.0.iter().zip(got.0).all(|(e, g)| (e - g).abs() < TOLERANCE),
+            "{full_name}: vertex {i} is at {got:?} but the reference solver has it at {expected:?}"
+        );
+    }
+}
+
+/// test_crease_damping swings a single crease towards its target fold angle, and checks that its damping reduces the
+/// overshoot without changing the first step (before which no previous fold angle is known)
+#[test]
+fn test_crease_damping() {
+    use rtori_os_model::LoaderDyn as _;
+
+    initialize_tracing();
+
+    // A square folded by 45 degrees along its diagonal, its last vertex being the only one free to move
+    let frame = serde_json::from_str::<fold::FrameCore>(
+        r#"{
+            "vertices_coords": [[0, 0, 0], [1, 0, 0], [1, 1, 0], [0.14644661, 0.85355339, 0.5]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [0, 2]],
+            "edges_assignment": ["B", "B", "B", "B", "V"],
+            "edges_foldAngle": [0, 0, 0, 0, 90],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3]]
+        }"#,
+    )
+    .expect("source deserialization (json/fold file) failed");
+    let free_vertex = [0.14644661f32, 0.85355339, 0.5];
+
+    let allocator = alloc::alloc::Global;
+    let transformed =
+        transform_in(&frame, allocator).expect("Transformation into importation input failed");
+    let transformed_input = transformed.with_fold(&frame);
+    let preprocessed =
+        rtori_os_fold_importer::InputWithCreaseGeometry::process(&transformed_input, allocator)
+            .expect("preprocessing failed");
+
+    // The beams are left undamped, so that only the crease damps the swing
+    let config = rtori_os_fold_importer::ImportConfig {
+        damping_percentage: 0.0,
+        ..Default::default()
+    };
+    let defaults = rtori_os_fold_importer::ImportConfig::default();
+    let crease_k = defaults.default_crease_stiffness * core::f32::consts::SQRT_2;
+    let crease_d = defaults.damping_percentage * 2.0 * (crease_k * defaults.default_mass).sqrt();
+    let load = |loader: &mut dyn rtori_os_model::LoaderDyn<'_>, d: f32| {
+        loader.copy_node_fixed(&[true, true, true, false], 0);
+        loader.copy_crease_parameters(
+            &[rtori_os_model::CreaseParameters {
+                k: crease_k,
+                d,
+                target_fold_angle: core::f32::consts::FRAC_PI_2,
+            }],
+            0,
+        );
+    };
+
+    // The fold angle of the crease, from the rotation of the free vertex around the diagonal
+    let fold_angle = |extractor: &dyn rtori_os_model::ExtractorDyn<'_>| {
+        let mut offsets = vec![rtori_os_model::Vector3F::default(); 4];
+        extractor.copy_node_position(&mut offsets[..], 0);
+        let [x, y, z] = [0, 1, 2].map(|i| free_vertex[i] + offsets[3].0[i]);
+        let away_from_diagonal = ((0.5 - x) + (y - 0.5)) / core::f32::consts::SQRT_2;
+        z.atan2(away_from_diagonal)
+    };
+
+    const STEP_COUNT: usize = 400;
+    const DT: f32 = 0.02;
+    let reference = |d: f32| {
+        let mut runner = rtori_os_reference::Runner::with_size(&preprocessed.compute_size());
+        preprocessed
+            .load(&mut runner.load(), config, allocator)
+            .expect("import failed");
+        load(&mut runner.load(), d);
+        runner.set_dt(DT);
+        runner.set_fold_percentage(1.0);
+
+        (0..STEP_COUNT)
+            .map(|_| {
+                runner.step();
+                fold_angle(&runner.extract(rtori_os_model::ExtractFlags::POSITION))
+            })
+            .collect::<Vec<_>>()
+    };
+    let simd = |d: f32| {
+        let mut runner = rtori_os_simd::owned::OwnedRunner::<4, alloc::alloc::Global>::with_size(
+            &preprocessed.compute_size(),
+        );
+        preprocessed
+            .load(&mut runner.load(), config, allocator)
+            .expect("import failed");
+        load(&mut runner.load(), d);
+        runner.set_dt(DT);
+        runner.set_fold_percentage(1.0);
+
+        (0..STEP_COUNT)
+            .map(|_| {
+                runner.step().expect("Step failed");
+                fold_angle(&runner.extract(rtori_os_model::ExtractFlags::POSITION))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let runs: [(&str, &dyn Fn(f32) -> Vec<f32>); 2] = [("reference", &reference), ("SIMD", &simd)];
+    for (name, run) in runs {
+        let undamped = run(0.0);
+        let damped = run(crease_d);
+
+        // The crease starts still, so its damping doesn't act on the first step
+        assert_eq!(
+            undamped[0], damped[0],
+            "{name}: the damping shouldn't change the first step"
+        );
+
+        // Undamped, the crease swings as far past its target as it started from it (a quarter of PI)
+        let overshoot = |fold_angles: &[f32]| {
+            fold_angles.iter().copied().fold(f32::MIN, f32::max) - core::f32::consts::FRAC_PI_2
+        };
+        let (undamped_overshoot, damped_overshoot) = (overshoot(&undamped), overshoot(&damped));
+        assert!(
+            undamped_overshoot > 0.5,
+            "{name}: the undamped crease should swing past its target, but overshoots by {undamped_overshoot}"
+        );
+        assert!(
+            damped_overshoot < 0.1,
+            "{name}: the damped crease overshoots by {damped_overshoot}, against {undamped_overshoot} undamped"
+        );
+    }
+}
+
+/// test_solve_until checks that every test file reaches equilibrium, and that the report describes it
+#[apply(pair_test)]
+fn test_solve_until(fold_file: std::path::PathBuf) {
+    initialize_tracing();
+
+    let (name, fold_ratio) = parse_path(fold_file.as_ref());
+    let full_name = format!("{name} ({:.1}%)", fold_ratio * 100.0);
+
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .open(fold_file)
+        .unwrap();
+    let parsed_input = serde_json::from_reader::<_, fold::File>(file)
+        .expect("source deserialization (json/fold file) failed");
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    solver
+        .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+        .unwrap();
+    solver.set_fold_percentage(fold_ratio).unwrap();
+
+    const MAX_STEPS: u32 = 32_000;
+    const MAX_VELOCITY: f32 = 1e-3;
+    let criteria = rtori_core::os_solver::SolveCriteria {
+        max_velocity: Some(MAX_VELOCITY),
+        ..rtori_core::os_solver::SolveCriteria::with_max_steps(MAX_STEPS)
+    };
+    let report = solver
+        .solve_until(criteria, TickingClock::new(std::time::Duration::ZERO))
+        .expect("solve_until failed");
+
+    assert_eq!(
+        report.stop_reason,
+        rtori_core::os_solver::StopReason::Converged,
+        "{full_name}: {report:?}"
+    );
+    assert!(report.steps < MAX_STEPS, "{full_name}: {report:?}");
+    assert!(
+        report.residuals.max_velocity <= MAX_VELOCITY,
+        "{full_name}: {report:?}"
+    );
+    assert_eq!(report.residuals, solver.residuals().unwrap());
+}
+
+/// test_solve_until_fixed_node checks that a pattern with a fixed vertex still reaches equilibrium,
+/// even though the force pushing on the fixed vertex is never balanced
+#[test]
+fn test_solve_until_fixed_node() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FLAT: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_0.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FLAT)
+        .expect("source deserialization (json/fold file) failed");
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    solver
+        .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+        .unwrap();
+    solver.set_fold_percentage(1.0).unwrap();
+    solver.set_node_fixed(1, true).unwrap();
+    solver
+        .set_node_external_force(1, rtori_os_model::Vector3F([0.0, 0.0, 1.0]))
+        .unwrap();
+
+    const MAX_STEPS: u32 = 32_000;
+    const MAX_VELOCITY: f32 = 1e-3;
+    let criteria = rtori_core::os_solver::SolveCriteria {
+        max_velocity: Some(MAX_VELOCITY),
+        ..rtori_core::os_solver::SolveCriteria::with_max_steps(MAX_STEPS)
+    };
+    let report = solver
+        .solve_until(criteria, TickingClock::new(std::time::Duration::ZERO))
+        .expect("solve_until failed");
+
+    assert_eq!(
+        report.stop_reason,
+        rtori_core::os_solver::StopReason::Converged,
+        "{report:?}"
+    );
+    assert!(report.residuals.max_velocity <= MAX_VELOCITY, "{report:?}");
+
+    // Without any threshold, only the budget stops the stepping. The clock is read once at the start, then before
+    // each step: the budget of 10 ticks is spent before the tenth one, so 9 steps are done
+    solver.reset().unwrap();
+    let criteria = rtori_core::os_solver::SolveCriteria {
+        time_budget: Some(std::time::Duration::from_millis(10)),
+        ..rtori_core::os_solver::SolveCriteria::with_max_steps(MAX_STEPS)
+    };
+    let report = solver
+        .solve_until(
+            criteria,
+            TickingClock::new(std::time::Duration::from_millis(1)),
+        )
+        .expect("solve_until failed");
+    assert_eq!(
+        report.stop_reason,
+        rtori_core::os_solver::StopReason::TimeBudget,
+        "{report:?}"
+    );
+    assert_eq!(report.steps, 9, "{report:?}");
+}
+
+/// A clock advancing by a fixed amount every time it is read
+struct TickingClock {
+    now: std::cell::Cell<std::time::Duration>,
+    tick: std::time::Duration,
+}
+
+impl TickingClock {
+    fn new(tick: std::time::Duration) -> Self {
+        Self {
+            now: std::cell::Cell::new(std::time::Duration::ZERO),
+            tick,
+        }
+    }
+}
+
+impl rtori_core::os_solver::Clock for TickingClock {
+    fn now(&self) -> std::time::Duration {
+        let now = self.now.get();
+        self.now.set(now + self.tick);
+        now
+    }
+}
+
+/// test_step_for checks that time-budgeted stepping honours both the simulated time and the wall-clock budget
+#[test]
+fn test_step_for() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FOLDED: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_100.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FOLDED)
+        .expect("source deserialization (json/fold file) failed");
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    solver
+        .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+        .unwrap();
+    solver.set_fold_percentage(1.0).unwrap();
+    let dt = solver.dt().unwrap();
+
+    let clock = TickingClock::new(std::time::Duration::from_millis(1));
+
+    // Bounded by the simulated time
+    let report = solver
+        .step_for(std::time::Duration::from_secs(3600), 10.0 * dt, &clock)
+        .expect("step_for failed");
+    assert_eq!(report.steps, 10, "{report:?}");
+    assert!(report.remaining_simulated_time.abs() < dt / 2.0, "{report:?}");
+    // Each step reads the clock twice, once before and once after
+    assert_eq!(report.step_cost, clock.tick);
+
+    // Bounded by the wall-clock time
+    let budget = std::time::Duration::from_millis(10);
+    let report = solver
+        .step_for(budget, f32::INFINITY, &clock)
+        .expect("step_for failed");
+    assert!(report.steps > 0, "{report:?}");
+    assert!(report.step_cost * report.steps <= budget, "{report:?}");
+    assert_eq!(report.remaining_simulated_time, f32::INFINITY);
+
+    // A fixed timestep has to be strictly positive, and replaces the automatic one right away
+    for dt in [0.0, -1e-3, f32::NAN, f32::INFINITY] {
+        assert_eq!(
+            solver.set_time_step(rtori_core::os_solver::TimeStep::Fixed(dt)),
+            Err(rtori_core::os_solver::ParameterError::InvalidValue)
+        );
+    }
+    assert_eq!(solver.dt(), Some(dt));
+    solver
+        .set_time_step(rtori_core::os_solver::TimeStep::Fixed(dt / 2.0))
+        .unwrap();
+    assert_eq!(solver.dt(), Some(dt / 2.0));
+}
+
+/// test_snapshot checks that restoring a snapshot resumes the simulation where it was taken, whatever the lane width
+#[apply(pair_test)]
+fn test_snapshot(fold_file: std::path::PathBuf) {
+    initialize_tracing();
+
+    let (_, fold_ratio) = parse_path(fold_file.as_ref());
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .open(fold_file)
+        .unwrap();
+    let parsed_input = serde_json::from_reader::<_, fold::File>(file)
+        .expect("source deserialization (json/fold file) failed");
+
+    let create = |lane_width| {
+        let mut solver =
+            rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+                .block_on()
+                .unwrap();
+        solver.set_lane_width(lane_width).unwrap();
+        solver
+            .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+            .unwrap();
+        solver
+    };
+
+    let positions = |solver: &rtori_core::os_solver::Solver| {
+        let mut positions = Vec::new();
+        positions.resize(
+            parsed_input.key_frame.vertices.count(),
+            rtori_os_model::Vector3F([0.0; 3]),
+        );
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+    };
+
+    let mut solver = create(rtori_core::os_solver::LaneWidth::detect());
+    solver.set_fold_percentage(fold_ratio).unwrap();
+    solver.step(50).expect("Step failed");
+    let snapshot = solver.snapshot().expect("snapshot failed");
+    solver.step(50).expect("Step failed");
+    let expected = positions(&solver);
+
+    // In the same solver, the simulation is resumed exactly
+    solver.restore(&snapshot).expect("restore failed");
+    solver.step(50).expect("Step failed");
+    assert_eq!(positions(&solver), expected);
+
+    assert_eq!(
+        solver.restore(&snapshot[..snapshot.len() - 1]),
+        Err(rtori_core::os_solver::RestoreError::InvalidFormat)
+    );
+
+    // The timestep follows the magic, version, lane count, model size, step count and fold percentage
+    const DT_OFFSET: usize = 8 + 4 + 4 + 6 * 4 + 8 + 4;
+    for dt in [f32::NAN, f32::INFINITY, 0.0, -1.0] {
+        let mut corrupted = snapshot.clone();
+        corrupted[DT_OFFSET..DT_OFFSET + 4].copy_from_slice(&dt.to_le_bytes());
+        assert_eq!(
+            solver.restore(&corrupted),
+            Err(rtori_core::os_solver::RestoreError::InvalidFormat),
+            "a snapshot with a timestep of {dt} should be rejected"
+        );
+    }
+    let mut corrupted = snapshot.clone();
+    corrupted[DT_OFFSET - 4..DT_OFFSET].copy_from_slice(&f32::NAN.to_le_bytes());
+    assert_eq!(
+        solver.restore(&corrupted),
+        Err(rtori_core::os_solver::RestoreError::InvalidFormat)
+    );
+
+    // In a solver of another lane width, the parameters are restored as well (the fold percentage isn't set here)
+    let mut other = create(rtori_core::os_solver::LaneWidth::X4);
+    other.restore(&snapshot).expect("restore failed");
+    other.step(50).expect("Step failed");
+
+    // The instructions used differ between widths (e.g. fused multiply-adds), so the results may differ slightly
+    const TOLERANCE: f32 = 1e-4;
+    for (i, (expected, got)) in expected.iter().zip(positions(&other)).enumerate() {
+        assert!(
+            expected
+                .0
+                .iter()
+                .zip(got.0)
+                .all(|(e, g)| (e - g).abs() < TOLERANCE),
+            "vertex {i} is at {got:?} but should be at {expected:?}"
+        );
+    }
+}
+
+/// test_snapshot_parameters checks that the parameters changed after loading and the gravity round-trip through a snapshot
+#[test]
+fn test_snapshot_parameters() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FLAT: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_0.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FLAT)
+        .expect("source deserialization (json/fold file) failed");
+    let node_count = parsed_input.key_frame.vertices.count();
+
+    let create = || {
+        let mut solver =
+            rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+                .block_on()
+                .unwrap();
+        solver
+            .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+            .unwrap();
+        solver
+    };
+
+    let positions = |solver: &rtori_core::os_solver::Solver| {
+        let mut positions = Vec::new();
+        positions.resize(node_count, rtori_os_model::Vector3F([0.0; 3]));
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+    };
+
+    let mut solver = create();
+    solver.set_fold_percentage(0.5).unwrap();
+    solver.set_gravity(rtori_os_model::Vector3F([0.0, 0.0, -0.5]));
+    solver.set_node_mass(1, 2.0).unwrap();
+    solver.set_node_fixed(2, true).unwrap();
+    solver
+        .set_node_external_force(3, rtori_os_model::Vector3F([0.1, 0.0, 0.2]))
+        .unwrap();
+    solver
+        .set_node_constraint(
+            4,
+            rtori_core::model::NodeConstraint::Spring {
+                target: rtori_os_model::Vector3F([0.0, 0.0, 0.5]),
+                stiffness: 5.0,
+            },
+        )
+        .unwrap();
+    solver.set_crease_stiffness(0, 3.0).unwrap();
+    solver
+        .set_crease_target_fold_angle(1, -core::f32::consts::FRAC_PI_4)
+        .unwrap();
+    solver.step(50).expect("Step failed");
+    let snapshot = solver.snapshot().expect("snapshot failed");
+    solver.step(50).expect("Step failed");
+
+    // A freshly loaded solver only has the default parameters, until the snapshot is restored
+    let mut other = create();
+    other.restore(&snapshot).expect("restore failed");
+    assert_eq!(other.gravity(), solver.gravity());
+    assert_eq!(other.dt(), solver.dt());
+    other.step(50).expect("Step failed");
+    assert_eq!(positions(&other), positions(&solver));
+}
+
+/// test_reset checks that resetting brings the model back to the state it had just after loading
+#[apply(pair_test)]
+fn test_reset(fold_file: std::path::PathBuf) {
+    initialize_tracing();
+
+    let (_, fold_ratio) = parse_path(fold_file.as_ref());
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .open(fold_file)
+        .unwrap();
+    let parsed_input = serde_json::from_reader::<_, fold::File>(file)
+        .expect("source deserialization (json/fold file) failed");
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    solver
+        .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+        .unwrap();
+    solver.set_fold_percentage(fold_ratio).unwrap();
+
+    let positions = |solver: &rtori_core::os_solver::Solver| {
+        let mut positions = Vec::new();
+        positions.resize(
+            parsed_input.key_frame.vertices.count(),
+            rtori_os_model::Vector3F([6.9f32, 42.0f32, 6009.0f32]),
+        );
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+    };
+    let at_rest = positions(&solver);
+
+    solver.step(100).expect("Step failed");
+    let expected = positions(&solver);
+
+    solver.reset().expect("reset failed");
+    assert_eq!(positions(&solver), at_rest);
+    assert_eq!(
+        solver.residuals().unwrap().max_velocity,
+        0.0,
+        "the nodes should be at rest"
+    );
+
+    // The fold percentage is kept, so the same steps are done again
+    solver.step(100).expect("Step failed");
+    assert_eq!(positions(&solver), expected);
+}
+
+/// test_parameter_setters checks that the parameters changed after loading are taken into account by the next steps
+#[test]
+fn test_parameter_setters() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FLAT: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_0.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FLAT)
+        .expect("source deserialization (json/fold file) failed");
+    let node_count = parsed_input.key_frame.vertices.count();
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    solver
+        .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+        .unwrap();
+    solver.set_fold_percentage(1.0).unwrap();
+
+    let max_offset = |solver: &rtori_core::os_solver::Solver| {
+        let mut positions = Vec::new();
+        positions.resize(node_count, rtori_os_model::Vector3F([0.0; 3]));
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+            .iter()
+            .flat_map(|position| position.0)
+            .fold(0.0f32, |max, c| max.max(c.abs()))
+    };
+
+    assert_eq!(
+        solver.set_node_fixed(node_count as u32, true),
+        Err(rtori_core::os_solver::ParameterError::OutOfRange {
+            index: node_count as u32,
+            count: node_count as u32
+        })
+    );
+    for face_stiffness in [f32::NAN, f32::INFINITY, -1.0] {
+        assert_eq!(
+            solver.set_face_stiffness(face_stiffness),
+            Err(rtori_core::os_solver::ParameterError::InvalidValue)
+        );
+    }
+    for crease_stiffness in [f32::NAN, f32::INFINITY, -1.0] {
+        assert_eq!(
+            solver.set_crease_stiffness(0, crease_stiffness),
+            Err(rtori_core::os_solver::ParameterError::InvalidValue)
+        );
+    }
+    solver.set_face_stiffness(0.0).unwrap();
+    solver.set_face_stiffness(1.0).unwrap();
+
+    // Without any target fold angle, the model stays flat
+    let crease_count = (0..)
+        .take_while(|crease_index| {
+            solver
+                .set_crease_target_fold_angle(*crease_index, 0.0)
+                .is_ok()
+        })
+        .count();
+    assert!(crease_count > 0);
+    solver.step(100).expect("Step failed");
+    assert!(max_offset(&solver) < 1e-5);
+
+    // With their target fold angles back, the creases fold unless every node is fixed
+    solver.reset().unwrap();
+    for crease_index in 0..(crease_count as u32) {
+        solver
+            .set_crease_target_fold_angle(crease_index, core::f32::consts::FRAC_PI_2)
+            .unwrap();
+    }
+    for node_index in 0..(node_count as u32) {
+        solver.set_node_fixed(node_index, true).unwrap();
+    }
+    solver.step(100).expect("Step failed");
+    assert_eq!(max_offset(&solver), 0.0);
+
+    for node_index in 0..(node_count as u32) {
+        solver.set_node_fixed(node_index, false).unwrap();
+    }
+    solver.step(100).expect("Step failed");
+    assert!(max_offset(&solver) > 1e-3);
+}
+
+/// test_node_mass checks that the masses set after loading are validated, and that the automatic timestep follows them
+#[test]
+fn test_node_mass() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FLAT: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_0.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FLAT)
+        .expect("source deserialization (json/fold file) failed");
+    let node_count = parsed_input.key_frame.vertices.count();
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    solver
+        .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+        .unwrap();
+    solver.set_fold_percentage(1.0).unwrap();
+
+    for mass in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+        assert_eq!(
+            solver.set_node_mass(0, mass),
+            Err(rtori_core::os_solver::ParameterError::InvalidValue)
+        );
+    }
+
+    // Every natural frequency doubles when every mass is quartered, halving the timestep
+    let dt = solver.dt().unwrap();
+    for node_index in 0..(node_count as u32) {
+        solver.set_node_mass(node_index, 0.25).unwrap();
+    }
+    let lighter_dt = solver.dt().unwrap();
+    assert!(
+        (lighter_dt - dt / 2.0).abs() < dt * 1e-4,
+        "{dt} -> {lighter_dt}"
+    );
+
+    // With the damping following the masses, the lighter model settles as well
+    let criteria = rtori_core::os_solver::SolveCriteria {
+        max_velocity: Some(1e-3),
+        ..rtori_core::os_solver::SolveCriteria::with_max_steps(32_000)
+    };
+    let report = solver
+        .solve_until(criteria, TickingClock::new(std::time::Duration::ZERO))
+        .expect("solve_until failed");
+    assert_eq!(
+        report.stop_reason,
+        rtori_core::os_solver::StopReason::Converged,
+        "{report:?}"
+    );
+}
+
+/// test_external_forces checks that gravity, per-node forces and the static forces of the FOLD extension move the nodes
+#[test]
+fn test_external_forces() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FLAT: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_0.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FLAT)
+        .expect("source deserialization (json/fold file) failed");
+    let node_count = parsed_input.key_frame.vertices.count();
+    const STEP_COUNT: u32 = 100;
+
+    let positions = |solver: &rtori_core::os_solver::Solver| {
+        let mut positions = Vec::new();
+        positions.resize(node_count, rtori_os_model::Vector3F([0.0; 3]));
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+    };
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+
+    // The gravity is kept across loads
+    let gravity = rtori_os_model::Vector3F([0.0, 0.0, -1.0]);
+    solver.set_gravity(gravity);
+    solver
+        .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+        .unwrap();
+    assert_eq!(solver.gravity(), gravity);
+
+    // Flat and unfolded, the whole model falls at once, as all the masses are equal
+    solver.set_fold_percentage(0.0).unwrap();
+    solver.step(STEP_COUNT).expect("Step failed");
+    let fallen = positions(&solver);
+    let dt = solver.dt().unwrap();
+    let expected_fall = -dt * dt * (STEP_COUNT * (STEP_COUNT + 1) / 2) as f32;
+    for position in &fallen {
+        assert!(position.0[0].abs() < 1e-6 && position.0[1].abs() < 1e-6);
+        assert!((position.0[2] - expected_fall).abs() < expected_fall.abs() * 1e-3);
+    }
+
+    // A force on a single node only pushes that one away from the others
+    solver.set_gravity(rtori_os_model::Vector3F::default());
+    solver.reset().unwrap();
+    solver
+        .set_node_external_forces(&[rtori_os_model::Vector3F([0.0, 0.0, 1.0])], 0)
+        .unwrap();
+    solver.step(STEP_COUNT).expect("Step failed");
+    let pushed = positions(&solver);
+    assert!(pushed[1..]
+        .iter()
+        .all(|position| pushed[0].0[2] > position.0[2]));
+
+    assert_eq!(
+        solver.set_node_external_forces(
+            &[rtori_os_model::Vector3F::default(); 2],
+            node_count as u32 - 1
+        ),
+        Err(rtori_core::os_solver::ParameterError::OutOfRange {
+            index: node_count as u32,
+            count: node_count as u32
+        })
+    );
+
+    // The static forces of the FOLD extension act like the gravity, with a mass of 1
+    let mut with_forces = parsed_input.key_frame.clone();
+    with_forces.vertices.external_forces = Some(vec![gravity.0; node_count]);
+    solver
+        .load_fold_in(&with_forces, alloc::alloc::Global)
+        .unwrap();
+    solver.set_fold_percentage(0.0).unwrap();
+    solver.step(STEP_COUNT).expect("Step failed");
+    for (position, expected) in positions(&solver).iter().zip(fallen.iter()) {
+        for (c, expected_c) in position.0.iter().zip(expected.0.iter()) {
+            assert!((c - expected_c).abs() < 1e-5);
+        }
+    }
+}
+
+/// test_vertex_and_face_extensions checks that the per-vertex mass and fixed flags and the per-face stiffness are loaded from the FOLD extensions
+#[test]
+fn test_vertex_and_face_extensions() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FLAT: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_0.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FLAT)
+        .expect("source deserialization (json/fold file) failed");
+    let node_count = parsed_input.key_frame.vertices.count();
+    let face_count = parsed_input.key_frame.faces.count();
+    const STEP_COUNT: u32 = 100;
+
+    let positions = |solver: &rtori_core::os_solver::Solver| {
+        let mut positions = Vec::new();
+        positions.resize(node_count, rtori_os_model::Vector3F([0.0; 3]));
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+    };
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+
+    // Fixed vertices stay where they are, whatever pushes them
+    let mut fixed = parsed_input.key_frame.clone();
+    fixed.vertices.fixed = Some(vec![true; node_count]);
+    fixed.vertices.external_forces = Some(vec![[0.0, 0.0, -1.0]; node_count]);
+    solver.load_fold_in(&fixed, alloc::alloc::Global).unwrap();
+    solver.set_fold_percentage(1.0).unwrap();
+    solver.step(STEP_COUNT).expect("Step failed");
+    assert!(positions(&solver)
+        .iter()
+        .all(|position| position.0 == [0.0; 3]));
+
+    // Twice as heavy, the model falls half as far under the same static forces
+    let mut heavy = parsed_input.key_frame.clone();
+    heavy.vertices.mass = Some(vec![2.0; node_count]);
+    heavy.vertices.external_forces = Some(vec![[0.0, 0.0, -1.0]; node_count]);
+    solver.load_fold_in(&heavy, alloc::alloc::Global).unwrap();
+    solver.set_fold_percentage(0.0).unwrap();
+    solver.step(STEP_COUNT).expect("Step failed");
+    let dt = solver.dt().unwrap();
+    let expected_fall = -dt * dt * (STEP_COUNT * (STEP_COUNT + 1) / 2) as f32 / 2.0;
+    for position in &positions(&solver) {
+        assert!((position.0[2] - expected_fall).abs() < expected_fall.abs() * 1e-2);
+    }
+
+    // The stiffness of each face is scaled by the solver-wide face stiffness
+    let fold_with_face_stiffness = |face_stiffness: Option<Vec<f32>>, solver_wide: f32| {
+        let mut frame = parsed_input.key_frame.clone();
+        frame.faces.stiffness = face_stiffness;
+        let mut solver =
+            rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+                .block_on()
+                .unwrap();
+        solver.load_fold_in(&frame, alloc::alloc::Global).unwrap();
+        solver.set_face_stiffness(solver_wide).unwrap();
+        solver.set_fold_percentage(1.0).unwrap();
+        solver.step(STEP_COUNT).expect("Step failed");
+        positions(&solver)
+    };
+    assert_eq!(
+        fold_with_face_stiffness(Some(vec![2.0; face_count]), 0.1),
+        fold_with_face_stiffness(None, 0.2)
+    );
+    assert_ne!(
+        fold_with_face_stiffness(Some(vec![0.5; face_count]), 0.2),
+        fold_with_face_stiffness(None, 0.2)
+    );
+}
+
+/// test_2d_vertices checks that a crease pattern with two-dimensional coordinates is simulated as if it lay on the z=0 plane
+#[test]
+fn test_2d_vertices() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FLAT: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_0.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FLAT)
+        .expect("source deserialization (json/fold file) failed");
+    let node_count = parsed_input.key_frame.vertices.count();
+    const STEP_COUNT: u32 = 100;
+
+    // The flat pattern lies on the y=0 plane, so its x and z coordinates are enough to describe it
+    let with_coords = |to_coords: &dyn Fn(&[f32]) -> Vec<f32>| {
+        let mut frame = parsed_input.key_frame.clone();
+        let coords = frame.vertices.coords.as_mut().unwrap();
+        for vertex in coords.iter_mut() {
+            vertex.0 = to_coords(&vertex.0);
+        }
+        frame
+    };
+    let frame_2d = with_coords(&|vertex| vec![vertex[0], vertex[2]]);
+    let frame_3d = with_coords(&|vertex| vec![vertex[0], vertex[2], 0.0]);
+
+    let simulate = |frame: &fold::FrameCore| {
+        let mut solver =
+            rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+                .block_on()
+                .unwrap();
+        solver.load_fold_in(frame, alloc::alloc::Global).unwrap();
+        solver.set_fold_percentage(0.5).unwrap();
+        solver.step(STEP_COUNT).expect("Step failed");
+
+        let mut positions = Vec::new();
+        positions.resize(node_count, rtori_os_model::Vector3F([0.0; 3]));
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+    };
+    assert_eq!(simulate(&frame_2d), simulate(&frame_3d));
+
+    // Other dimensions are rejected
+    let frame_1d = with_coords(&|vertex| vec![vertex[0]]);
+    assert_eq!(
+        transform_in(&frame_1d, alloc::alloc::Global).err(),
+        Some(
+            rtori_os_fold_importer::supplement::TransformError::InvalidVertexDimension {
+                vertex_index: 0,
+                dimension: 1
+            }
+        )
+    );
+}
+
+/// test_node_constraints checks that a node can be pinned onto or pulled towards a target, and released
+#[test]
+fn test_node_constraints() {
+    initialize_tracing();
+
+    const SIMPLE_VERTEX_FLAT: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_0.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FLAT)
+        .expect("source deserialization (json/fold file) failed");
+    let node_count = parsed_input.key_frame.vertices.count();
+
+    let offsets = |solver: &rtori_core::os_solver::Solver| {
+        let mut positions = Vec::new();
+        positions.resize(node_count, rtori_os_model::Vector3F([0.0; 3]));
+        solver
+            .extract(rtori_os_model::ExtractFlags::POSITION)
+            .expect("extract call failed")
+            .copy_node_position(&mut positions[..], 0);
+        positions
+    };
+    let distance = |lhs: rtori_os_model::Vector3F, rhs: [f32; 3]| {
+        lhs.0
+            .iter()
+            .zip(rhs.iter())
+            .fold(0.0f32, |max, (l, r)| max.max((l - r).abs()))
+    };
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    solver
+        .load_fold_in(&parsed_input.key_frame, alloc::alloc::Global)
+        .unwrap();
+    solver.set_fold_percentage(0.0).unwrap();
+
+    // The targets are in the space of the vertices, one unit above the first one
+    let nominal = &parsed_input.key_frame.vertices.coords.as_ref().unwrap()[0];
+    let target = rtori_os_model::Vector3F([nominal[0], nominal[1], nominal[2] + 1.0]);
+
+    assert_eq!(
+        solver.remove_node_constraint(node_count as u32),
+        Err(rtori_core::os_solver::ParameterError::OutOfRange {
+            index: node_count as u32,
+            count: node_count as u32
+        })
+    );
+
+    // A pinned node reaches its target in a single step, before the others follow
+    solver
+        .set_node_constraint(0, rtori_core::model::NodeConstraint::Pinned { target })
+        .unwrap();
+    solver.step(1).expect("Step failed");
+    let pinned = offsets(&solver);
+    assert!(distance(pinned[0], [0.0, 0.0, 1.0]) < 1e-5);
+    assert!(pinned[1..]
+        .iter()
+        .all(|offset| distance(*offset, [0.0; 3]) < 1e-5));
+
+    // A spring pulls it there over time
+    solver.reset().unwrap();
+    solver
+        .set_node_constraint(
+            0,
+            rtori_core::model::NodeConstraint::Spring {
+                target,
+                stiffness: 10.0,
+            },
+        )
+        .unwrap();
+    solver.step(2000).expect("Step failed");
+    let pulled = offsets(&solver)[0];
+    assert!(distance(pulled, [0.0, 0.0, 1.0]) < 1e-3);
+
+    // A spring too stiff for the timestep is clamped instead of making the simulation diverge
+    solver.reset().unwrap();
+    solver
+        .set_node_constraint(
+            0,
+            rtori_core::model::NodeConstraint::Spring {
+                target,
+                stiffness: 1e12,
+            },
+        )
+        .unwrap();
+    solver.step(2000).expect("Step failed");
+    let pulled = offsets(&solver)[0];
+    assert!(distance(pulled, [0.0, 0.0, 1.0]) < 1e-3, "{pulled:?}");
+
+    // Once released (and back at rest), nothing moves the node anymore
+    solver.clear_node_constraints().unwrap();
+    solver.reset().unwrap();
+    solver.step(100).expect("Step failed");
+    assert!(distance(offsets(&solver)[0], [0.0; 3]) < 1e-5);
+}
+
+/// test_node_error checks that the error of each node is the mean strain of the beams around it,
+/// and of its faces when the face strain is calculated
+#[test]
+fn test_node_error() {
+    initialize_tracing();
+
+    let frame = serde_json::from_str::<fold::FrameCore>(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 0]],
+            "edges_assignment": ["B", "B", "B"],
+            "faces_vertices": [[0, 1, 2]]
+        }"#,
+    )
+    .expect("source deserialization (json/fold file) failed");
+
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    solver.load_fold_in(&frame, alloc::alloc::Global).unwrap();
+
+    let errors = |solver: &rtori_core::os_solver::Solver| {
+        let mut errors = vec![0.0f32; 3];
+        solver
+            .extract(rtori_os_model::ExtractFlags::ERROR)
+            .expect("extract call failed")
+            .copy_node_error(&mut errors[..], 0);
+        errors
+    };
+
+    // At rest, nothing is strained
+    solver.step(1).expect("Step failed");
+    assert!(errors(&solver).iter().all(|error| error.abs() < 1e-6));
+
+    // The second node is pinned a tenth further along the first edge, the others being held in place.
+    // The errors are computed at the start of a step, so they reflect the new position one step after it is reached.
+    solver.set_node_fixed(0, true).unwrap();
+    solver.set_node_fixed(2, true).unwrap();
+    solver
+        .set_node_constraint(
+            1,
+            rtori_core::model::NodeConstraint::Pinned {
+                target: rtori_os_model::Vector3F([1.1, 0.0, 0.0]),
+            },
+        )
+        .unwrap();
+    solver.step(2).expect("Step failed");
+
+    let strain_01 = 0.1;
+    let strain_12 = (1.1f32 * 1.1 + 1.0).sqrt() / core::f32::consts::SQRT_2 - 1.0;
+    // The right angle at the first node is kept, the two others move away from a quarter of PI by the same amount
+    let strain_face = 2.0 * ((1.0f32 / 1.1).atan() - core::f32::consts::FRAC_PI_4).abs()
+        / core::f32::consts::FRAC_PI_4
+        / 3.0;
+
+    const TOLERANCE: f32 = 1e-4;
+    let assert_errors = |got: Vec<f32>, expected: [f32; 3]| {
+        assert!(
+            got.iter()
+                .zip(expected)
+                .all(|(got, expected)| (got - expected).abs() < TOLERANCE),
+            "the node errors are {got:?} but should be {expected:?}"
+        );
+    };
+    assert_errors(
+        errors(&solver),
+        [
+            strain_01 / 2.0,
+            (strain_01 + strain_12) / 2.0,
+            strain_12 / 2.0,
+        ],
+    );
+
+    // With the face strain, the face counts as one more element around each node
+    solver.set_calculate_face_strain(true).unwrap();
+    solver.step(1).expect("Step failed");
+    assert_errors(
+        errors(&solver),
+        [
+            (strain_01 + strain_face) / 3.0,
+            (strain_01 + strain_12 + strain_face) / 3.0,
+            (strain_12 + strain_face) / 3.0,
+        ],
+    );
+}
+
+/// test_malformed_frames checks that malformed frames are rejected with an error instead of a panic,
+/// and that faces with more than three vertices are loaded
+#[test]
+fn test_malformed_frames() {
+    initialize_tracing();
+
+    let frame = |json: &str| {
+        serde_json::from_str::<fold::FrameCore>(json)
+            .expect("source deserialization (json/fold file) failed")
+    };
+    let mut solver =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+
+    // The face refers to a vertex that doesn't exist
+    let out_of_range = frame(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 0]],
+            "edges_assignment": ["B", "B", "B"],
+            "faces_vertices": [[0, 1, 7]]
+        }"#,
+    );
+    assert!(matches!(
+        solver.load_fold_in(&out_of_range, alloc::alloc::Global),
+        Err(rtori_core::os_solver::LoadError::Transform(
+            rtori_os_fold_importer::supplement::TransformError::FaceVertexOutOfRange {
+                face_index: 0,
+                vertex_number: 2,
+                points_to_vertex: 7,
+                vertex_count: 3
+            }
+        ))
+    ));
+    assert!(matches!(
+        solver.step(1),
+        Err(rtori_core::os_solver::StepError::NotLoaded)
+    ));
+
+    // A vertex without mass
+    let massless = frame(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+            "rtori:os:vertices_mass": [1, 0, 1],
+            "edges_vertices": [[0, 1], [1, 2], [2, 0]],
+            "edges_assignment": ["B", "B", "B"],
+            "faces_vertices": [[0, 1, 2]]
+        }"#,
+    );
+    assert!(matches!(
+        solver.load_fold_in(&massless, alloc::alloc::Global),
+        Err(rtori_core::os_solver::LoadError::Transform(
+            rtori_os_fold_importer::supplement::TransformError::InvalidVertexMass {
+                vertex_index: 1,
+                ..
+            }
+        ))
+    ));
+
+    // An extension with fewer values than elements
+    let short_stiffness = frame(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [0, 2]],
+            "edges_assignment": ["B", "B", "B", "B", "V"],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3]],
+            "rtori:os:faces_stiffness": [1]
+        }"#,
+    );
+    assert!(matches!(
+        solver.load_fold_in(&short_stiffness, alloc::alloc::Global),
+        Err(rtori_core::os_solver::LoadError::Transform(
+            rtori_os_fold_importer::supplement::TransformError::MismatchedFieldLength {
+                field: fold::Field::FacesStiffness,
+                expected: 2,
+                found: 1
+            }
+        ))
+    ));
+
+    // A mountain fold needs two faces
+    let lonely_mountain = frame(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 0]],
+            "edges_assignment": ["M", "B", "B"],
+            "faces_vertices": [[0, 1, 2]]
+        }"#,
+    );
+    assert!(matches!(
+        solver.load_fold_in(&lonely_mountain, alloc::alloc::Global),
+        Err(rtori_core::os_solver::LoadError::Preprocessing(_))
+    ));
+
+    // Two quads, triangulated on import
+    let quads = frame(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1], [2, 0], [2, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [1, 4], [4, 5], [5, 2]],
+            "edges_assignment": ["B", "V", "B", "B", "B", "B", "B"],
+            "faces_vertices": [[0, 1, 2, 3], [1, 4, 5, 2]]
+        }"#,
+    );
+    solver.load_fold_in(&quads, alloc::alloc::Global).unwrap();
+    solver.set_fold_percentage(0.5).unwrap();
+    solver.step(100).expect("Step failed");
+}
+
+/// Counts the bytes currently allocated through it, forwarding to the global allocator
+#[derive(Debug, Clone, Default)]
+struct CountingAllocator(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+unsafe impl core::alloc::Allocator for CountingAllocator {
+    fn allocate(
+        &self,
+        layout: core::alloc::Layout,
+    ) -> Result<core::ptr::NonNull<[u8]>, core::alloc::AllocError> {
+        let allocated = alloc::alloc::Global.allocate(layout)?;
+        self.0
+            .fetch_add(layout.size(), std::sync::atomic::Ordering::Relaxed);
+        Ok(allocated)
+    }
+
+    unsafe fn deallocate(&self, ptr: core::ptr::NonNull<u8>, layout: core::alloc::Layout) {
+        self.0
+            .fetch_sub(layout.size(), std::sync::atomic::Ordering::Relaxed);
+        unsafe { alloc::alloc::Global.deallocate(ptr, layout) }
+    }
+}
+
+/// test_context_allocator checks that the buffers of a solver created from a context are allocated in its allocator
+#[test]
+fn test_context_allocator() {
+    initialize_tracing();
+
+    let frame = serde_json::from_str::<fold::FrameCore>(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [0, 2]],
+            "edges_assignment": ["B", "B", "B", "B", "V"],
+            "edges_foldAngle": [0, 0, 0, 0, 90],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3]]
+        }"#,
+    )
+    .expect("source deserialization (json/fold file) failed");
+
+    let allocator = CountingAllocator::default();
+    let context = rtori_core::Context::new(allocator.clone());
+    let mut solver = context
+        .create_os_solver(rtori_core::os_solver::BackendFlags::CPU)
+        .block_on()
+        .unwrap();
+    assert_eq!(allocator.0.load(std::sync::atomic::Ordering::Relaxed), 0);
+
+    solver.load_fold_in(&frame, alloc::alloc::Global).unwrap();
+    assert!(allocator.0.load(std::sync::atomic::Ordering::Relaxed) > 0);
+
+    solver.set_fold_percentage(1.0).unwrap();
+    solver.step(10).expect("Step failed");
+
+    drop(solver);
+    assert_eq!(allocator.0.load(std::sync::atomic::Ordering::Relaxed), 0);
+}
+
+/// test_family_registry drives an Origami Simulator solver through the family-agnostic trait,
+/// checking it matches the solver used directly
+#[test]
+fn test_family_registry() {
+    use rtori_core::family::{Capabilities, Family, OperationError, Parameter};
+
+    initialize_tracing();
+
+    let frame = serde_json::from_str::<fold::FrameCore>(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [0, 2]],
+            "edges_assignment": ["B", "B", "B", "B", "V"],
+            "edges_foldAngle": [0, 0, 0, 0, 90],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3]]
+        }"#,
+    )
+    .expect("source deserialization (json/fold file) failed");
+
+    let mut solver = Family::OrigamiSimulator
+        .create_in(
+            rtori_core::os_solver::BackendFlags::CPU,
+            alloc::alloc::Global,
+        )
+        .block_on()
+        .unwrap();
+    assert_eq!(solver.family(), Family::OrigamiSimulator);
+    assert!(solver.capabilities().contains(Capabilities::STEP));
+    assert_eq!(solver.node_count(), None);
+    assert_eq!(solver.step(1), Err(OperationError::NotLoaded));
+
+    solver.load_fold(&frame).unwrap();
+    assert_eq!(solver.node_count(), Some(4));
+    assert_eq!(
+        solver.set_parameter(Parameter::NodeConstraint {
+            node_index: 4,
+            constraint: None,
+        }),
+        Err(OperationError::OutOfRange { index: 4, count: 4 })
+    );
+    assert!(solver.capabilities().contains(
+        Capabilities::CREASE_STIFFNESS | Capabilities::NODE_MASS | Capabilities::NODE_FIXED
+    ));
+    assert_eq!(
+        solver.set_parameter(Parameter::CreaseStiffness {
+            crease_index: 0,
+            stiffness: f32::NAN,
+        }),
+        Err(OperationError::InvalidValue)
+    );
+    assert_eq!(
+        solver.set_parameter(Parameter::NodeMass {
+            node_index: 0,
+            mass: 0.0,
+        }),
+        Err(OperationError::InvalidValue)
+    );
+    solver
+        .set_parameter(Parameter::FoldPercentage(1.0))
+        .unwrap();
+    solver
+        .set_parameter(Parameter::CreaseStiffness {
+            crease_index: 0,
+            stiffness: 2.0,
+        })
+        .unwrap();
+    solver
+        .set_parameter(Parameter::NodeMass {
+            node_index: 1,
+            mass: 2.0,
+        })
+        .unwrap();
+    solver
+        .set_parameter(Parameter::NodeFixed {
+            node_index: 0,
+            fixed: true,
+        })
+        .unwrap();
+    solver.step(100).unwrap();
+
+    let mut direct =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    direct.load_fold_in(&frame, alloc::alloc::Global).unwrap();
+    direct.set_fold_percentage(1.0).unwrap();
+    direct.set_crease_stiffness(0, 2.0).unwrap();
+    direct.set_node_mass(1, 2.0).unwrap();
+    direct.set_node_fixed(0, true).unwrap();
+    direct.step(100).unwrap();
+
+    let mut through_trait = vec![rtori_os_model::Vector3F::default(); 4];
+    solver
+        .extract(rtori_os_model::ExtractFlags::POSITION, &mut |extractor| {
+            extractor.copy_node_position(&mut through_trait, 0);
+        })
+        .unwrap();
+    let mut directly = vec![rtori_os_model::Vector3F::default(); 4];
+    direct
+        .extract(rtori_os_model::ExtractFlags::POSITION)
+        .unwrap()
+        .copy_node_position(&mut directly, 0);
+    assert_eq!(through_trait, directly);
+}
+
+/// test_node_sources checks that the nodes of a cut model can be mapped back to the vertices of the FOLD frame
+#[test]
+fn test_node_sources() {
+    use rtori_core::family::{Family, OperationError};
+
+    initialize_tracing();
+
+    // A slit from the border to the center of a fan, whose border end is split in two nodes
+    let frame = serde_json::from_str::<fold::FrameCore>(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [0, 1], [-1, 0], [0, -1]],
+            "edges_vertices": [[0, 1], [0, 2], [0, 3], [0, 4], [1, 2], [2, 3], [3, 4], [4, 1]],
+            "edges_assignment": ["C", "V", "M", "V", "B", "B", "B", "B"],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3], [0, 3, 4], [0, 4, 1]]
+        }"#,
+    )
+    .expect("source deserialization (json/fold file) failed");
+
+    let mut direct =
+        rtori_core::os_solver::Solver::create(rtori_core::os_solver::BackendFlags::CPU)
+            .block_on()
+            .unwrap();
+    assert_eq!(direct.node_sources(), None);
+    direct.load_fold_in(&frame, alloc::alloc::Global).unwrap();
+    assert_eq!(direct.node_sources(), Some(&[0, 1, 2, 3, 4, 1][..]));
+
+    let mut solver = Family::OrigamiSimulator
+        .create_in(
+            rtori_core::os_solver::BackendFlags::CPU,
+            alloc::alloc::Global,
+        )
+        .block_on()
+        .unwrap();
+    let mut sources = [u32::MAX; 4];
+    assert_eq!(
+        solver.copy_node_source_vertex(&mut sources, 0),
+        Err(OperationError::NotLoaded)
+    );
+    solver.load_fold(&frame).unwrap();
+    assert_eq!(solver.node_count(), Some(6));
+    solver.copy_node_source_vertex(&mut sources, 2).unwrap();
+    assert_eq!(sources, [2, 3, 4, 1]);
+    assert_eq!(
+        solver.copy_node_source_vertex(&mut sources, 3),
+        Err(OperationError::OutOfRange { index: 6, count: 6 })
+    );
+
+    // Without cuts nor joins, every node is the vertex of the same index
+    const SIMPLE_VERTEX_FLAT: &'static str =
+        include_str!("../testdata/simpleVertex/simpleVertex_0.fold");
+    let parsed_input = serde_json::from_str::<fold::File>(SIMPLE_VERTEX_FLAT)
+        .expect("source deserialization (json/fold file) failed");
+    solver.load_fold(&parsed_input.key_frame).unwrap();
+    assert_eq!(solver.node_sources(), None);
+    solver.copy_node_source_vertex(&mut sources, 1).unwrap();
+    assert_eq!(sources, [1, 2, 3, 4]);
+}
@@ -0,0 +1,185 @@
+#![feature(allocator_api)]
+
+extern crate alloc;
+use rtori_core::rigid_solver::{CreaseKind, Solver};
+use rtori_os_model::ExtractorDyn;
+
+fn frame(json: &str) -> fold::FrameCore {
+    serde_json::from_str::<fold::FrameCore>(json)
+        .expect("source deserialization (json/fold file) failed")
+}
+
+fn positions(solver: &Solver, frame: &fold::FrameCore) -> Vec<[f32; 3]> {
+    let extractor = solver.extract().unwrap();
+    let mut offsets = vec![rtori_os_model::Vector3F::default(); extractor.count_nodes()];
+    assert!(extractor.copy_node_position(&mut offsets, 0));
+
+    // The extracted positions are offsets from the loaded ones (on the z=0 plane for 2D coordinates)
+    let nominal_positions = frame.vertices.coords.as_ref().unwrap().iter();
+    offsets
+        .into_iter()
+        .zip(nominal_positions)
+        .map(|(offset, coords)| [0, 1, 2].map(|i| coords.get(i).unwrap_or(&0.0) + offset.0[i]))
+        .collect()
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f32>()
+        .sqrt()
+}
+
+/// A degree-4 vertex in the middle of four triangles, with sector angles of 60°, 120°, 120° and 60°.
+/// It folds rigidly like a vertex of the Miura-ori, the creases at 60° and 300° being the zigzag
+/// and those at 0° and 180° the straight line crossing it.
+fn degree_four_vertex() -> fold::FrameCore {
+    let s = 3f32.sqrt() / 2.0;
+    frame(&format!(
+        r#"{{
+            "vertices_coords": [[0, 0], [1, 0], [0.5, {s}], [-1, 0], [0.5, -{s}]],
+            "edges_vertices": [[0, 1], [0, 2], [0, 3], [0, 4], [1, 2], [2, 3], [3, 4], [4, 1]],
+            "edges_assignment": ["V", "M", "M", "M", "B", "B", "B", "B"],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3], [0, 3, 4], [0, 4, 1]]
+        }}"#
+    ))
+}
+
+/// test_single_fold checks the placement of a square folded along its diagonal
+#[test]
+fn test_single_fold() {
+    let square = frame(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [0, 2]],
+            "edges_assignment": ["B", "B", "B", "B", "V"],
+            "edges_foldAngle": [0, 0, 0, 0, 90],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3]]
+        }"#,
+    );
+
+    let mut solver = Solver::new();
+    solver.load_fold_in(&square, alloc::alloc::Global).unwrap();
+    assert_eq!(solver.crease_count(), Some(1));
+    assert_eq!(solver.crease_kind(0), Some(CreaseKind::Driving));
+
+    // Flat until folded
+    let rest = positions(&solver, &square);
+    assert!(distance(rest[3], [0.0, 1.0, 0.0]) < 1e-6);
+
+    // A valley fold brings the faces together on the side of their normal (+z, as they're counterclockwise)
+    solver.set_fold_percentage(1.0);
+    let folded = positions(&solver, &square);
+    let half = 0.5f32.sqrt();
+    assert!(distance(folded[0], [0.0, 0.0, 0.0]) < 1e-6);
+    assert!(distance(folded[1], [1.0, 0.0, 0.0]) < 1e-6);
+    assert!(distance(folded[3], [0.5, 0.5, half]) < 1e-6, "{folded:?}");
+    assert!((solver.fold_angle(0).unwrap() - core::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    assert!(solver.closure_error().unwrap() < 1e-6);
+
+    // Mountain the other way
+    solver
+        .set_crease_target_fold_angle(0, -core::f32::consts::FRAC_PI_2)
+        .unwrap();
+    assert!(distance(positions(&solver, &square)[3], [0.5, 0.5, -half]) < 1e-6);
+
+    assert!(matches!(
+        solver.set_crease_target_fold_angle(1, 0.0),
+        Err(rtori_core::family::OperationError::OutOfRange { index: 1, count: 1 })
+    ));
+}
+
+/// test_loop_closure checks that fold angles incompatible with rigid folding are reported as node error,
+/// and that leaving folds free lets them follow a driving one
+#[test]
+fn test_loop_closure() {
+    let vertex = degree_four_vertex();
+    let mut solver = Solver::new();
+    solver.load_fold_in(&vertex, alloc::alloc::Global).unwrap();
+    assert_eq!(solver.crease_count(), Some(4));
+
+    // Folding every crease by the same amount doesn't close around the vertex
+    solver.set_fold_percentage(0.5);
+    assert!(solver.closure_error().unwrap() > 1e-2);
+
+    // Only the last crease is driving, the others follow
+    solver.set_fold_percentage(0.0);
+    for crease_index in 0..3 {
+        solver.set_crease_driving(crease_index, false).unwrap();
+    }
+    assert_eq!(solver.crease_kind(0), Some(CreaseKind::Free));
+    for step in 1..=9 {
+        let fold_percentage = step as f32 / 10.0;
+        solver.set_fold_percentage(fold_percentage);
+
+        assert!(
+            solver.closure_error().unwrap() < 1e-5,
+            "at {fold_percentage}: {:?}",
+            solver.closure_error()
+        );
+        let angles: Vec<f32> = (0..4).map(|i| solver.fold_angle(i).unwrap()).collect();
+        assert!((angles[3] + fold_percentage * core::f32::consts::PI).abs() < 1e-5);
+        // The zigzag folds as one, while the straight line switches from valley to mountain at the vertex
+        assert!((angles[1] - angles[3]).abs() < 1e-4, "{angles:?}");
+        assert!((angles[0] + angles[2]).abs() < 1e-4, "{angles:?}");
+        assert!(angles[0] > 0.0, "{angles:?}");
+    }
+
+    // The nodes stay where the driving crease puts them
+    let folded = positions(&solver, &vertex);
+    for node in [0, 1] {
+        let rest = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]][node];
+        assert!(distance(folded[node], rest) < 1e-5, "{folded:?}");
+    }
+}
+
+/// test_family_trait drives the rigid solver through the family registry
+#[test]
+fn test_family_trait() {
+    use pollster::FutureExt as _;
+    use rtori_core::family::{Capabilities, Family, OperationError, Parameter};
+
+    let mut solver = Family::RigidOrigami
+        .create_in(
+            rtori_core::os_solver::BackendFlags::CPU,
+            alloc::alloc::Global,
+        )
+        .block_on()
+        .unwrap();
+    assert!(!solver.capabilities().contains(Capabilities::STEP));
+    assert!(solver.capabilities().contains(Capabilities::EXTRACT_ERROR));
+
+    solver.load_fold(&degree_four_vertex()).unwrap();
+    solver
+        .set_parameter(Parameter::FoldPercentage(0.5))
+        .unwrap();
+    assert_eq!(
+        solver.step(1),
+        Err(OperationError::Unsupported(Capabilities::STEP))
+    );
+    assert_eq!(
+        solver.set_parameter(Parameter::FaceStiffness(1.0)),
+        Err(OperationError::Unsupported(Capabilities::FACE_STIFFNESS))
+    );
+
+    let mut errors = vec![0.0; 5];
+    solver
+        .extract(rtori_os_model::ExtractFlags::all(), &mut |extractor| {
+            assert!(extractor.copy_node_error(&mut errors, 0));
+            let mut velocities = vec![rtori_os_model::Vector3F::default(); 5];
+            assert!(!extractor.copy_node_velocity(&mut velocities, 0));
+        })
+        .unwrap();
+    assert!(errors.iter().any(|error| *error > 1e-2), "{errors:?}");
+
+    assert!(matches!(
+        Family::RigidOrigami
+            .create_in(
+                rtori_core::os_solver::BackendFlags::GPU_ANY,
+                alloc::alloc::Global
+            )
+            .block_on(),
+        Err(rtori_core::family::CreationError::NoBackendMatching)
+    ));
+}
@@ -0,0 +1,124 @@
+#![feature(allocator_api)]
+
+extern crate alloc;
+use pollster::FutureExt as _;
+use rtori_core::family::{Capabilities, Family, OperationError, Parameter};
+use rtori_os_model::Vector3F;
+
+/// A square folded along its diagonal, held by the nodes of the crease
+fn held_square() -> fold::FrameCore {
+    serde_json::from_str::<fold::FrameCore>(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [0, 2]],
+            "edges_assignment": ["B", "B", "B", "B", "V"],
+            "edges_foldAngle": [0, 0, 0, 0, 90],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3]],
+            "rtori:os:vertices_fixed": [true, false, true, false]
+        }"#,
+    )
+    .expect("source deserialization (json/fold file) failed")
+}
+
+/// test_family_trait drives the static solver through the family registry, each parameter being solved for
+#[test]
+fn test_family_trait() {
+    let mut solver = Family::StaticEquilibrium
+        .create_in(
+            rtori_core::os_solver::BackendFlags::CPU,
+            alloc::alloc::Global,
+        )
+        .block_on()
+        .unwrap();
+    assert!(!solver.capabilities().contains(Capabilities::STEP));
+    assert!(solver.capabilities().contains(Capabilities::GRAVITY));
+    assert!(solver.capabilities().contains(Capabilities::NODE_FIXED));
+
+    assert_eq!(
+        solver.set_parameter(Parameter::CreaseTargetFoldAngle {
+            crease_index: 0,
+            angle: 1.0
+        }),
+        Err(OperationError::NotLoaded)
+    );
+    solver.load_fold(&held_square()).unwrap();
+    assert_eq!(
+        solver.step(1),
+        Err(OperationError::Unsupported(Capabilities::STEP))
+    );
+    assert_eq!(
+        solver.set_parameter(Parameter::ClearNodeConstraints),
+        Err(OperationError::Unsupported(Capabilities::NODE_CONSTRAINTS))
+    );
+
+    let corner_height = |solver: &dyn rtori_core::family::Solver| {
+        let mut offsets = vec![Vector3F::default(); 4];
+        solver
+            .extract(rtori_os_model::ExtractFlags::all(), &mut |extractor| {
+                assert!(extractor.copy_node_position(&mut offsets, 0));
+                let mut velocities = vec![Vector3F::default(); 4];
+                assert!(!extractor.copy_node_velocity(&mut velocities, 0));
+            })
+            .unwrap();
+        offsets[3].0[2]
+    };
+
+    // The valley fold raises the free corners, and gravity pulls them back down
+    solver
+        .set_parameter(Parameter::FoldPercentage(0.5))
+        .unwrap();
+    let folded = corner_height(&*solver);
+    assert!(folded > 0.1, "{folded}");
+    solver
+        .set_parameter(Parameter::Gravity(Vector3F([0.0, 0.0, -0.1])))
+        .unwrap();
+    let weighed = corner_height(&*solver);
+    assert!(weighed < folded, "{weighed} should be under {folded}");
+
+    solver.reset().unwrap();
+    assert!((corner_height(&*solver) - weighed).abs() < 1e-4);
+    assert_eq!(
+        solver.set_parameter(Parameter::NodeExternalForces {
+            forces: &[Vector3F::default(); 2],
+            offset: 3
+        }),
+        Err(OperationError::OutOfRange { index: 4, count: 4 })
+    );
+
+    assert_eq!(
+        solver.set_parameter(Parameter::NodeMass {
+            node_index: 3,
+            mass: f32::NAN
+        }),
+        Err(OperationError::InvalidValue)
+    );
+    // Once fixed, the free corner stays where it is, whatever the forces
+    solver
+        .set_parameter(Parameter::NodeFixed {
+            node_index: 3,
+            fixed: true,
+        })
+        .unwrap();
+    let held = corner_height(&*solver);
+    solver
+        .set_parameter(Parameter::Gravity(Vector3F([0.0, 0.0, -0.2])))
+        .unwrap();
+    assert!((corner_height(&*solver) - held).abs() < 1e-6);
+}
+
+/// test_reactions checks that the supports carry the weight of the model
+#[test]
+fn test_reactions() {
+    let mut solver = rtori_core::static_solver::Solver::new();
+    solver.set_gravity(Vector3F([0.0, 0.0, -0.1])).unwrap();
+    solver
+        .load_fold_in(&held_square(), alloc::alloc::Global)
+        .unwrap();
+    assert!(matches!(solver.last_solve(), Some(Ok(_))));
+
+    let reactions = solver.node_reaction().unwrap();
+    assert_eq!(reactions[1], Vector3F::default());
+    // Every node has a mass of 1
+    let lift = reactions[0].0[2] + reactions[2].0[2];
+    assert!((lift - 0.4).abs() < 1e-5, "{reactions:?}");
+}
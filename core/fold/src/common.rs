@@ -55,6 +55,14 @@ pub fn get_property<'a, T>(
         .map_or(Ok(None), |v| v.map(Some))
 }
 
+/// Fills `own` with a copy of `parent` when `own` is not set, as per the
+/// `frame_inherit` semantics of the FOLD specification
+pub(crate) fn inherit_property<T: Clone>(own: &mut Option<T>, parent: &Option<T>) {
+    if own.is_none() {
+        own.clone_from(parent);
+    }
+}
+
 // The goal is to put in the vertexInformation type declaration in there
 /*macro_rules! create_type {
 
@@ -1,4 +1,4 @@
-use crate::common::{self};
+use crate::common::inherit_property;
 use crate::Handful;
 use crate::Lockstep;
 
@@ -72,4 +72,14 @@ impl EdgeInformation {
     pub fn count(&self) -> usize {
         self.vertices.as_ref().map(|c| c.len()).unwrap_or(0)
     }
+
+    pub(crate) fn inherit_from(&mut self, parent: &Self) {
+        inherit_property(&mut self.vertices, &parent.vertices);
+        inherit_property(&mut self.faces, &parent.faces);
+        inherit_property(&mut self.assignments, &parent.assignments);
+        inherit_property(&mut self.fold_angles, &parent.fold_angles);
+        inherit_property(&mut self.length, &parent.length);
+        inherit_property(&mut self.crease_stiffness, &parent.crease_stiffness);
+        inherit_property(&mut self.axial_stiffness, &parent.axial_stiffness);
+    }
 }
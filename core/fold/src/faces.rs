@@ -38,10 +38,22 @@ pub struct FaceInformation {
     /// That is, for `rtori:faces_uvs[n][a] = k` assigns to the vertex index `faces_edges[n][a]` the uv `rtori:uvs[k]`
     #[serde(rename = "rtori:faces_uvs")]
     pub uvs: Option<Vec<Handful<u32, 8>>>,
+
+    /// For each face, a factor on the stiffness of the face in the simulation
+    #[serde(rename = "rtori:os:faces_stiffness")]
+    pub stiffness: Option<Vec<f32>>,
 }
 
 impl FaceInformation {
     pub fn count(&self) -> usize {
         self.vertices.as_ref().map(|c| c.len()).unwrap_or(0)
     }
+
+    pub(crate) fn inherit_from(&mut self, parent: &Self) {
+        inherit_property(&mut self.vertices, &parent.vertices);
+        inherit_property(&mut self.edges, &parent.edges);
+        inherit_property(&mut self.faces, &parent.faces);
+        inherit_property(&mut self.uvs, &parent.uvs);
+        inherit_property(&mut self.stiffness, &parent.stiffness);
+    }
 }
@@ -22,6 +22,16 @@ pub struct FrameMetadata {
     pub unit: Option<String>,
 }
 
+impl FrameMetadata {
+    pub(crate) fn inherit_from(&mut self, parent: &Self) {
+        inherit_property(&mut self.title, &parent.title);
+        inherit_property(&mut self.description, &parent.description);
+        inherit_property(&mut self.classes, &parent.classes);
+        inherit_property(&mut self.attributes, &parent.attributes);
+        inherit_property(&mut self.unit, &parent.unit);
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct FrameCore {
     #[serde(flatten)]
@@ -42,6 +52,18 @@ pub struct FrameCore {
     pub uvs: Lockstep<[f32; 2]>,
 }
 
+impl FrameCore {
+    /// Fills every property not set in this frame with the one of the given parent
+    pub fn inherit_from(&mut self, parent: &Self) {
+        self.metadata.inherit_from(&parent.metadata);
+        self.vertices.inherit_from(&parent.vertices);
+        self.edges.inherit_from(&parent.edges);
+        self.faces.inherit_from(&parent.faces);
+        self.layering.inherit_from(&parent.layering);
+        inherit_property(&mut self.uvs, &parent.uvs);
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct NonKeyFrame {
     #[serde(flatten)]
@@ -52,18 +74,102 @@ pub struct NonKeyFrame {
     pub inherit: Option<bool>,
 }
 
+/// Error returned when resolving the parent chain of a non-key frame fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameResolutionError {
+    /// The frame {frame_index} refers to the parent {parent_index}, which does not exist
+    ParentOutOfBounds {
+        frame_index: FrameIndex,
+        parent_index: FrameIndex,
+    },
+    /// The frame {frame_index} refers to the parent {parent_index}, which is already part of its ancestry
+    ParentCycle {
+        frame_index: FrameIndex,
+        parent_index: FrameIndex,
+    },
+}
+
+impl core::fmt::Display for FrameResolutionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ParentOutOfBounds {
+                frame_index,
+                parent_index,
+            } => write!(
+                f,
+                "frame {frame_index} refers to the parent frame {parent_index}, which does not exist"
+            ),
+            Self::ParentCycle {
+                frame_index,
+                parent_index,
+            } => write!(
+                f,
+                "frame {frame_index} refers to the parent frame {parent_index}, which creates a cycle"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for FrameResolutionError {}
+
 pub enum FrameRef<'a> {
     Key(&'a FrameCore),
-    NonKey(&'a NonKeyFrame),
+    NonKey {
+        file: &'a File,
+        index: FrameIndex,
+        frame: &'a NonKeyFrame,
+    },
 }
 
 impl<'a> FrameRef<'a> {
     /// To get a core frame, allocations may be needed to resolve the whole
     /// parenting/inheritance logic
-    pub fn get(&'a self) -> Cow<'a, FrameCore> {
-        match *self {
-            Self::Key(core) => Cow::Borrowed(core),
-            Self::NonKey(_nk) => unimplemented!(),
+    ///
+    /// A non-key frame with `frame_inherit` set to `true` gets every property it does not define
+    /// from its parent, recursively.
+    /// If no inheritance is needed, the frame is borrowed as-is.
+    pub fn get(&self) -> Result<Cow<'a, FrameCore>, FrameResolutionError> {
+        let (file, index, frame) = match *self {
+            Self::Key(core) => return Ok(Cow::Borrowed(core)),
+            Self::NonKey { file, index, frame } => (file, index, frame),
+        };
+
+        let mut resolved: Cow<'a, FrameCore> = Cow::Borrowed(&frame.frame);
+        let mut ancestry: Vec<FrameIndex> = Vec::new();
+        ancestry.push(index);
+
+        let (mut current_index, mut current) = (index, frame);
+        while current.inherit == Some(true) {
+            let Some(parent_index) = current.parent else {
+                break;
+            };
+
+            if ancestry.contains(&parent_index) {
+                return Err(FrameResolutionError::ParentCycle {
+                    frame_index: current_index,
+                    parent_index,
+                });
+            }
+            ancestry.push(parent_index);
+
+            match file.frame(parent_index) {
+                Some(Self::Key(core)) => {
+                    resolved.to_mut().inherit_from(core);
+                    break;
+                }
+                Some(Self::NonKey { frame: parent, .. }) => {
+                    resolved.to_mut().inherit_from(&parent.frame);
+                    (current_index, current) = (parent_index, parent);
+                }
+                None => {
+                    return Err(FrameResolutionError::ParentOutOfBounds {
+                        frame_index: current_index,
+                        parent_index,
+                    })
+                }
+            }
         }
+
+        Ok(resolved)
     }
 }
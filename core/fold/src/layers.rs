@@ -1,3 +1,4 @@
+use super::common::inherit_property;
 use super::indices::*;
 use super::Lockstep;
 
@@ -41,3 +42,10 @@ pub struct LayerInformation {
     #[serde(rename = "edgeOrders")]
     pub edge_orders: Lockstep<EdgeOrder>,
 }
+
+impl LayerInformation {
+    pub(crate) fn inherit_from(&mut self, parent: &Self) {
+        inherit_property(&mut self.face_orders, &parent.face_orders);
+        inherit_property(&mut self.edge_orders, &parent.edge_orders);
+    }
+}
@@ -39,9 +39,14 @@ pub mod macros;
 pub enum Field {
     FacesVertices,
     EdgesVertices,
+    EdgesAssignment,
     VerticesFaces,
     VerticesEdges,
     VerticesCoords,
+    VerticesMass,
+    VerticesFixed,
+    VerticesExternalForce,
+    FacesStiffness,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -74,7 +79,11 @@ impl File {
                 .frames
                 .as_ref()
                 .and_then(|frame_vec| frame_vec.get(usize::from(other - 1)))
-                .map(|frame| FrameRef::NonKey(frame)),
+                .map(|frame| FrameRef::NonKey {
+                    file: self,
+                    index: other,
+                    frame,
+                }),
         }
     }
 
@@ -140,4 +149,84 @@ mod tests {
         deserialize_thirteen_horns_augmented_triangulated,
         "../testdata/13-horns-123-vertices-augmented-triangulated.fold"
     );
+
+    const INHERITING: &'static str = r#"{
+        "vertices_coords": [[0, 0, 0], [1, 0, 0], [0, 1, 0]],
+        "edges_vertices": [[0, 1], [1, 2], [2, 0]],
+        "edges_assignment": ["B", "B", "B"],
+        "faces_vertices": [[0, 1, 2]],
+        "file_frames": [
+            {
+                "frame_parent": 0,
+                "frame_inherit": true,
+                "vertices_coords": [[0, 0, 0], [2, 0, 0], [0, 2, 0]]
+            },
+            {
+                "frame_parent": 1,
+                "frame_inherit": true,
+                "edges_assignment": ["B", "V", "B"]
+            },
+            {
+                "frame_parent": 0,
+                "frame_inherit": false,
+                "frame_title": "standalone"
+            },
+            {
+                "frame_parent": 42,
+                "frame_inherit": true
+            },
+            {
+                "frame_parent": 6,
+                "frame_inherit": true
+            },
+            {
+                "frame_parent": 5,
+                "frame_inherit": true
+            }
+        ]
+    }"#;
+
+    #[test]
+    pub fn resolve_inherited_frames() {
+        let file = serde_json::from_str::<File>(INHERITING).unwrap();
+        assert_eq!(file.frame_count(), 7);
+
+        let child = file.frame(1).unwrap().get().unwrap();
+        assert!(matches!(child, alloc::borrow::Cow::Owned(_)));
+        assert_eq!(child.vertices.coords.as_ref().unwrap()[1][0], 2.0);
+        assert_eq!(child.edges.count(), 3);
+        assert_eq!(child.faces.count(), 1);
+
+        let grandchild = file.frame(2).unwrap().get().unwrap();
+        assert_eq!(grandchild.vertices.coords.as_ref().unwrap()[2][1], 2.0);
+        assert_eq!(
+            grandchild.edges.assignments.as_deref(),
+            Some(&[EdgeAssignment::B, EdgeAssignment::V, EdgeAssignment::B][..])
+        );
+        assert_eq!(grandchild.faces.count(), 1);
+
+        let standalone = file.frame(3).unwrap().get().unwrap();
+        assert!(matches!(standalone, alloc::borrow::Cow::Borrowed(_)));
+        assert_eq!(standalone.vertices.count(), 0);
+    }
+
+    #[test]
+    pub fn resolve_invalid_frames() {
+        let file = serde_json::from_str::<File>(INHERITING).unwrap();
+
+        assert_eq!(
+            file.frame(4).unwrap().get().unwrap_err(),
+            FrameResolutionError::ParentOutOfBounds {
+                frame_index: 4,
+                parent_index: 42
+            }
+        );
+        assert_eq!(
+            file.frame(5).unwrap().get().unwrap_err(),
+            FrameResolutionError::ParentCycle {
+                frame_index: 6,
+                parent_index: 5
+            }
+        );
+    }
 }
@@ -31,12 +31,31 @@ pub struct VertexInformation {
     #[serde(rename = "vertices_faces")]
     pub faces: Lockstep<Handful<Option<FaceIndex>, 8>>,
 
-    #[serde(rename = "rtori:vertices_mass")]
-    pub sim_weight: Lockstep<f32>,
+    /// For each vertex, its mass in the simulation
+    #[serde(rename = "rtori:os:vertices_mass", alias = "rtori:vertices_mass")]
+    pub mass: Lockstep<f32>,
+
+    /// For each vertex, whether it is held in place during the simulation
+    #[serde(rename = "rtori:os:vertices_fixed")]
+    pub fixed: Lockstep<bool>,
+
+    /// For each vertex, a static force applied to it during the simulation
+    #[serde(rename = "rtori:os:vertices_externalForce")]
+    pub external_forces: Lockstep<[f32; 3]>,
 }
 
 impl VertexInformation {
     pub fn count(&self) -> usize {
         self.coords.as_ref().map(|c| c.len()).unwrap_or(0)
     }
+
+    pub(crate) fn inherit_from(&mut self, parent: &Self) {
+        inherit_property(&mut self.coords, &parent.coords);
+        inherit_property(&mut self.adjacent, &parent.adjacent);
+        inherit_property(&mut self.edges, &parent.edges);
+        inherit_property(&mut self.faces, &parent.faces);
+        inherit_property(&mut self.mass, &parent.mass);
+        inherit_property(&mut self.fixed, &parent.fixed);
+        inherit_property(&mut self.external_forces, &parent.external_forces);
+    }
 }
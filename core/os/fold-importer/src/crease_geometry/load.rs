@@ -4,20 +4,31 @@ use crate::ImportError;
 use alloc::alloc::Allocator;
 use alloc::vec::Vec;
 
-fn load_node_position<'output, O, I>(output: &mut O, input: &I)
+fn load_node_position<'output, O, I>(output: &mut O, input: &I, config: crate::ImportConfig)
 where
     O: rtori_os_model::LoaderDyn<'output> + 'output,
     I: crate::ImportInput,
 {
+    let external_forces = input.vertices_external_forces();
+    let fixed = input.vertices_fixed();
     for (i, vertex) in input.vertices_coords().iter().enumerate() {
         output.copy_node_position(&[rtori_os_model::Vector3F(vertex)], i as u32);
 
-        const BASE: rtori_os_model::NodeConfig = rtori_os_model::NodeConfig {
-            mass: 1.0,
-            fixed: 0,
+        // Buffers start zeroed, so there is only something to write for vertices with a static force
+        if let Some(external_force) = external_forces.as_ref().and_then(|forces| forces.get(i)) {
+            output.copy_node_external_forces(&[rtori_os_model::Vector3F(external_force)], i as u32);
+        }
+
+        let is_fixed = fixed
+            .as_ref()
+            .and_then(|fixed| fixed.get(i))
+            .unwrap_or(false);
+        let node_config = rtori_os_model::NodeConfig {
+            mass: super::node_mass(input, i, config),
+            fixed: u8::from(is_fixed),
             _reserved: [0; 3],
         };
-        output.copy_node_config(&[BASE], i as u32);
+        output.copy_node_config(&[node_config], i as u32);
     }
 }
 
@@ -26,12 +37,19 @@ where
     O: rtori_os_model::LoaderDyn<'output> + 'output,
     I: crate::ImportInput,
 {
+    let stiffnesses = input.faces_stiffnesses();
     for (face_index, face_vertices) in input.faces_vertices().iter().enumerate() {
         output.copy_face_indices(
             &[rtori_os_model::Vector3U(face_vertices)],
             face_index as u32,
         );
 
+        let stiffness = stiffnesses
+            .as_ref()
+            .and_then(|stiffnesses| stiffnesses.get(face_index))
+            .unwrap_or(1.0);
+        output.copy_face_stiffness(&[stiffness], face_index as u32);
+
         {
             let pos_for_node = |vertex_number: u8| {
                 let node_index = face_vertices[usize::from(vertex_number)];
@@ -81,7 +99,7 @@ where
             .edges_vertices()
             .get(crease.edge_index as usize)
             .ok_or(ImportError::InvalidCrease {
-                crease_index: u32::try_from(crease_index).unwrap(),
+                crease_index: crease_index as u32,
                 edge_index: crease.edge_index,
             })?;
 
@@ -94,10 +112,8 @@ where
 
         let crease_stiffness = input
             .edges_crease_stiffnesses()
-            .and_then(|v| {
-                v.get(crease.edge_index as usize)
-                    .expect("Crease refers to non-existing edge in edges_crease_stiffnesses")
-            })
+            .and_then(|v| v.get(crease.edge_index as usize))
+            .flatten()
             .unwrap_or(config.default_crease_stiffness);
 
         /*let axial_stiffness = input
@@ -132,7 +148,11 @@ where
         };
 
         let k = crease_stiffness * length;
-        let d = 0.0f32; //axial_stiffness / length;
+        let min_mass = vertex_indices
+            .map(|vertex_index| super::node_mass(input, vertex_index as usize, config))
+            .into_iter()
+            .fold(f32::INFINITY, f32::min);
+        let d = config.damping_percentage * 2.0 * f32::sqrt(k * min_mass);
 
         let parameters = rtori_os_model::CreaseParameters {
             target_fold_angle: crease.fold_angle,
@@ -166,7 +186,7 @@ where
         source: S,
         vertices_count: usize,
         allocator: A,
-    ) -> Vec<Vec<crate::creases::CreaseIndex, A>, A>
+    ) -> Result<Vec<Vec<crate::creases::CreaseIndex, A>, A>, ImportError>
     where
         S: AsRef<[crate::crease_geometry::CreaseNodePair]>,
         A: Allocator + Clone,
@@ -175,19 +195,26 @@ where
         // TODO: use a fixed capacity here for the switch to bump allocators
         mapping.resize(vertices_count, alloc::vec::Vec::new_in(allocator.clone()));
 
-        source.as_ref().iter().for_each(|pair| {
-            mapping[usize::try_from(pair.node_index).unwrap()].push(pair.crease_index);
-        });
+        source.as_ref().iter().try_for_each(|pair| {
+            mapping
+                .get_mut(pair.node_index as usize)
+                .ok_or(ImportError::CreaseNodeOutOfRange {
+                    crease_index: pair.crease_index,
+                    node_index: pair.node_index,
+                    vertex_count: vertices_count as u32,
+                })
+                .map(|crease_indices| crease_indices.push(pair.crease_index))
+        })?;
 
-        mapping
+        Ok(mapping)
     }
 
     let node_index_to_crease_indices =
-        create_node_index_to_crease_index(node_creases, vertices_count, allocator.clone());
+        create_node_index_to_crease_index(node_creases, vertices_count, allocator.clone())?;
     assert_eq!(node_index_to_crease_indices.len(), vertices_count);
 
     let node_index_to_inv_crease_indices =
-        create_node_index_to_crease_index(node_inv_creases, vertices_count, allocator.clone());
+        create_node_index_to_crease_index(node_inv_creases, vertices_count, allocator.clone())?;
     assert_eq!(node_index_to_inv_crease_indices.len(), vertices_count);
 
     let mut node_creases_cursor = 0;
@@ -241,7 +268,8 @@ where
                 crease_index: u32,
                 crease: &crate::creases::Crease,
                 edges_vertices: &EV,
-            ) where
+            ) -> Result<(), ImportError>
+            where
                 L: rtori_os_model::LoaderDyn<'loader>,
                 EV: crate::Proxy<'input, Output = [u32; 2]>,
             {
@@ -257,25 +285,32 @@ where
                 fn get_node_number_from_index<'a, EV>(
                     edges_vertices: &EV,
                     crease: &crate::creases::Crease,
+                    crease_index: u32,
                     node_index: u32,
-                ) -> NodeNumber
+                ) -> Result<NodeNumber, ImportError>
                 where
                     EV: crate::Proxy<'a, Output = [u32; 2]>,
                 {
                     if node_index == crease.faces[0].complement_vertex_index {
-                        NodeNumber::N1
+                        Ok(NodeNumber::N1)
                     } else if node_index == crease.faces[1].complement_vertex_index {
-                        NodeNumber::N2
+                        Ok(NodeNumber::N2)
                     } else {
                         let edge_vertex_indices = edges_vertices
                             .get(crease.edge_index as usize)
-                            .expect("edge should be defined");
+                            .ok_or(ImportError::InvalidCrease {
+                                crease_index,
+                                edge_index: crease.edge_index,
+                            })?;
                         if node_index == edge_vertex_indices[0] {
-                            NodeNumber::N3
+                            Ok(NodeNumber::N3)
                         } else if node_index == edge_vertex_indices[1] {
-                            NodeNumber::N4
+                            Ok(NodeNumber::N4)
                         } else {
-                            panic!("Not in crease")
+                            Err(ImportError::NodeNotInCrease {
+                                crease_index,
+                                node_index,
+                            })
                         }
                     }
                 }
@@ -284,8 +319,9 @@ where
                     // ...
                     edges_vertices,
                     &crease,
+                    crease_index,
                     node_index,
-                );
+                )?;
 
                 output.copy_node_crease(
                     &[rtori_os_model::NodeCreaseSpec {
@@ -294,6 +330,8 @@ where
                     }],
                     local_node_crease_index as u32,
                 );
+
+                Ok(())
             }
 
             let edges_vertices = input.edges_vertices();
@@ -302,7 +340,7 @@ where
                 .into_iter()
                 .map(|crease_index| (crease_index, creases[crease_index as usize]))
                 .enumerate()
-                .for_each(|(local_node_crease_index, (crease_index, crease))| {
+                .try_for_each(|(local_node_crease_index, (crease_index, crease))| {
                     process_node_crease(
                         output,
                         node_index,
@@ -310,8 +348,8 @@ where
                         crease_index as u32,
                         &crease,
                         &edges_vertices,
-                    );
-                });
+                    )
+                })?;
             node_creases_cursor += node_creases_len as u32;
 
             let node_inv_creases_len = node_inv_creases_for_this_node.len();
@@ -319,7 +357,7 @@ where
                 .into_iter()
                 .map(|crease_index| (crease_index, creases[crease_index as usize]))
                 .enumerate()
-                .for_each(|(local_node_crease_index, (crease_index, crease))| {
+                .try_for_each(|(local_node_crease_index, (crease_index, crease))| {
                     process_node_crease(
                         output,
                         node_index,
@@ -327,8 +365,8 @@ where
                         crease_index as u32,
                         &crease,
                         &edges_vertices,
-                    );
-                });
+                    )
+                })?;
             node_creases_cursor += node_inv_creases_len as u32;
         }
         // Load node-beams
@@ -338,63 +376,89 @@ where
             vertices_edges
                 .iter()
                 .map(|edge_index| {
-                    (
-                        edge_index,
-                        edge_vertex_indices.get(*edge_index as usize).unwrap(),
-                    )
+                    edge_vertex_indices
+                        .get(*edge_index as usize)
+                        .map(|edge_vertex_indices| (edge_index, edge_vertex_indices))
+                        .ok_or(ImportError::InvalidVertexEdge {
+                            vertex_index: node_index,
+                            edge_index: *edge_index,
+                        })
                 })
                 .enumerate()
-                .for_each(
-                    |(node_beam_local_index, (edge_index, edge_vertex_indices))| {
-                        let edge_vertices_coords: [[f32; 3]; 2] = edge_vertex_indices
-                            .map(|index| input.vertices_coords().get(index as usize).unwrap());
-
-                        let length = {
-                            let a = glam::Vec3::from(edge_vertices_coords[0]);
-                            let b = glam::Vec3::from(edge_vertices_coords[1]);
-
-                            (b - a).length()
-                        };
-
-                        let default_mass = config.default_mass;
-                        // TODO: recover the mass of the vertices
-                        let min_mass = default_mass;
-                        let axial_stiffness = input
-                            .edges_axial_stiffnesses()
-                            .and_then(|proxy| proxy.get(*edge_index as usize))
-                            .flatten()
-                            .unwrap_or(config.default_axial_stiffness);
-
-                        let k = axial_stiffness / length;
-                        let d = config.damping_percentage * 2.0 * f32::sqrt(k * min_mass);
-                        let this_node_index_in_edge = match edge_vertex_indices {
-                            [a, _] if a == node_index => 0,
-                            [_, a] if a == node_index => 1,
-                            _ => unreachable!(),
-                        };
-                        let neighbour_index_in_edge = 1 - this_node_index_in_edge;
-                        let neighbour_index = edge_vertex_indices[neighbour_index_in_edge];
-
-                        let spec = rtori_os_model::NodeBeamSpec {
-                            node_index,
-                            k,
-                            d,
-                            length,
-                            neighbour_index,
-                        };
-
-                        output.copy_node_beam(
-                            &[spec],
-                            node_beams_cursor + (node_beam_local_index as u32),
+                .try_for_each(|(node_beam_local_index, edge)| {
+                    let (edge_index, edge_vertex_indices) = edge?;
+                    let edge_vertices_coords: [[f32; 3]; 2] = [0, 1].try_map(|edge_member| {
+                        let pointing_to = edge_vertex_indices[usize::from(edge_member)];
+                        input.vertices_coords().get(pointing_to as usize).ok_or(
+                            ImportError::EdgesVerticesPointingToInvalidVertex {
+                                edge_index: *edge_index,
+                                edge_member,
+                                pointing_to,
+                                vertex_count: vertices_count as u32,
+                            },
                         )
-                    },
-                );
+                    })?;
+
+                    let length = {
+                        let a = glam::Vec3::from(edge_vertices_coords[0]);
+                        let b = glam::Vec3::from(edge_vertices_coords[1]);
+
+                        (b - a).length()
+                    };
+
+                    let min_mass = edge_vertex_indices
+                        .map(|index| super::node_mass(input, index as usize, config))
+                        .into_iter()
+                        .fold(f32::INFINITY, f32::min);
+                    let axial_stiffness = input
+                        .edges_axial_stiffnesses()
+                        .and_then(|proxy| proxy.get(*edge_index as usize))
+                        .flatten()
+                        .unwrap_or(config.default_axial_stiffness);
+
+                    let k = axial_stiffness / length;
+                    let d = config.damping_percentage * 2.0 * f32::sqrt(k * min_mass);
+                    let this_node_index_in_edge = match edge_vertex_indices {
+                        [a, _] if a == node_index => 0,
+                        [_, a] if a == node_index => 1,
+                        _ => {
+                            return Err(ImportError::InvalidVertexEdge {
+                                vertex_index: node_index,
+                                edge_index: *edge_index,
+                            })
+                        }
+                    };
+                    let neighbour_index_in_edge = 1 - this_node_index_in_edge;
+                    let neighbour_index = edge_vertex_indices[neighbour_index_in_edge];
+
+                    let spec = rtori_os_model::NodeBeamSpec {
+                        node_index,
+                        k,
+                        d,
+                        length,
+                        neighbour_index,
+                    };
+
+                    output.copy_node_beam(
+                        &[spec],
+                        node_beams_cursor + (node_beam_local_index as u32),
+                    );
+                    Ok(())
+                })?;
             node_beams_cursor += beams_count;
         }
 
         // Load node-faces
-        vertices_faces.iter().enumerate().for_each(
+        let face_count = input.faces_vertices().count();
+        vertices_faces.iter().enumerate().try_for_each(
             |(node_faces_offset_local, node_face_face_index)| {
+                if *node_face_face_index as usize >= face_count {
+                    return Err(ImportError::InvalidVertexFace {
+                        vertex_index: node_index,
+                        face_index: *node_face_face_index,
+                    });
+                }
+
                 let index = node_faces_cursor + (node_faces_offset_local as u32);
                 output.copy_node_face(
                     &[rtori_os_model::NodeFaceSpec {
@@ -403,8 +467,9 @@ where
                     }],
                     index,
                 );
+                Ok(())
             },
-        );
+        )?;
         node_faces_cursor += faces_count;
     }
 
@@ -432,7 +497,7 @@ where
         let node_inv_creases = &crease_geometry.node_creases_adjacent;
         let node_creases = &crease_geometry.node_creases_complement;
 
-        load_node_position(output, input);
+        load_node_position(output, input, config);
         load_faces(output, input)?;
         load_creases(output, input, creases, config)?;
         load_node_geometry(
@@ -1,19 +1,11 @@
-mod dt;
-pub use dt::*;
-
 mod load;
 pub use load::*;
 
 use crate::creases::Crease;
+use crate::input::Proxy;
 use core::alloc::Allocator;
 use rtori_os_model::ModelSize;
 
-pub enum InvalidationMask {
-    None,
-    InvalidateAll,
-    // TODO: bitvec to do partial invalidation
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub(crate) struct CreaseNodePair {
     pub crease_index: u32,
@@ -109,7 +101,7 @@ where
                 .edges_vertices()
                 .get(crease.edge_index as usize)
                 .ok_or(PreprocessingError::InvalidCrease {
-                    crease_index: u32::try_from(crease_index).unwrap(),
+                    crease_index: crease_index as u32,
                     edge_index: crease.edge_index,
                 })?;
 
@@ -195,3 +187,14 @@ impl core::fmt::Display for PreprocessingError {
 }
 
 impl core::error::Error for PreprocessingError {}
+
+/// The mass of a vertex, as given by the input or the configured default
+fn node_mass<I>(input: &I, node_index: usize, config: crate::ImportConfig) -> f32
+where
+    I: crate::ImportInput,
+{
+    input
+        .vertices_mass()
+        .and_then(|masses| masses.get(node_index))
+        .unwrap_or(config.default_mass)
+}
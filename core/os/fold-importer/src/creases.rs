@@ -41,6 +41,12 @@ pub enum ExtractCreasesIteratorErrorKind {
         face_number: usize,
         face_index: usize,
     },
+    /// edge #{edge_index}'s face #{face_number} (index {face_index}) doesn't exist, as there are only {face_count} faces
+    FaceOutOfRange {
+        face_number: usize,
+        face_index: usize,
+        face_count: usize,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -60,7 +66,9 @@ impl core::fmt::Display for ExtractCreasesIteratorError {
             ExtractCreasesIteratorErrorKind::InvalidFaceVertices { face_number, face_index }
                 => write!(f, "edge #{edge_index}'s face #{face_number} (index {face_index}) does not contain both vertices of the edge"),
             ExtractCreasesIteratorErrorKind::FaceHasTwiceTheSameVertex { face_number, face_index }
-                => write!(f, "edge #{edge_index}'s face #{face_number} (index {face_index}) contains at least twice same vertex index")
+                => write!(f, "edge #{edge_index}'s face #{face_number} (index {face_index}) contains at least twice same vertex index"),
+            ExtractCreasesIteratorErrorKind::FaceOutOfRange { face_number, face_index, face_count }
+                => write!(f, "edge #{edge_index}'s face #{face_number} (index {face_index}) doesn't exist, as there are only {face_count} faces")
         }
     }
 }
@@ -115,7 +123,8 @@ fn iter_edges<'a, FI: ExtractCreasesInput>(
             // No such field
             || itertools::Either::Right(core::iter::repeat(None)),
             // Such a field
-            |v| itertools::Either::Left(v.iter().map(|i| i)),
+            // Edges beyond the field (such as those added by the triangulation) have no fold angle
+            |v| itertools::Either::Left(v.iter().chain(core::iter::repeat(None))),
         )
         .into_iter();
 
@@ -130,14 +139,12 @@ fn iter_edges<'a, FI: ExtractCreasesInput>(
 }
 
 pub fn count_creases<'a, FI: ExtractCreasesInput>(input: &'a FI) -> usize {
-    input
-        .edges_assignment()
-        .iter()
-        .filter(|ea| {
-            matches!(
-                ea,
-                FoldAssignment::Facet | FoldAssignment::Mountain | FoldAssignment::Valley
-            )
+    iter_edges(input)
+        .filter(|edge_info| match edge_info.assignment {
+            FoldAssignment::Facet | FoldAssignment::Mountain | FoldAssignment::Valley => true,
+            // Only a join between two faces has something to glue
+            FoldAssignment::Join => edge_info.face_indices.len() == 2,
+            _ => false,
         })
         .count()
 }
@@ -154,7 +161,7 @@ pub fn extract_creases<'a, FI: ExtractCreasesInput>(
     let faces_vertices = input.faces_vertices();
 
     iterator
-        // Filter out the irrelevant folds (non-mountain, valley or facet)
+        // Filter out the irrelevant folds (non-mountain, valley, facet or join)
         .enumerate()
         .filter_map(move |(edge_index, edge_info)| {
 
@@ -164,6 +171,8 @@ pub fn extract_creases<'a, FI: ExtractCreasesInput>(
                 (FoldAssignment::Mountain, None) => Some(default_mountain_fold_angle),
                 (FoldAssignment::Valley, None) => Some(default_valley_fold_angle),
                 (FoldAssignment::Facet, None) => Some(0.0),
+                // Joined faces are glued flat, unless told otherwise
+                (FoldAssignment::Join, fold_angle) if edge_info.face_indices.len() == 2 => Some(fold_angle.unwrap_or(0.0)),
                 _ => None
             }.map(|fold_angle| (
                 edge_index,
@@ -174,7 +183,7 @@ pub fn extract_creases<'a, FI: ExtractCreasesInput>(
         })
         .map(move |(edge_index, vertex, faces, fold_angle)| {
             if faces.len() < 2 {
-                // When an edge is M, V or F (as it should now be), then it must have at least two faces
+                // When an edge is M, V, F or J (as it should now be), then it must have at least two faces
                 return Err(ExtractCreasesIteratorError{
                     edge_index,
                     kind: ExtractCreasesIteratorErrorKind::EdgeHasInvalidNumberOfFaces{
@@ -184,8 +193,11 @@ pub fn extract_creases<'a, FI: ExtractCreasesInput>(
             }
 
             let per_face = |face_number| {
-                let face_index = usize::try_from(faces[face_number]).unwrap();
-                let indices: Vector3U = faces_vertices.get(face_index).unwrap();
+                let face_index = faces[face_number] as usize;
+                let indices: Vector3U = faces_vertices.get(face_index).ok_or(ExtractCreasesIteratorError{
+                    edge_index,
+                    kind: ExtractCreasesIteratorErrorKind::FaceOutOfRange { face_number, face_index, face_count: faces_vertices.count() }
+                })?;
                 if indices.len() != 3 {
                     return Err(ExtractCreasesIteratorError{
                         edge_index,
@@ -212,13 +224,8 @@ pub fn extract_creases<'a, FI: ExtractCreasesInput>(
                     });
                 }
 
-                let complement_vertex_index_index = [0, 1, 2]
-                    .into_iter()
-                    .find(|face_vertex_index| {
-                        let face_vertex_index = usize::try_from(*face_vertex_index).unwrap();
-                        face_vertex_index != v0_idx && face_vertex_index != v1_idx
-                    })
-                    .expect("Should find the complement given we checked every conceivable reason it wouldn't give it");
+                // The three positions sum up to 3, so the one left is what the edge's two don't add up to
+                let complement_vertex_index_index = 3 - v0_idx - v1_idx;
 
                 let complement_vertex_index = indices[complement_vertex_index_index];
 
@@ -7,6 +7,10 @@ pub enum FoldAssignment {
     Valley,
     Mountain,
     Facet,
+    /// Cut edges separate their faces, so they never become creases
+    Cut,
+    /// Join edges glue their faces together, as flat creases
+    Join,
     Other,
 }
 
@@ -28,7 +32,7 @@ where
     type Output = T;
 
     fn count(&self) -> usize {
-        todo!()
+        self.len()
     }
 
     fn get(&self, idx: usize) -> Option<Self::Output> {
@@ -64,6 +68,29 @@ pub trait ImportInput {
     where
         'call: 'output;
 
+    type VerticesExternalForces<'a>: Proxy<'a, Output = Vector3F>
+    where
+        Self: 'a;
+    fn vertices_external_forces<'call, 'output>(
+        &'call self,
+    ) -> Option<Self::VerticesExternalForces<'output>>
+    where
+        'call: 'output;
+
+    type VerticesMass<'a>: Proxy<'a, Output = f32>
+    where
+        Self: 'a;
+    fn vertices_mass<'call, 'output>(&'call self) -> Option<Self::VerticesMass<'output>>
+    where
+        'call: 'output;
+
+    type VerticesFixed<'a>: Proxy<'a, Output = bool>
+    where
+        Self: 'a;
+    fn vertices_fixed<'call, 'output>(&'call self) -> Option<Self::VerticesFixed<'output>>
+    where
+        'call: 'output;
+
     type EdgeVertices<'a>: Proxy<'a, Output = Vector2U>
     where
         Self: 'a;
@@ -116,6 +143,14 @@ pub trait ImportInput {
     fn faces_vertices<'call, 'output>(&'call self) -> Self::FaceVertices<'output>
     where
         'call: 'output;
+
+    /// Indexed like `faces_vertices` (that is, by triangulated face)
+    type FaceStiffnesses<'a>: Proxy<'a, Output = f32>
+    where
+        Self: 'a;
+    fn faces_stiffnesses<'call, 'output>(&'call self) -> Option<Self::FaceStiffnesses<'output>>
+    where
+        'call: 'output;
 }
 
 macro_rules! subclass {
@@ -1,14 +1,17 @@
 //! Logic to import a FOLD frame into an Origami Simulator model solver
 //! The import works in several phases which build on top of each other.
 //!
-//! 1. `triangulation`
+//! 1. `supplement::kirigami`
+//!     The FOLD frame's cut edges are opened and its join edges welded, creating a new frame.
+//!     Mapping between the new vertices & edges and the previous ones is generated.
+//! 2. `triangulation`
 //!     The FOLD frame's faces are triangulated, creating facet creases as needed.
 //!     Mapping between the new faces and the previous ones is generated.
-//! 2. `supplement`
+//! 3. `supplement`
 //!     The given FOLD frame's missing information is computed and kept as
 //!     a `FoldSupplement` instance. When combined with the input, this is a `SupplementedInput`,
 //!     which implements the `ImportInput` trait needed to import.
-//! 3. `crease_geometry`
+//! 4. `crease_geometry`
 //!     The `SupplementedInput` is analysed for its creases, which are
 //!     extracted as well, stored in `CreaseGeometry`. This is the data, combined with the fold frame input,
 //!     that is sufficient to be loaded into an Origami Simulator model solver.
@@ -57,6 +60,27 @@ pub enum ImportError {
         pointing_to: u32,
         vertex_count: u32,
     },
+    /// In `vertices_edges`, vertex {vertex_index} refers to edge {edge_index}, which is not present in the input or doesn't contain it
+    InvalidVertexEdge {
+        vertex_index: u32,
+        edge_index: u32,
+    },
+    /// In `vertices_faces`, vertex {vertex_index} refers to face {face_index}, which is not present in the input
+    InvalidVertexFace {
+        vertex_index: u32,
+        face_index: u32,
+    },
+    /// Crease {crease_index} refers to node {node_index}, but only {vertex_count} vertices are defined
+    CreaseNodeOutOfRange {
+        crease_index: u32,
+        node_index: u32,
+        vertex_count: u32,
+    },
+    /// Node {node_index} is attached to crease {crease_index}, but is none of its nodes
+    NodeNotInCrease {
+        crease_index: u32,
+        node_index: u32,
+    },
     PreprocessingError(crate::crease_geometry::PreprocessingError),
 }
 
@@ -69,6 +93,14 @@ impl core::fmt::Display for ImportError {
                 => write!(f, "crease {crease_index} refers to an edge {edge_index} that is not present in the input"),
             Self::EdgesVerticesPointingToInvalidVertex { edge_index, edge_member, pointing_to, vertex_count }
                 => write!(f, "in `edges_vertices`, {edge_index}'s member #{edge_member} refers to vertex index {pointing_to}, but only {vertex_count} vertices are defined"),
+            Self::InvalidVertexEdge { vertex_index, edge_index }
+                => write!(f, "in `vertices_edges`, vertex {vertex_index} refers to edge {edge_index}, which is not present in the input or doesn't contain it"),
+            Self::InvalidVertexFace { vertex_index, face_index }
+                => write!(f, "in `vertices_faces`, vertex {vertex_index} refers to face {face_index}, which is not present in the input"),
+            Self::CreaseNodeOutOfRange { crease_index, node_index, vertex_count }
+                => write!(f, "crease {crease_index} refers to node {node_index}, but only {vertex_count} vertices are defined"),
+            Self::NodeNotInCrease { crease_index, node_index }
+                => write!(f, "node {node_index} is attached to crease {crease_index}, but is none of its nodes"),
             Self::PreprocessingError(p) => write!(f, "preprocessing error: {p}")
         }
     }
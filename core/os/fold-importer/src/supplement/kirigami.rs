@@ -0,0 +1,412 @@
+//! Kirigami support: cut (`C`) edges are opened by splitting the vertices they separate,
+//! and join (`J`) edges without any face weld their two vertices together.
+//!
+//! As this changes the vertices and edges of the frame, the result is a new frame,
+//! along with a [`KirigamiDiff`] mapping its vertices and edges back to the source ones.
+use core::alloc::Allocator;
+
+extern crate alloc;
+use alloc::{collections::BTreeMap, vec::Vec};
+use fold::{EdgeAssignment, EdgeIndex, VertexIndex};
+
+use super::TransformError;
+
+/// Maps the vertices & edges of a cut & joined frame back to the ones of its source
+pub struct KirigamiDiff<A>
+where
+    A: Allocator,
+{
+    /// For each vertex of the result, the vertex of the source it comes from.
+    /// A vertex split by a cut appears several times, vertices welded together only once.
+    pub vertices_source: Vec<VertexIndex, A>,
+    /// For each edge of the result, the edge of the source it comes from.
+    /// A cut edge appears once per side it separates, and join edges used for welding not at all.
+    pub edges_source: Vec<EdgeIndex, A>,
+}
+
+/// A frame whose cuts have been opened and whose joins have been welded
+pub struct KirigamiFrame<A>
+where
+    A: Allocator,
+{
+    pub frame: fold::FrameCore,
+    pub diff: KirigamiDiff<A>,
+}
+
+/// A union-find over indices, keeping the smallest index of each set as its root
+struct DisjointSets<A: Allocator>(Vec<u32, A>);
+
+impl<A: Allocator> DisjointSets<A> {
+    fn new_in(count: usize, allocator: A) -> Self {
+        let mut parents = Vec::with_capacity_in(count, allocator);
+        parents.extend(0..count as u32);
+        Self(parents)
+    }
+
+    fn find(&mut self, mut index: u32) -> u32 {
+        while self.0[index as usize] != index {
+            let grandparent = self.0[self.0[index as usize] as usize];
+            self.0[index as usize] = grandparent;
+            index = grandparent;
+        }
+        index
+    }
+
+    fn union(&mut self, a: u32, b: u32) {
+        let (a, b) = (self.find(a), self.find(b));
+        let (root, child) = if a < b { (a, b) } else { (b, a) };
+        self.0[child as usize] = root;
+    }
+}
+
+const fn sorted(edge: [VertexIndex; 2]) -> [VertexIndex; 2] {
+    if edge[0] < edge[1] {
+        edge
+    } else {
+        [edge[1], edge[0]]
+    }
+}
+
+/// Clones the elements of an optional per-element property in the order given by `sources`,
+/// dropping the property altogether if it doesn't cover every source
+fn remap<T: Clone>(values: &Option<Vec<T>>, sources: &[u32]) -> Option<Vec<T>> {
+    let values = values.as_ref()?;
+    sources
+        .iter()
+        .map(|source| values.get(*source as usize).cloned())
+        .collect()
+}
+
+/// Opens the cut edges and welds the join edges of the frame.
+///
+/// Returns `None` when the frame has neither, in which case it can be used as-is.
+pub fn cut_and_join_in<A: Allocator + Clone>(
+    input: &fold::FrameCore,
+    allocator: A,
+) -> Result<Option<KirigamiFrame<A>>, TransformError> {
+    let assignments = match input.edges.assignments.as_ref() {
+        Some(assignments)
+            if assignments
+                .iter()
+                .any(|assignment| matches!(assignment, EdgeAssignment::C | EdgeAssignment::J)) =>
+        {
+            assignments
+        }
+        _ => return Ok(None),
+    };
+    // The indices are used as-is below
+    super::validate(input)?;
+    let edges_vertices =
+        input
+            .edges
+            .vertices
+            .as_ref()
+            .ok_or(TransformError::MissingRequiredField(
+                fold::Field::EdgesVertices,
+            ))?;
+    let faces_vertices =
+        input
+            .faces
+            .vertices
+            .as_ref()
+            .ok_or(TransformError::MissingRequiredField(
+                fold::Field::FacesVertices,
+            ))?;
+    let vertex_count = input.vertices.count();
+
+    // Each face has a corner per vertex, which ends up as a vertex of its own when separated by cuts
+    let mut corner_vertices = Vec::new_in(allocator.clone());
+    let mut face_sides =
+        BTreeMap::<[VertexIndex; 2], Vec<[u32; 2], A>, A>::new_in(allocator.clone());
+    for face in faces_vertices.iter() {
+        let first_corner = corner_vertices.len() as u32;
+        corner_vertices.extend(face.iter().copied());
+
+        let order = face.len() as u32;
+        for i in 0..order {
+            let corners = [first_corner + i, first_corner + (i + 1) % order];
+            let vertices = corners.map(|corner| corner_vertices[corner as usize]);
+            // The corners are stored in the order of the sorted vertices
+            let corners = if vertices[0] < vertices[1] {
+                corners
+            } else {
+                [corners[1], corners[0]]
+            };
+            face_sides
+                .entry(sorted(vertices))
+                .or_insert_with(|| Vec::new_in(allocator.clone()))
+                .push(corners);
+        }
+    }
+
+    // Corners of neighbouring faces stay together, unless the side they share is cut
+    let mut corners = DisjointSets::new_in(corner_vertices.len(), allocator.clone());
+    {
+        let mut cut_sides = Vec::new_in(allocator.clone());
+        cut_sides.extend(
+            edges_vertices
+                .iter()
+                .zip(assignments.iter())
+                .filter(|(_, assignment)| **assignment == EdgeAssignment::C)
+                .map(|(edge, _)| sorted(edge.0)),
+        );
+
+        for (side, sides) in face_sides.iter() {
+            if cut_sides.contains(side) {
+                continue;
+            }
+            for other in &sides[1..] {
+                corners.union(sides[0][0], other[0]);
+                corners.union(sides[0][1], other[1]);
+            }
+        }
+    }
+
+    // The first group of corners around a vertex keeps it, the others get a new one
+    let mut extended_sources = Vec::with_capacity_in(vertex_count, allocator.clone());
+    extended_sources.extend(0..vertex_count as VertexIndex);
+    let mut corner_extended = Vec::with_capacity_in(corner_vertices.len(), allocator.clone());
+    {
+        let mut vertex_taken = Vec::with_capacity_in(vertex_count, allocator.clone());
+        vertex_taken.resize(vertex_count, false);
+        let mut root_extended = Vec::with_capacity_in(corner_vertices.len(), allocator.clone());
+        root_extended.resize(corner_vertices.len(), None);
+
+        for (corner, vertex) in corner_vertices.iter().copied().enumerate() {
+            let root = corners.find(corner as u32) as usize;
+            let extended = *root_extended[root].get_or_insert_with(|| {
+                if vertex_taken[vertex as usize] {
+                    extended_sources.push(vertex);
+                    (extended_sources.len() - 1) as VertexIndex
+                } else {
+                    vertex_taken[vertex as usize] = true;
+                    vertex
+                }
+            });
+            corner_extended.push(extended);
+        }
+    }
+
+    // Join edges without faces weld their vertices
+    let mut welded = DisjointSets::new_in(extended_sources.len(), allocator.clone());
+    for (edge, assignment) in edges_vertices.iter().zip(assignments.iter()) {
+        if *assignment == EdgeAssignment::J && !face_sides.contains_key(&sorted(edge.0)) {
+            welded.union(edge.0[0], edge.0[1]);
+        }
+    }
+
+    let mut vertices_source = Vec::new_in(allocator.clone());
+    let mut extended_vertex = Vec::with_capacity_in(extended_sources.len(), allocator.clone());
+    for (extended, source) in extended_sources.iter().copied().enumerate() {
+        let root = welded.find(extended as u32);
+        let vertex = if root as usize == extended {
+            vertices_source.push(source);
+            (vertices_source.len() - 1) as VertexIndex
+        } else {
+            // Roots being the smallest of their set, they have been given their vertex already
+            extended_vertex[root as usize]
+        };
+        extended_vertex.push(vertex);
+    }
+    let corner_vertex = |corner: u32| extended_vertex[corner_extended[corner as usize] as usize];
+
+    // Each edge is kept once per distinct pair of vertices it ends up between
+    let mut edges = Vec::<fold::EdgeVertexIndices>::new();
+    let mut edges_assignment = Vec::<EdgeAssignment>::new();
+    let mut edges_source = Vec::new_in(allocator.clone());
+    let mut edge_lookup = BTreeMap::<[VertexIndex; 2], usize, A>::new_in(allocator.clone());
+    for (edge_index, (edge, assignment)) in
+        edges_vertices.iter().zip(assignments.iter()).enumerate()
+    {
+        let side = sorted(edge.0);
+        let mut candidates = Vec::new_in(allocator.clone());
+        match face_sides.get(&side) {
+            None if *assignment == EdgeAssignment::J => continue,
+            None => candidates.push(edge.0.map(|vertex| extended_vertex[vertex as usize])),
+            Some(sides) => candidates.extend(sides.iter().map(|corners| {
+                let [a, b] = corners.map(corner_vertex);
+                // Keep the orientation of the source edge
+                if edge.0[0] == side[0] {
+                    [a, b]
+                } else {
+                    [b, a]
+                }
+            })),
+        }
+
+        for candidate in candidates {
+            if candidate[0] == candidate[1] {
+                continue;
+            }
+
+            match edge_lookup.get(&sorted(candidate)) {
+                // Two borders brought together by welds are glued
+                Some(existing) => {
+                    if edges_assignment[*existing] == EdgeAssignment::B {
+                        edges_assignment[*existing] = match assignment {
+                            EdgeAssignment::B => EdgeAssignment::J,
+                            other => *other,
+                        };
+                    }
+                }
+                None => {
+                    edge_lookup.insert(sorted(candidate), edges.len());
+                    edges.push(fold::EdgeVertexIndices(candidate));
+                    edges_assignment.push(*assignment);
+                    edges_source.push(edge_index as EdgeIndex);
+                }
+            }
+        }
+    }
+
+    let mut frame = input.clone();
+    frame.vertices = fold::VertexInformation {
+        coords: remap(&input.vertices.coords, &vertices_source),
+        adjacent: None,
+        edges: None,
+        faces: None,
+        mass: remap(&input.vertices.mass, &vertices_source),
+        fixed: remap(&input.vertices.fixed, &vertices_source),
+        external_forces: remap(&input.vertices.external_forces, &vertices_source),
+    };
+    frame.edges = fold::EdgeInformation {
+        vertices: Some(edges),
+        faces: None,
+        assignments: Some(edges_assignment),
+        fold_angles: remap(&input.edges.fold_angles, &edges_source),
+        length: remap(&input.edges.length, &edges_source),
+        crease_stiffness: remap(&input.edges.crease_stiffness, &edges_source),
+        axial_stiffness: remap(&input.edges.axial_stiffness, &edges_source),
+    };
+    frame.faces.vertices = Some({
+        let mut corner = 0;
+        faces_vertices
+            .iter()
+            .map(|face| {
+                let first_corner = corner;
+                corner += face.len() as u32;
+                fold::Face((first_corner..corner).map(corner_vertex).collect())
+            })
+            .collect()
+    });
+    frame.faces.edges = None;
+    frame.faces.faces = None;
+    frame.layering.edge_orders = None;
+
+    Ok(Some(KirigamiFrame {
+        frame,
+        diff: KirigamiDiff {
+            vertices_source,
+            edges_source,
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    fn frame(json: &str) -> fold::FrameCore {
+        serde_json::from_str(json).expect("invalid test frame")
+    }
+
+    fn faces(frame: &fold::FrameCore) -> Vec<Vec<u32>> {
+        let faces = frame.faces.vertices.as_ref().unwrap();
+        faces.iter().map(|face| face.0.clone()).collect()
+    }
+
+    #[test]
+    fn test_without_cuts_nor_joins() {
+        let frame = frame(
+            r#"{
+                "vertices_coords": [[0, 0], [1, 0], [1, 1]],
+                "edges_vertices": [[0, 1], [1, 2], [2, 0]],
+                "edges_assignment": ["B", "B", "B"],
+                "faces_vertices": [[0, 1, 2]]
+            }"#,
+        );
+
+        assert!(super::cut_and_join_in(&frame, alloc::alloc::Global)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_cut_across() {
+        // A square cut along its diagonal falls apart into two triangles
+        let frame = frame(
+            r#"{
+                "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1]],
+                "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [0, 2]],
+                "edges_assignment": ["B", "B", "B", "B", "C"],
+                "edges_foldAngle": [null, null, null, null, 12],
+                "faces_vertices": [[0, 1, 2], [0, 2, 3]]
+            }"#,
+        );
+
+        let cut = super::cut_and_join_in(&frame, alloc::alloc::Global)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cut.diff.vertices_source, [0, 1, 2, 3, 0, 2]);
+        assert_eq!(cut.diff.edges_source, [0, 1, 2, 3, 4, 4]);
+        assert_eq!(faces(&cut.frame), [[0, 1, 2], [4, 5, 3]]);
+        assert_eq!(
+            cut.frame.edges.fold_angles.as_ref().unwrap()[4..],
+            [Some(12.0), Some(12.0)]
+        );
+        assert_eq!(cut.frame.vertices.coords.as_ref().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_slit() {
+        // A slit from the border to the center of a fan only opens its border end
+        let frame = frame(
+            r#"{
+                "vertices_coords": [[0, 0], [1, 0], [0, 1], [-1, 0], [0, -1]],
+                "edges_vertices": [[0, 1], [0, 2], [0, 3], [0, 4], [1, 2], [2, 3], [3, 4], [4, 1]],
+                "edges_assignment": ["C", "F", "F", "F", "B", "B", "B", "B"],
+                "faces_vertices": [[0, 1, 2], [0, 2, 3], [0, 3, 4], [0, 4, 1]]
+            }"#,
+        );
+
+        let cut = super::cut_and_join_in(&frame, alloc::alloc::Global)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cut.diff.vertices_source, [0, 1, 2, 3, 4, 1]);
+        assert_eq!(faces(&cut.frame)[3], [0, 4, 5]);
+        assert_eq!(cut.diff.edges_source, [0, 0, 1, 2, 3, 4, 5, 6, 7]);
+        let edges = cut.frame.edges.vertices.as_ref().unwrap();
+        assert_eq!([edges[0].0, edges[1].0], [[0, 1], [0, 5]]);
+    }
+
+    #[test]
+    fn test_join_sheets() {
+        // Two triangles whose borders touch are glued by welding their vertices
+        let frame = frame(
+            r#"{
+                "vertices_coords": [[0, 0], [1, 0], [1, 1], [1, 1], [1, 0], [2, 1]],
+                "edges_vertices": [[0, 1], [1, 2], [2, 0], [3, 4], [4, 5], [5, 3], [1, 4], [2, 3]],
+                "edges_assignment": ["B", "B", "B", "B", "B", "B", "J", "J"],
+                "faces_vertices": [[0, 1, 2], [3, 4, 5]]
+            }"#,
+        );
+
+        let joined = super::cut_and_join_in(&frame, alloc::alloc::Global)
+            .unwrap()
+            .unwrap();
+        assert_eq!(joined.diff.vertices_source, [0, 1, 2, 5]);
+        assert_eq!(joined.diff.edges_source, [0, 1, 2, 4, 5]);
+        assert_eq!(faces(&joined.frame), [[0, 1, 2], [2, 1, 3]]);
+        assert_eq!(
+            joined.frame.edges.assignments.as_ref().unwrap(),
+            &[
+                fold::EdgeAssignment::B,
+                fold::EdgeAssignment::J,
+                fold::EdgeAssignment::B,
+                fold::EdgeAssignment::B,
+                fold::EdgeAssignment::B,
+            ]
+        );
+    }
+}
@@ -4,6 +4,8 @@ extern crate alloc;
 use alloc::vec::Vec;
 use fold::{EdgeIndex, FaceIndex, VertexIndex};
 
+pub mod kirigami;
+
 use crate::{
     input::{FoldAssignment, ImportInput, Proxy, Vector2U, Vector3F, Vector3U},
     triangulation::TriangulatedDiff,
@@ -54,73 +56,281 @@ where
     vertices_faces
 }
 
-/// In FOLD terms, compute `edges_faces` given `faces_vertices` and `edges_vertices`
-fn create_edges_faces<'a, A: Allocator + Clone>(
-    input: &'a fold::FrameCore,
+/// In FOLD terms, compute `edges_faces` given the triangulated faces and `edges_vertices`
+/// (the source edges followed by the ones added by the triangulation)
+fn create_edges_faces<EdgeVerticesSource, A>(
+    edge_vertices: EdgeVerticesSource,
+    triangulated: &TriangulatedDiff<A>,
     allocator: A,
-) -> Result<Vec<Vec<FaceIndex, A>, A>, ()> {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct Key([VertexIndex; 2]);
-    impl Key {
-        pub const fn new(src: [u32; 2]) -> Self {
-            // MAke sure that regardless of the sort order, we're the same key
-            let sorted = if src[0] < src[1] {
-                [src[0], src[1]]
-            } else {
-                [src[1], src[0]]
-            };
-            Self(sorted)
+) -> Result<Vec<Vec<FaceIndex, A>, A>, TransformError>
+where
+    EdgeVerticesSource: IntoIterator<Item = [VertexIndex; 2]>,
+    A: Allocator + Clone,
+{
+    let mut edge_lookup =
+        alloc::collections::BTreeMap::<[VertexIndex; 2], EdgeIndex, A>::new_in(allocator.clone());
+    for (edge_index, edge_vertex_indices) in edge_vertices.into_iter().enumerate() {
+        // Make sure that regardless of the orientation of the edge, we're the same key
+        let mut key = edge_vertex_indices;
+        key.sort_unstable();
+
+        if let Err(e) = edge_lookup.try_insert(key, edge_index as EdgeIndex) {
+            return Err(TransformError::DuplicateEdge {
+                edge_index: edge_index as EdgeIndex,
+                duplicate_of: *e.entry.get(),
+            });
         }
     }
 
-    let mut edge_to_face_map =
-        alloc::collections::BTreeMap::<Key, Vec<FaceIndex, A>, A>::new_in(allocator.clone());
+    let mut edges_faces = Vec::with_capacity_in(edge_lookup.len(), allocator.clone());
+    edges_faces.resize(edge_lookup.len(), Vec::new_in(allocator));
 
-    for (face_index, face_vertex_indices) in
-        input.faces.vertices.as_ref().unwrap().iter().enumerate()
+    for (face_index, (face_vertex_indices, source_face_index)) in
+        triangulated.iter_faces().enumerate()
     {
-        let face_order = face_vertex_indices.0.len();
-        for i in 0..face_order {
-            let u = face_vertex_indices.0[i];
-            let v = face_vertex_indices.0[(i + 1) % face_order];
-            let k = Key::new([u, v]);
-
-            let faces_for_edge = edge_to_face_map.try_insert(k, Vec::new_in(allocator.clone()));
-            match faces_for_edge {
-                Ok(inserted) => inserted.push(face_index as u32),
-                Err(mut e) => e.entry.get_mut().push(face_index as u32),
-            }
+        for i in 0..3 {
+            let vertices = [face_vertex_indices[i], face_vertex_indices[(i + 1) % 3]];
+            let mut key = vertices;
+            key.sort_unstable();
+
+            let edge_index = edge_lookup
+                .get(&key)
+                .ok_or(TransformError::FaceSideIsNotAnEdge {
+                    face_index: source_face_index,
+                    vertices,
+                })?;
+            edges_faces[*edge_index as usize].push(face_index as FaceIndex);
         }
     }
 
-    if edge_to_face_map.len() != input.edges.vertices.as_ref().map(|v| v.len()).unwrap() {
-        return Err(());
+    Ok(edges_faces)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransformError {
+    MissingRequiredField(fold::Field),
+    /// The vertex has {dimension} coordinates, while only two (lifted to z=0) or three are supported
+    InvalidVertexDimension {
+        vertex_index: VertexIndex,
+        dimension: usize,
+    },
+    /// The vertex has a mass of {mass}, while masses must be strictly positive
+    InvalidVertexMass {
+        vertex_index: VertexIndex,
+        mass: f32,
+    },
+    /// The field has {found} elements, while there are {expected} of the elements it describes
+    MismatchedFieldLength {
+        field: fold::Field,
+        expected: usize,
+        found: usize,
+    },
+    /// In `faces_vertices`, face {face_index}'s vertex #{vertex_number} refers to vertex index {points_to_vertex}, but only {vertex_count} vertices are defined
+    FaceVertexOutOfRange {
+        face_index: FaceIndex,
+        vertex_number: usize,
+        points_to_vertex: VertexIndex,
+        vertex_count: usize,
+    },
+    /// In `edges_vertices`, edge {edge_index}'s member #{edge_member} refers to vertex index {points_to_vertex}, but only {vertex_count} vertices are defined
+    EdgeVertexOutOfRange {
+        edge_index: EdgeIndex,
+        edge_member: usize,
+        points_to_vertex: VertexIndex,
+        vertex_count: usize,
+    },
+    /// Edge {edge_index} goes from a vertex to itself
+    DegenerateEdge {
+        edge_index: EdgeIndex,
+    },
+    /// Edge {edge_index} joins the same vertices as edge {duplicate_of}
+    DuplicateEdge {
+        edge_index: EdgeIndex,
+        duplicate_of: EdgeIndex,
+    },
+    /// The side of face {face_index} going from vertex {vertices[0]} to vertex {vertices[1]} isn't in `edges_vertices`
+    FaceSideIsNotAnEdge {
+        face_index: FaceIndex,
+        vertices: [VertexIndex; 2],
+    },
+    TriangulationError(crate::triangulation::Triangulate3DError),
+    IncorrectInput,
+}
+
+impl core::fmt::Display for TransformError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingRequiredField(field)
+                => write!(f, "the required field {field:?} is missing"),
+            Self::InvalidVertexDimension { vertex_index, dimension }
+                => write!(f, "vertex {vertex_index} has {dimension} coordinates, while only two or three are supported"),
+            Self::InvalidVertexMass { vertex_index, mass }
+                => write!(f, "vertex {vertex_index} has a mass of {mass}, while masses must be strictly positive"),
+            Self::MismatchedFieldLength { field, expected, found }
+                => write!(f, "the field {field:?} has {found} elements, while {expected} were expected"),
+            Self::FaceVertexOutOfRange { face_index, vertex_number, points_to_vertex, vertex_count }
+                => write!(f, "in `faces_vertices`, face {face_index}'s vertex #{vertex_number} refers to vertex index {points_to_vertex}, but only {vertex_count} vertices are defined"),
+            Self::EdgeVertexOutOfRange { edge_index, edge_member, points_to_vertex, vertex_count }
+                => write!(f, "in `edges_vertices`, edge {edge_index}'s member #{edge_member} refers to vertex index {points_to_vertex}, but only {vertex_count} vertices are defined"),
+            Self::DegenerateEdge { edge_index }
+                => write!(f, "edge {edge_index} goes from a vertex to itself"),
+            Self::DuplicateEdge { edge_index, duplicate_of }
+                => write!(f, "edge {edge_index} joins the same vertices as edge {duplicate_of}"),
+            Self::FaceSideIsNotAnEdge { face_index, vertices }
+                => write!(f, "the side of face {face_index} going from vertex {} to vertex {} isn't in `edges_vertices`", vertices[0], vertices[1]),
+            Self::TriangulationError(e) => write!(f, "triangulation error: {e}"),
+            Self::IncorrectInput => write!(f, "incorrect input"),
+        }
     }
+}
 
-    let mut edges_faces = Vec::with_capacity_in(input.edges.count(), allocator.clone());
-    edges_faces.resize(input.edges.count(), Vec::new_in(allocator));
+impl core::error::Error for TransformError {}
+
+/// Checks that an optional per-element field, if present, has one value per element
+fn validate_length<T>(
+    field: fold::Field,
+    values: Option<&[T]>,
+    expected: usize,
+) -> Result<(), TransformError> {
+    match values {
+        Some(values) if values.len() != expected => Err(TransformError::MismatchedFieldLength {
+            field,
+            expected,
+            found: values.len(),
+        }),
+        _ => Ok(()),
+    }
+}
 
-    for (edge_index, edge_vertex_indices) in
-        input.edges.vertices.as_ref().unwrap().iter().enumerate()
+/// Checks that the frame has the fields required for the import,
+/// that they only refer to vertices that exist, and that the extensions have one value per element
+pub(crate) fn validate(input: &fold::FrameCore) -> Result<(), TransformError> {
+    let vertices_coords =
+        input
+            .vertices
+            .coords
+            .as_ref()
+            .ok_or(TransformError::MissingRequiredField(
+                fold::Field::VerticesCoords,
+            ))?;
+    if let Some((vertex_index, vertex)) = vertices_coords
+        .iter()
+        .enumerate()
+        .find(|(_, vertex)| !matches!(vertex.len(), 2 | 3))
+    {
+        return Err(TransformError::InvalidVertexDimension {
+            vertex_index: vertex_index as VertexIndex,
+            dimension: vertex.len(),
+        });
+    }
+    let vertex_count = vertices_coords.len();
+
+    validate_length(
+        fold::Field::VerticesMass,
+        input.vertices.mass.as_deref(),
+        vertex_count,
+    )?;
+    validate_length(
+        fold::Field::VerticesFixed,
+        input.vertices.fixed.as_deref(),
+        vertex_count,
+    )?;
+    validate_length(
+        fold::Field::VerticesExternalForce,
+        input.vertices.external_forces.as_deref(),
+        vertex_count,
+    )?;
+
+    // A massless vertex would make the timestep & the damping derived from the masses degenerate
+    if let Some((vertex_index, mass)) = input
+        .vertices
+        .mass
+        .iter()
+        .flatten()
+        .copied()
+        .enumerate()
+        .find(|(_, mass)| !(mass.is_finite() && *mass > 0.0))
     {
-        let u = edge_vertex_indices.0[0];
-        let v = edge_vertex_indices.0[1];
-        let to_query = Key::new([u, v]);
+        return Err(TransformError::InvalidVertexMass {
+            vertex_index: vertex_index as VertexIndex,
+            mass,
+        });
+    }
 
-        let faces = edge_to_face_map.remove_entry(&to_query).unwrap();
-        // What to do with edges without faces ?
+    let faces_vertices =
+        input
+            .faces
+            .vertices
+            .as_ref()
+            .ok_or(TransformError::MissingRequiredField(
+                fold::Field::FacesVertices,
+            ))?;
+    validate_length(
+        fold::Field::FacesStiffness,
+        input.faces.stiffness.as_deref(),
+        faces_vertices.len(),
+    )?;
+    for (face_index, face) in faces_vertices.iter().enumerate() {
+        if let Some((vertex_number, points_to_vertex)) = face
+            .iter()
+            .copied()
+            .enumerate()
+            .find(|(_, vertex_index)| *vertex_index as usize >= vertex_count)
+        {
+            return Err(TransformError::FaceVertexOutOfRange {
+                face_index: face_index as FaceIndex,
+                vertex_number,
+                points_to_vertex,
+                vertex_count,
+            });
+        }
+    }
 
-        edges_faces[edge_index] = faces.1;
+    let edges_vertices =
+        input
+            .edges
+            .vertices
+            .as_ref()
+            .ok_or(TransformError::MissingRequiredField(
+                fold::Field::EdgesVertices,
+            ))?;
+    for (edge_index, edge) in edges_vertices.iter().enumerate() {
+        let edge_index = edge_index as EdgeIndex;
+        if let Some((edge_member, points_to_vertex)) = edge
+            .0
+            .into_iter()
+            .enumerate()
+            .find(|(_, vertex_index)| *vertex_index as usize >= vertex_count)
+        {
+            return Err(TransformError::EdgeVertexOutOfRange {
+                edge_index,
+                edge_member,
+                points_to_vertex,
+                vertex_count,
+            });
+        }
+        if edge.0[0] == edge.0[1] {
+            return Err(TransformError::DegenerateEdge { edge_index });
+        }
     }
 
-    Ok(edges_faces)
-}
+    let edges_assignment =
+        input
+            .edges
+            .assignments
+            .as_ref()
+            .ok_or(TransformError::MissingRequiredField(
+                fold::Field::EdgesAssignment,
+            ))?;
+    if edges_assignment.len() != edges_vertices.len() {
+        return Err(TransformError::MismatchedFieldLength {
+            field: fold::Field::EdgesAssignment,
+            expected: edges_vertices.len(),
+            found: edges_assignment.len(),
+        });
+    }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum TransformError {
-    MissingRequiredField(fold::Field),
-    TriangulationError(crate::triangulation::Triangulate3DError),
-    IncorrectInput,
+    Ok(())
 }
 
 /// The `FoldSupplement` is the additional information to the Fold Input,
@@ -133,18 +343,34 @@ where
     pub vertices_edges: Vec<Vec<EdgeIndex, A>, A>,
     pub vertices_faces: Vec<Vec<FaceIndex, A>, A>,
     pub edges_faces: Vec<Vec<FaceIndex, A>, A>,
+    /// When the source has cut or join edges, the frame they result in, which replaces the source
+    pub kirigami: Option<kirigami::KirigamiFrame<A>>,
 }
 
 impl<A> FoldSupplement<A>
 where
     A: Allocator,
 {
-    pub const fn with_fold<'frame>(
+    /// Combines the supplement with the frame it was computed from
+    /// (or, with cuts or joins, with the frame they result in)
+    pub fn with_fold<'frame>(
         &'frame self,
         frame: &'frame fold::FrameCore,
     ) -> SupplementedInput<'frame, A> {
+        let frame = self
+            .kirigami
+            .as_ref()
+            .map_or(frame, |kirigami| &kirigami.frame);
         SupplementedInput::new(frame, self)
     }
+
+    /// For each vertex of the frame given to [`FoldSupplement::with_fold`], the vertex of the source it comes from,
+    /// or `None` when they're the same (as the source has neither cut nor join edges)
+    pub fn vertices_source(&self) -> Option<&[VertexIndex]> {
+        self.kirigami
+            .as_ref()
+            .map(|kirigami| kirigami.diff.vertices_source.as_slice())
+    }
 }
 
 /// The `SupplementedInput` combines the fold source and the `FoldSupplement` to provide
@@ -173,6 +399,14 @@ where
         }
     }
 }
+
+/// Two-dimensional vertices are lifted onto the z=0 plane.
+///
+/// Vertices of other dimensions are rejected by [`transform_in`], so missing coordinates are only ever the z one.
+fn lift_vertex(vertex: &[f32]) -> [f32; 3] {
+    core::array::from_fn(|i| vertex.get(i).copied().unwrap_or(0.0))
+}
+
 pub struct VerticesCoords<'input, A>(&'input SupplementedInput<'input, A>)
 where
     A: Allocator;
@@ -194,13 +428,7 @@ where
             .coords
             .as_ref()
             .and_then(|v| v.get(idx))
-            .map(|slice| {
-                if slice.len() != 3 {
-                    panic!();
-                }
-
-                [slice[0], slice[1], slice[2]]
-            })
+            .map(|vertex| lift_vertex(vertex))
     }
 
     type Iter = impl core::iter::ExactSizeIterator<Item = Self::Output>;
@@ -210,16 +438,10 @@ where
             .source
             .vertices
             .coords
-            .as_ref()
-            .unwrap()
+            .as_deref()
+            .unwrap_or_default()
             .iter()
-            .map(|slice| {
-                if slice.len() != 3 {
-                    panic!();
-                }
-
-                [slice[0], slice[1], slice[2]]
-            })
+            .map(|vertex| lift_vertex(vertex))
     }
 }
 
@@ -253,29 +475,45 @@ const fn convert(ea: fold::EdgeAssignment) -> crate::input::FoldAssignment {
         fold::EdgeAssignment::M => crate::input::FoldAssignment::Mountain,
         fold::EdgeAssignment::V => crate::input::FoldAssignment::Valley,
         fold::EdgeAssignment::F => crate::input::FoldAssignment::Facet,
+        fold::EdgeAssignment::C => crate::input::FoldAssignment::Cut,
+        fold::EdgeAssignment::J => crate::input::FoldAssignment::Join,
         _ => crate::input::FoldAssignment::Other,
     }
 }
 
-pub struct TranslatingProxy<'a>(&'a [fold::EdgeAssignment]);
+/// Exposes the assignments of the source edges, followed by the edges added by the triangulation as facets
+pub struct TranslatingProxy<'a> {
+    source: &'a [fold::EdgeAssignment],
+    additional: usize,
+}
 impl<'a> Proxy<'a> for TranslatingProxy<'a> {
     type Output = crate::input::FoldAssignment;
 
     fn count(&self) -> usize {
-        self.0.len()
+        self.source.len() + self.additional
     }
 
     fn get(&self, idx: usize) -> Option<Self::Output> {
-        self.0.get(idx).map(|ea| convert(*ea))
+        match self.source.get(idx) {
+            Some(ea) => Some(convert(*ea)),
+            None if idx < self.count() => Some(crate::input::FoldAssignment::Facet),
+            None => None,
+        }
     }
 
     type Iter
-        = impl ExactSizeIterator<Item = Self::Output>
+        = impl Iterator<Item = Self::Output>
     where
         Self: 'a;
 
     fn iter(&self) -> Self::Iter {
-        self.0.iter().map(|ea| convert(*ea))
+        self.source
+            .iter()
+            .map(|ea| convert(*ea))
+            .chain(core::iter::repeat_n(
+                crate::input::FoldAssignment::Facet,
+                self.additional,
+            ))
     }
 }
 
@@ -308,6 +546,49 @@ impl<'a> Proxy<'a> for DegreesToRadiansProxy<'a> {
     }
 }
 
+/// Exposes a per-face property of the source by triangulated face,
+/// each triangle taking the value of the face it was cut from
+pub struct TriangulatedFaceProxy<'a, T, A>
+where
+    A: Allocator,
+{
+    source: &'a [T],
+    triangulated: &'a TriangulatedDiff<A>,
+}
+
+impl<'a, T, A> Proxy<'a> for TriangulatedFaceProxy<'a, T, A>
+where
+    T: Copy,
+    A: Allocator,
+{
+    type Output = T;
+
+    fn count(&self) -> usize {
+        self.triangulated.face_replacing.len()
+    }
+
+    fn get(&self, idx: usize) -> Option<Self::Output> {
+        self.triangulated
+            .face_replacing
+            .get(idx)
+            .and_then(|source_index| self.source.get(*source_index as usize))
+            .copied()
+    }
+
+    type Iter
+        = impl Iterator<Item = Self::Output>
+    where
+        Self: 'a;
+
+    fn iter(&self) -> Self::Iter {
+        let source = self.source;
+        self.triangulated
+            .face_replacing
+            .iter()
+            .map_while(move |source_index| source.get(*source_index as usize).copied())
+    }
+}
+
 impl<'input, A> crate::input::ImportInput for SupplementedInput<'input, A>
 where
     A: core::alloc::Allocator,
@@ -348,6 +629,44 @@ where
         VecWrapper(self.transformed.vertices_faces.as_slice())
     }
 
+    type VerticesExternalForces<'a>
+        = &'a [[f32; 3]]
+    where
+        Self: 'a;
+
+    fn vertices_external_forces<'call, 'output>(
+        &'call self,
+    ) -> Option<Self::VerticesExternalForces<'output>>
+    where
+        'call: 'output,
+    {
+        self.source.vertices.external_forces.as_deref()
+    }
+
+    type VerticesMass<'a>
+        = &'a [f32]
+    where
+        Self: 'a;
+
+    fn vertices_mass<'call, 'output>(&'call self) -> Option<Self::VerticesMass<'output>>
+    where
+        'call: 'output,
+    {
+        self.source.vertices.mass.as_deref()
+    }
+
+    type VerticesFixed<'a>
+        = &'a [bool]
+    where
+        Self: 'a;
+
+    fn vertices_fixed<'call, 'output>(&'call self) -> Option<Self::VerticesFixed<'output>>
+    where
+        'call: 'output,
+    {
+        self.source.vertices.fixed.as_deref()
+    }
+
     type EdgeVertices<'a>
         = impl crate::input::Proxy<'a, Output = [VertexIndex; 2]>
     where
@@ -357,34 +676,42 @@ where
     where
         'call: 'output,
     {
-        struct Source<'a>(&'a [fold::EdgeVertexIndices]);
+        /// The source edges, followed by the ones added by the triangulation
+        struct Source<'a> {
+            source: &'a [fold::EdgeVertexIndices],
+            additional: &'a [[VertexIndex; 2]],
+        }
         impl<'a> Proxy<'a> for Source<'a> {
             type Output = [VertexIndex; 2];
 
             fn count(&self) -> usize {
-                self.0.len()
+                self.source.len() + self.additional.len()
             }
 
             fn get(&self, idx: usize) -> Option<Self::Output> {
-                self.0.get(idx).map(|v| v.0)
+                match self.source.get(idx) {
+                    Some(v) => Some(v.0),
+                    None => self.additional.get(idx - self.source.len()).copied(),
+                }
             }
 
             type Iter
-                = impl ExactSizeIterator<Item = Self::Output>
+                = impl Iterator<Item = Self::Output>
             where
                 Self: 'a;
 
             fn iter(&self) -> Self::Iter {
-                self.0.iter().map(|inner| inner.0)
+                self.source
+                    .iter()
+                    .map(|inner| inner.0)
+                    .chain(self.additional.iter().copied())
             }
         }
 
-        self.source
-            .edges
-            .vertices
-            .as_ref()
-            .map(|v| Source(v.as_slice()))
-            .unwrap()
+        Source {
+            source: self.source.edges.vertices.as_deref().unwrap_or_default(),
+            additional: &self.transformed.triangulated.additional_edges,
+        }
     }
 
     type EdgeFaces<'a>
@@ -408,12 +735,10 @@ where
     where
         'call: 'output,
     {
-        self.source
-            .edges
-            .assignments
-            .as_ref()
-            .map(|v| TranslatingProxy(v.as_slice()))
-            .unwrap()
+        TranslatingProxy {
+            source: self.source.edges.assignments.as_deref().unwrap_or_default(),
+            additional: self.transformed.triangulated.additional_edges.len(),
+        }
     }
 
     type EdgeAxialStiffnesses<'a>
@@ -506,13 +831,38 @@ where
 
         TriangulatedProxy(&self.transformed.triangulated)
     }
+
+    type FaceStiffnesses<'a>
+        = TriangulatedFaceProxy<'a, f32, A>
+    where
+        Self: 'a;
+
+    fn faces_stiffnesses<'call, 'output>(&'call self) -> Option<Self::FaceStiffnesses<'output>>
+    where
+        'call: 'output,
+    {
+        self.source
+            .faces
+            .stiffness
+            .as_ref()
+            .map(|v| TriangulatedFaceProxy {
+                source: v.as_slice(),
+                triangulated: &self.transformed.triangulated,
+            })
+    }
 }
 
 pub fn transform_in<A: Allocator + Clone>(
     input: &fold::FrameCore,
     allocator: A,
 ) -> Result<FoldSupplement<A>, TransformError> {
-    // First, triangulate
+    validate(input)?;
+
+    // First, open the cuts and weld the joins, which replaces the input
+    let kirigami = kirigami::cut_and_join_in(input, allocator.clone())?;
+    let input = kirigami.as_ref().map_or(input, |kirigami| &kirigami.frame);
+
+    // Then, triangulate
     let triangulated = crate::triangulation::triangulate3d_collect(
         input
             .faces
@@ -532,7 +882,11 @@ pub fn transform_in<A: Allocator + Clone>(
     )
     .map_err(|e| TransformError::TriangulationError(e))?;
 
-    transform_triangulated_in(input, triangulated, allocator)
+    let supplement = transform_triangulated_in(input, triangulated, allocator)?;
+    Ok(FoldSupplement {
+        kirigami,
+        ..supplement
+    })
 }
 
 pub fn transform_triangulated_in<A: Allocator + Clone>(
@@ -540,34 +894,205 @@ pub fn transform_triangulated_in<A: Allocator + Clone>(
     triangulated: crate::triangulation::TriangulatedDiff<A>,
     allocator: A,
 ) -> Result<FoldSupplement<A>, TransformError> {
-    // Then, we compute the required mappings
+    validate(input)?;
+
     let vertices_count = input.vertices.count();
-    let vertices_edges = create_vertices_edges(
+    if let Some((face_index, vertex_number, points_to_vertex)) = triangulated
+        .iter_faces()
+        .flat_map(|(face, face_index)| {
+            face.into_iter()
+                .enumerate()
+                .map(move |(vertex_number, vertex_index)| (face_index, vertex_number, vertex_index))
+        })
+        .find(|(_, _, vertex_index)| *vertex_index as usize >= vertices_count)
+    {
+        return Err(TransformError::FaceVertexOutOfRange {
+            face_index,
+            vertex_number,
+            points_to_vertex,
+            vertex_count: vertices_count,
+        });
+    }
+
+    // Then, we compute the required mappings
+    let edges_vertices = || {
         core::iter::chain(
             input
                 .edges
                 .vertices
-                .as_ref()
-                .unwrap()
+                .as_deref()
+                .unwrap_or_default()
                 .iter()
                 .map(|wrapped| wrapped.0),
             triangulated.additional_edges.iter().copied(),
-        ),
-        vertices_count,
-        allocator.clone(),
-    );
+        )
+    };
+    if let Some((edge_index, edge_member, points_to_vertex)) = edges_vertices()
+        .enumerate()
+        .flat_map(|(edge_index, edge)| {
+            edge.into_iter()
+                .enumerate()
+                .map(move |(edge_member, vertex_index)| (edge_index, edge_member, vertex_index))
+        })
+        .find(|(_, _, vertex_index)| *vertex_index as usize >= vertices_count)
+    {
+        return Err(TransformError::EdgeVertexOutOfRange {
+            edge_index: edge_index as EdgeIndex,
+            edge_member,
+            points_to_vertex,
+            vertex_count: vertices_count,
+        });
+    }
+
+    let vertices_edges = create_vertices_edges(edges_vertices(), vertices_count, allocator.clone());
     let vertices_faces = create_vertices_faces(
         triangulated.face_indices.iter(),
         vertices_count,
         allocator.clone(),
     );
-    let edges_faces =
-        create_edges_faces(input, allocator.clone()).map_err(|_| TransformError::IncorrectInput)?;
+    let edges_faces = create_edges_faces(edges_vertices(), &triangulated, allocator.clone())?;
 
     Ok(FoldSupplement {
         triangulated,
         vertices_edges,
         vertices_faces,
         edges_faces,
+        kirigami: None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+    use super::TransformError;
+    use crate::input::{ImportInput, Proxy};
+    use alloc::vec::Vec;
+
+    fn frame(json: &str) -> fold::FrameCore {
+        serde_json::from_str(json).expect("invalid test frame")
+    }
+
+    #[test]
+    fn test_malformed_frames() {
+        let transform = |json| super::transform_in(&frame(json), alloc::alloc::Global).err();
+
+        assert_eq!(
+            transform(
+                r#"{
+                    "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+                    "edges_vertices": [[0, 1], [1, 2], [2, 0]],
+                    "edges_assignment": ["B", "B", "B"],
+                    "faces_vertices": [[0, 1, 7]]
+                }"#
+            ),
+            Some(TransformError::FaceVertexOutOfRange {
+                face_index: 0,
+                vertex_number: 2,
+                points_to_vertex: 7,
+                vertex_count: 3
+            })
+        );
+        assert_eq!(
+            transform(
+                r#"{
+                    "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+                    "edges_vertices": [[0, 1], [1, 9], [2, 0]],
+                    "edges_assignment": ["B", "B", "B"],
+                    "faces_vertices": [[0, 1, 2]]
+                }"#
+            ),
+            Some(TransformError::EdgeVertexOutOfRange {
+                edge_index: 1,
+                edge_member: 1,
+                points_to_vertex: 9,
+                vertex_count: 3
+            })
+        );
+        assert_eq!(
+            transform(
+                r#"{
+                    "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+                    "edges_vertices": [[0, 1], [1, 2], [2, 0]],
+                    "edges_assignment": ["B", "B"],
+                    "faces_vertices": [[0, 1, 2]]
+                }"#
+            ),
+            Some(TransformError::MismatchedFieldLength {
+                field: fold::Field::EdgesAssignment,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            transform(
+                r#"{
+                    "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+                    "edges_vertices": [[0, 1], [1, 2]],
+                    "edges_assignment": ["B", "B"],
+                    "faces_vertices": [[0, 1, 2]]
+                }"#
+            ),
+            Some(TransformError::FaceSideIsNotAnEdge {
+                face_index: 0,
+                vertices: [2, 0]
+            })
+        );
+        assert_eq!(
+            transform(
+                r#"{
+                    "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+                    "edges_vertices": [[0, 1], [1, 2], [2, 0], [1, 0]],
+                    "edges_assignment": ["B", "B", "B", "B"],
+                    "faces_vertices": [[0, 1, 2]]
+                }"#
+            ),
+            Some(TransformError::DuplicateEdge {
+                edge_index: 3,
+                duplicate_of: 0
+            })
+        );
+        assert_eq!(
+            transform(
+                r#"{
+                    "vertices_coords": [[0, 0], [1, 0], [0, 1]],
+                    "edges_vertices": [[0, 1], [1, 2], [2, 0]],
+                    "faces_vertices": [[0, 1, 2]]
+                }"#
+            ),
+            Some(TransformError::MissingRequiredField(
+                fold::Field::EdgesAssignment
+            ))
+        );
+    }
+
+    #[test]
+    fn test_triangulated_edges() {
+        // Each quad is split in two, the new edges being exposed after the source ones
+        let frame = frame(
+            r#"{
+                "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1], [2, 0], [2, 1]],
+                "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [1, 4], [4, 5], [5, 2]],
+                "edges_assignment": ["B", "V", "B", "B", "B", "B", "B"],
+                "faces_vertices": [[0, 1, 2, 3], [1, 4, 5, 2]]
+            }"#,
+        );
+        let supplement = super::transform_in(&frame, alloc::alloc::Global).unwrap();
+        let input = supplement.with_fold(&frame);
+
+        assert_eq!(input.edges_vertices().count(), 9);
+        assert_eq!(input.edges_assignment().count(), 9);
+        assert_eq!(
+            input.edges_assignment().get(8),
+            Some(crate::input::FoldAssignment::Facet)
+        );
+        assert_eq!(input.edges_faces().count(), 9);
+
+        // Every edge between two triangles knows both of them
+        let faces_per_edge = input
+            .edges_faces()
+            .iter()
+            .map(|faces| faces.len())
+            .collect::<Vec<_>>();
+        assert_eq!(faces_per_edge, [1, 2, 1, 1, 1, 1, 1, 2, 2]);
+    }
+}
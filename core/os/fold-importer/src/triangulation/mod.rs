@@ -1,3 +1,5 @@
+extern crate alloc;
+
 use itertools::Itertools;
 use rtori_os_model::FaceIndex;
 
@@ -5,25 +7,79 @@ type VertexIndex = u32;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Triangulate3DError {
+    /// The face has {vertex_count} vertices, while at least three are required
     ErrFaceIsNotAPolygon { vertex_count: usize },
+    /// The vertex {vertex_index} has neither two nor three coordinates
     ErrVertexIsNot3D { vertex_index: usize },
+    /// The face has no area: its vertices are all collinear, or two successive vertices are coincident
+    ErrFaceIsDegenerate,
+    /// The face's boundary intersects itself, between its sides #{sides[0]} & #{sides[1]}
+    /// (side #n going from the face's vertex #n to the next one)
+    ErrFaceIsSelfIntersecting { sides: [usize; 2] },
+}
+
+impl core::fmt::Display for Triangulate3DError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ErrFaceIsNotAPolygon { vertex_count } => write!(
+                f,
+                "the face has {vertex_count} vertices, while at least three are required"
+            ),
+            Self::ErrVertexIsNot3D { vertex_index } => write!(
+                f,
+                "the vertex {vertex_index} has neither two nor three coordinates"
+            ),
+            Self::ErrFaceIsDegenerate => write!(f, "the face has no area"),
+            Self::ErrFaceIsSelfIntersecting { sides } => write!(
+                f,
+                "the face's boundary intersects itself between its sides #{} & #{}",
+                sides[0], sides[1]
+            ),
+        }
+    }
 }
 
+impl core::error::Error for Triangulate3DError {}
+
 /// Operates on a single face at a time
 /// The two callbacks (`replace_face_partial` and `append_edge`) are called to create the new model.
 /// - `replace_face_partial` is called for every face created and/or reused (it's a replace operation)
 /// - `append_edge` is called only for new edges as it's not necessary to ever remove edges (it's an append operation)
 #[inline]
 pub fn triangulate3d<'a, Vertex, FuncRF, FuncAE>(
+    face_vertex_indices: &'a [VertexIndex],
+    vertices: &'a [Vertex],
+    replace_face_partial: FuncRF,
+    append_edge: FuncAE,
+) -> Result<(), Triangulate3DError>
+where
+    Vertex: core::ops::Deref<Target = [f32]>,
+    FuncRF: FnMut([VertexIndex; 3]),
+    FuncAE: FnMut([VertexIndex; 2]),
+{
+    triangulate3d_in(
+        face_vertex_indices,
+        vertices,
+        replace_face_partial,
+        append_edge,
+        alloc::alloc::Global,
+    )
+}
+
+/// Same as [`triangulate3d`], with the given allocator used for the scratch space needed by polygons
+/// of more than four vertices
+pub fn triangulate3d_in<'a, Vertex, FuncRF, FuncAE, A>(
     face_vertex_indices: &'a [VertexIndex],
     vertices: &'a [Vertex],
     mut replace_face_partial: FuncRF,
     mut append_edge: FuncAE,
+    allocator: A,
 ) -> Result<(), Triangulate3DError>
 where
     Vertex: core::ops::Deref<Target = [f32]>,
     FuncRF: FnMut([VertexIndex; 3]),
     FuncAE: FnMut([VertexIndex; 2]),
+    A: core::alloc::Allocator + Clone,
 {
     let vertex_count = face_vertex_indices.len();
 
@@ -37,26 +93,29 @@ where
         replace_face_partial(new_face);
     };
 
+    let mut register_edge = |numbers: [u32; 2]| {
+        append_edge([
+            face_vertex_indices[numbers[0] as usize],
+            face_vertex_indices[numbers[1] as usize],
+        ]);
+    };
+
+    let f = |idx: usize| {
+        let vertex_index = face_vertex_indices[idx] as usize;
+        // Two-dimensional vertices lie on the z=0 plane
+        match vertices[vertex_index][..] {
+            [x, y] => Ok(glam::Vec3 { x, y, z: 0.0 }),
+            [x, y, z] => Ok(glam::Vec3 { x, y, z }),
+            _ => Err(Triangulate3DError::ErrVertexIsNot3D { vertex_index }),
+        }
+    };
+
     if vertex_count < 3 {
         Err(Triangulate3DError::ErrFaceIsNotAPolygon { vertex_count })
     } else if vertex_count == 3 {
         register_face([0, 1, 2]);
         Ok(())
     } else if vertex_count == 4 {
-        let f = |idx| {
-            let vertex_index = face_vertex_indices[idx] as usize;
-            let vertices = &vertices[vertex_index];
-            if vertices.len() != 3 {
-                return Err(Triangulate3DError::ErrVertexIsNot3D { vertex_index });
-            }
-
-            Ok(glam::Vec3 {
-                x: vertices[0],
-                y: vertices[1],
-                z: vertices[2],
-            })
-        };
-
         let vertices = [f(0)?, f(1)?, f(2)?, f(3)?];
 
         let d0 = (vertices[0] - vertices[2]).length_squared();
@@ -67,20 +126,179 @@ where
         } else {
             ([[0, 1, 2], [0, 2, 3]], [0, 2])
         };
-        append_edge(new_edge);
+        register_edge(new_edge);
         register_face(faces[0]);
         register_face(faces[1]);
         Ok(())
     } else {
-        // As we're in 3D we need to take a careful approach for polygons over quads, as we need to create points
-        // - either like in OS, we use earcut with a lot of management around
-        // - same but with delaunator / other 2D CDT
-        // - we implemenet DeWall (https://www.sciencedirect.com/science/article/abs/pii/S0010448597000821) / (https://github.com/OpenDelaunayVoronoi/DeWall-InCoDe)
-        todo!()
+        // As we're in 3D, the polygon is first projected onto its best-fit plane, which is then
+        // triangulated using ear clipping.
+        // Just like for quads, the ear creating the shortest diagonal is clipped first.
+        let mut projected = alloc::vec::Vec::with_capacity_in(vertex_count, allocator.clone());
+        project_on_best_fit_plane(vertex_count, f, &mut projected)?;
+        check_simple(&projected)?;
+
+        let mut remaining = alloc::vec::Vec::with_capacity_in(vertex_count, allocator);
+        remaining.extend(0..(vertex_count as u32));
+
+        while remaining.len() > 3 {
+            let count = remaining.len();
+            let neighbours = |k: usize| {
+                [
+                    remaining[(k + count - 1) % count],
+                    remaining[k],
+                    remaining[(k + 1) % count],
+                ]
+            };
+
+            let ear = (0..count)
+                .filter_map(|k| {
+                    let numbers = neighbours(k);
+                    let [a, b, c] = numbers.map(|n| projected[n as usize]);
+                    if (b - a).perp_dot(c - b) <= EPSILON {
+                        // Reflex or flat
+                        return None;
+                    }
+
+                    let is_blocked = remaining
+                        .iter()
+                        .filter(|other| !numbers.contains(other))
+                        .any(|&other| is_in_triangle(projected[other as usize], [a, b, c]));
+                    if is_blocked {
+                        return None;
+                    }
+
+                    Some((k, (c - a).length_squared()))
+                })
+                .min_by(|lhs, rhs| lhs.1.total_cmp(&rhs.1));
+
+            let Some((k, _)) = ear else {
+                return Err(Triangulate3DError::ErrFaceIsDegenerate);
+            };
+
+            let [prev, current, next] = neighbours(k);
+            register_edge([prev, next]);
+            register_face([prev, current, next]);
+            remaining.remove(k);
+        }
+
+        register_face([remaining[0], remaining[1], remaining[2]]);
+        Ok(())
     }
 }
 
-extern crate alloc;
+/// Tolerance used by the geometric predicates, on coordinates normalized to the polygon's extent
+const EPSILON: f32 = 1e-6;
+
+/// Projects the polygon onto the plane given by its Newell normal, which is the best-fit plane for
+/// slightly non-planar polygons.
+/// The basis is chosen so that the projected polygon is counter-clockwise, and normalized so that
+/// its furthest vertex is at a distance of one from its centroid.
+fn project_on_best_fit_plane<F, A>(
+    vertex_count: usize,
+    f: F,
+    output: &mut alloc::vec::Vec<glam::Vec2, A>,
+) -> Result<(), Triangulate3DError>
+where
+    F: Fn(usize) -> Result<glam::Vec3, Triangulate3DError>,
+    A: core::alloc::Allocator,
+{
+    let mut centroid = glam::Vec3::ZERO;
+    for idx in 0..vertex_count {
+        centroid += f(idx)?;
+    }
+    centroid /= vertex_count as f32;
+
+    let mut normal = glam::Vec3::ZERO;
+    let mut extent_squared = 0.0f32;
+    for idx in 0..vertex_count {
+        let current = f(idx)? - centroid;
+        let next = f((idx + 1) % vertex_count)? - centroid;
+        normal += current.cross(next);
+        extent_squared = extent_squared.max(current.length_squared());
+    }
+
+    if extent_squared == 0.0 || normal.length() <= EPSILON * extent_squared {
+        return Err(Triangulate3DError::ErrFaceIsDegenerate);
+    }
+
+    let normal = normal.normalize();
+    let u = normal.any_orthonormal_vector();
+    let v = normal.cross(u);
+    let scale = extent_squared.sqrt().recip();
+
+    for idx in 0..vertex_count {
+        let relative = f(idx)? - centroid;
+        output.push(glam::Vec2::new(relative.dot(u), relative.dot(v)) * scale);
+    }
+
+    Ok(())
+}
+
+/// Checks that the projected polygon has no coincident successive vertices and that its sides do not
+/// intersect each other
+fn check_simple(polygon: &[glam::Vec2]) -> Result<(), Triangulate3DError> {
+    let count = polygon.len();
+    let side = |n: usize| (polygon[n], polygon[(n + 1) % count]);
+
+    if (0..count).any(|n| {
+        let (start, end) = side(n);
+        start.distance_squared(end) <= EPSILON * EPSILON
+    }) {
+        return Err(Triangulate3DError::ErrFaceIsDegenerate);
+    }
+
+    for first in 0..count {
+        for second in (first + 2)..count {
+            if first == 0 && second == count - 1 {
+                // Adjacent through the closing side
+                continue;
+            }
+
+            if do_sides_intersect(side(first), side(second)) {
+                return Err(Triangulate3DError::ErrFaceIsSelfIntersecting {
+                    sides: [first, second],
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn orientation(a: glam::Vec2, b: glam::Vec2, c: glam::Vec2) -> f32 {
+    (b - a).perp_dot(c - a)
+}
+
+/// Inclusive: a point on the boundary of the triangle is considered inside
+fn is_in_triangle(point: glam::Vec2, [a, b, c]: [glam::Vec2; 3]) -> bool {
+    orientation(a, b, point) >= -EPSILON
+        && orientation(b, c, point) >= -EPSILON
+        && orientation(c, a, point) >= -EPSILON
+}
+
+/// Inclusive: sides touching each other are considered intersecting
+fn do_sides_intersect(
+    (p1, p2): (glam::Vec2, glam::Vec2),
+    (q1, q2): (glam::Vec2, glam::Vec2),
+) -> bool {
+    let is_on_side = |(start, end): (glam::Vec2, glam::Vec2), point: glam::Vec2| {
+        orientation(start, end, point).abs() <= EPSILON
+            && point.cmpge(start.min(end) - EPSILON).all()
+            && point.cmple(start.max(end) + EPSILON).all()
+    };
+
+    let straddles = |(start, end): (glam::Vec2, glam::Vec2), (a, b): (glam::Vec2, glam::Vec2)| {
+        let (oa, ob) = (orientation(start, end, a), orientation(start, end, b));
+        (oa > EPSILON && ob < -EPSILON) || (oa < -EPSILON && ob > EPSILON)
+    };
+
+    (straddles((p1, p2), (q1, q2)) && straddles((q1, q2), (p1, p2)))
+        || is_on_side((p1, p2), q1)
+        || is_on_side((p1, p2), q2)
+        || is_on_side((q1, q2), p1)
+        || is_on_side((q1, q2), p2)
+}
 
 #[derive(Debug, Clone)]
 pub struct TriangulatedDiff<A>
@@ -118,7 +336,7 @@ where
 {
     let mut face_indices = alloc::vec::Vec::new_in(allocator.clone());
     let mut face_replacing = alloc::vec::Vec::new_in(allocator.clone());
-    let mut additional_edges = alloc::vec::Vec::new_in(allocator);
+    let mut additional_edges = alloc::vec::Vec::new_in(allocator.clone());
 
     face_vertex_indices
         .as_ref()
@@ -132,7 +350,13 @@ where
 
             let append_edge = |edge: [VertexIndex; 2]| additional_edges.push(edge);
 
-            crate::triangulation::triangulate3d(&face, vertices, replace_face, append_edge)
+            crate::triangulation::triangulate3d_in(
+                face,
+                vertices,
+                replace_face,
+                append_edge,
+                allocator.clone(),
+            )
         })?;
 
     Ok(TriangulatedDiff {
@@ -211,4 +435,141 @@ mod tests {
         assert_eq!(replaced.len(), 2);
         assert_matches!(appended, Some([0, 2]) | Some([1, 3]));
     }
+
+    #[test]
+    fn test_triangulate_2d() {
+        let indices = [0, 1, 2, 3];
+        let vertices = [
+            [0.0, 0.0].as_slice(),
+            [1.0, 0.0].as_slice(),
+            [1.0, 1.0].as_slice(),
+            [0.0, 1.0].as_slice(),
+        ];
+
+        let (faces, edges) = triangulate_collecting(&indices, &vertices).unwrap();
+        assert_eq!(faces.len(), 2);
+        assert_eq!(edges.len(), 1);
+
+        // Triangles are passed through without looking at their vertices
+        let vertices = [
+            [0.0, 0.0].as_slice(),
+            [1.0].as_slice(),
+            [1.0, 1.0].as_slice(),
+            [0.0, 1.0].as_slice(),
+        ];
+        assert_eq!(
+            triangulate_collecting(&indices, &vertices),
+            Err(super::Triangulate3DError::ErrVertexIsNot3D { vertex_index: 1 })
+        );
+    }
+
+    /// The faces replacing the polygon, and the edges added between them
+    type Triangulated = (Vec<[u32; 3]>, Vec<[u32; 2]>);
+
+    fn triangulate_collecting(
+        indices: &[u32],
+        vertices: &[&[f32]],
+    ) -> Result<Triangulated, super::Triangulate3DError> {
+        let mut replaced = Vec::new();
+        let mut appended = Vec::new();
+        super::triangulate3d(
+            indices,
+            vertices,
+            |triple| replaced.push(triple),
+            |edge| appended.push(edge),
+        )?;
+        Ok((replaced, appended))
+    }
+
+    #[test]
+    fn test_triangulate_non_convex_hexagon() {
+        // An L-shape, slightly non-planar, using non-contiguous vertex indices
+        let indices = [5, 0, 1, 2, 3, 4];
+        let vertices = [
+            [2.0, 0.0, 0.01].as_slice(),
+            [2.0, 1.0, 0.0].as_slice(),
+            [1.0, 1.0, -0.01].as_slice(),
+            [1.0, 2.0, 0.0].as_slice(),
+            [0.0, 2.0, 0.01].as_slice(),
+            [0.0, 0.0, 0.0].as_slice(),
+        ];
+
+        let (faces, edges) = triangulate_collecting(&indices, &vertices).unwrap();
+        assert_eq!(faces.len(), 4);
+        assert_eq!(edges.len(), 3);
+
+        // All faces keep the orientation of the polygon, and cover it exactly
+        let total_area: f32 = faces
+            .iter()
+            .map(|face| {
+                let [a, b, c] = face.map(|v| {
+                    let v = vertices[v as usize];
+                    glam::Vec2::new(v[0], v[1])
+                });
+                let area = (b - a).perp_dot(c - a) / 2.0;
+                assert!(area > 0.0, "face {face:?} is flipped or flat");
+                area
+            })
+            .sum();
+        assert!((total_area - 3.0).abs() < 1e-4, "got {total_area}");
+
+        // None of the new edges goes outside of the polygon, through the notch
+        assert!(!edges.contains(&[0, 4]) && !edges.contains(&[4, 0]));
+    }
+
+    #[test]
+    fn test_triangulate_pentagon_with_collinear_vertex() {
+        let indices = [0, 1, 2, 3, 4];
+        let vertices = [
+            [0.0, 0.0, 0.0].as_slice(),
+            [0.5, 0.0, 0.0].as_slice(),
+            [1.0, 0.0, 0.0].as_slice(),
+            [1.0, 1.0, 0.0].as_slice(),
+            [0.0, 1.0, 0.0].as_slice(),
+        ];
+
+        let (faces, edges) = triangulate_collecting(&indices, &vertices).unwrap();
+        assert_eq!(faces.len(), 3);
+        assert_eq!(edges.len(), 2);
+        for face in &faces {
+            assert!(
+                !(face.contains(&0) && face.contains(&1) && face.contains(&2)),
+                "created a flat face {face:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_triangulate_degenerate() {
+        let indices = [0, 1, 2, 3, 4];
+        let vertices = [
+            [0.0, 0.0, 0.0].as_slice(),
+            [1.0, 1.0, 1.0].as_slice(),
+            [2.0, 2.0, 2.0].as_slice(),
+            [3.0, 3.0, 3.0].as_slice(),
+            [4.0, 4.0, 4.0].as_slice(),
+        ];
+
+        assert_eq!(
+            triangulate_collecting(&indices, &vertices),
+            Err(super::Triangulate3DError::ErrFaceIsDegenerate)
+        );
+    }
+
+    #[test]
+    fn test_triangulate_self_intersecting() {
+        let indices = [0, 1, 2, 3, 4];
+        let vertices = [
+            [0.0, 0.0, 0.0].as_slice(),
+            [2.0, 0.0, 0.0].as_slice(),
+            [2.0, 2.0, 0.0].as_slice(),
+            [3.0, 1.0, 0.0].as_slice(),
+            [0.0, 2.0, 0.0].as_slice(),
+        ];
+
+        assert_matches!(
+            triangulate_collecting(&indices, &vertices),
+            Err(super::Triangulate3DError::ErrFaceIsSelfIntersecting { .. })
+        );
+    }
 }
@@ -0,0 +1,43 @@
+#![feature(allocator_api)]
+
+extern crate alloc;
+
+mod store;
+use rtori_os_fold_importer::{import_in, supplement::transform_in};
+use store::*;
+
+/// Two unit squares sharing a valley fold, as crease patterns usually come (with quads)
+const QUADS_FOLD: &str = r#"{
+    "vertices_coords": [[0, 0, 0], [1, 0, 0], [1, 1, 0], [0, 1, 0], [2, 0, 0], [2, 1, 0]],
+    "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [1, 4], [4, 5], [5, 2]],
+    "edges_assignment": ["B", "V", "B", "B", "B", "B", "B"],
+    "edges_foldAngle": [null, 90, null, null, null, null, null],
+    "faces_vertices": [[0, 1, 2, 3], [1, 4, 5, 2]]
+}"#;
+
+#[test]
+fn test_quads() {
+    let frame =
+        serde_json::from_str::<fold::FrameCore>(QUADS_FOLD).expect("source deserialization failed");
+
+    let allocator = alloc::alloc::Global;
+    let transformed = transform_in(&frame, allocator).expect("transformation failed");
+    let transformed_input = transformed.with_fold(&frame);
+    let imported = import_in(
+        Store::with_size,
+        &transformed_input,
+        Default::default(),
+        allocator,
+    )
+    .expect("import failed");
+
+    // Each quad is split in two triangles, whose diagonal becomes a facet crease
+    let size = imported.size();
+    assert_eq!(size.nodes, 6);
+    assert_eq!(size.faces, 4);
+    assert_eq!(size.creases, 3);
+    // Every edge, diagonals included, is a beam for both of its nodes
+    assert_eq!(size.node_beams, 2 * 9);
+    assert_eq!(size.node_faces, 3 * 4);
+    assert_eq!(size.node_creases, 4 * 3);
+}
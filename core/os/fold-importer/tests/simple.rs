@@ -7,7 +7,7 @@ use rtori_os_fold_importer::{import_in, supplement::transform_in};
 use store::*;
 
 const SIMPLE_EXPECTED: &'static str = include_str!("../testdata/simple.json");
-const SIMPLE_FOLD: &'static str = include_str!("../../fold/testdata/simple.fold");
+const SIMPLE_FOLD: &'static str = include_str!("../../../fold/testdata/simple.fold");
 
 fn test_pair(expected: &str, fold: &str) {
     let parsed_input = serde_json::from_str::<fold::File>(fold)
@@ -30,6 +30,18 @@ impl rtori_os_model::LoaderDyn<'_> for Store {
         self.node_config[(offset as usize)..(offset as usize) + from.len()].copy_from_slice(from);
     }
 
+    fn copy_node_mass(&mut self, from: &[f32], offset: rtori_os_model::NodeIndex) {
+        for (config, mass) in self.node_config[(offset as usize)..].iter_mut().zip(from) {
+            config.mass = *mass;
+        }
+    }
+
+    fn copy_node_fixed(&mut self, from: &[bool], offset: rtori_os_model::NodeIndex) {
+        for (config, fixed) in self.node_config[(offset as usize)..].iter_mut().zip(from) {
+            config.fixed = (*fixed).into();
+        }
+    }
+
     fn copy_node_geometry(
         &mut self,
         from: &[rtori_os_model::NodeGeometry],
@@ -56,6 +68,24 @@ impl rtori_os_model::LoaderDyn<'_> for Store {
             .copy_from_slice(from);
     }
 
+    fn copy_crease_stiffness(&mut self, from: &[f32], offset: rtori_os_model::CreaseIndex) {
+        for (parameters, k) in self.crease_parameters[(offset as usize)..]
+            .iter_mut()
+            .zip(from)
+        {
+            parameters.k = *k;
+        }
+    }
+
+    fn copy_crease_target_fold_angle(&mut self, from: &[f32], offset: rtori_os_model::CreaseIndex) {
+        for (parameters, target_fold_angle) in self.crease_parameters[(offset as usize)..]
+            .iter_mut()
+            .zip(from)
+        {
+            parameters.target_fold_angle = *target_fold_angle;
+        }
+    }
+
     fn copy_face_indices(
         &mut self,
         from: &[rtori_os_model::Vector3U],
@@ -73,6 +103,11 @@ impl rtori_os_model::LoaderDyn<'_> for Store {
             .copy_from_slice(from);
     }
 
+    fn copy_face_stiffness(&mut self, from: &[f32], offset: rtori_os_model::FaceIndex) {
+        self.face_stiffness[(offset as usize)..(offset as usize) + from.len()]
+            .copy_from_slice(from);
+    }
+
     fn copy_node_crease(
         &mut self,
         from: &[rtori_os_model::NodeCreaseSpec],
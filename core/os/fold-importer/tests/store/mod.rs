@@ -14,6 +14,7 @@ pub struct Store {
     pub crease_geometry: Vec<model::CreaseGeometry>,
     pub face_indices: Vec<model::Vector3U>,
     pub face_nominal_angles: Vec<model::Vector3F>,
+    pub face_stiffness: Vec<f32>,
     pub node_creases: Vec<model::NodeCreaseSpec>,
     pub node_beams: Vec<model::NodeBeamSpec>,
     pub node_faces: Vec<model::NodeFaceSpec>,
@@ -46,7 +47,11 @@ impl Store {
     pub fn size(&self) -> model::ModelSize {
         assert_same_size!(self.node_positions, self.node_config, self.node_geometry);
         assert_same_size!(self.crease_parameters, self.crease_geometry);
-        assert_same_size!(self.face_indices, self.face_nominal_angles);
+        assert_same_size!(
+            self.face_indices,
+            self.face_nominal_angles,
+            self.face_stiffness
+        );
 
         model::ModelSize {
             nodes: u32::try_from(self.node_positions.len()).unwrap(),
@@ -73,6 +78,7 @@ impl Store {
             crease_geometry: create(size.creases as usize),
             face_indices: create(size.faces as usize),
             face_nominal_angles: create(size.faces as usize),
+            face_stiffness: create(size.faces as usize),
             node_creases: create(size.node_creases as usize),
             node_beams: create(size.node_beams as usize),
             node_faces: create(size.node_faces as usize),
@@ -0,0 +1,94 @@
+//! Accesses backed by plain slices, for the solvers storing their state as arrays of the model's types
+
+pub struct SliceWriteAccess<'a, T>(pub &'a mut [T]);
+
+impl<'a, T> crate::WriteAccess<'a, T> for SliceWriteAccess<'a, T>
+where
+    T: Copy + 'a,
+{
+    fn capacity(&self) -> usize {
+        self.0.len()
+    }
+
+    fn copy_in(&mut self, from: &[T], offset: u32) {
+        let offset = usize::try_from(offset).unwrap();
+        self.0[offset..(offset + from.len())].copy_from_slice(from);
+    }
+
+    type Mapped<'b>
+        = &'b mut [T]
+    where
+        Self: 'b,
+        'b: 'a;
+
+    fn try_map<'call>(&'call mut self) -> Option<Self::Mapped<'call>>
+    where
+        'call: 'a,
+    {
+        Some(&mut *self.0)
+    }
+}
+
+/// Writes a single field of each element, leaving the others untouched.
+/// As the field isn't contiguous, it can't be mapped.
+pub struct FieldWriteAccess<'a, S, T> {
+    pub slice: &'a mut [S],
+    pub setter: fn(&mut S, T),
+}
+
+impl<'a, S, T> crate::WriteAccess<'a, T> for FieldWriteAccess<'a, S, T>
+where
+    T: Copy + 'a,
+{
+    fn capacity(&self) -> usize {
+        self.slice.len()
+    }
+
+    fn copy_in(&mut self, from: &[T], offset: u32) {
+        let offset = usize::try_from(offset).unwrap();
+        for (element, value) in self.slice[offset..(offset + from.len())]
+            .iter_mut()
+            .zip(from)
+        {
+            (self.setter)(element, *value);
+        }
+    }
+
+    type Mapped<'b>
+        = &'b mut [T]
+    where
+        Self: 'b,
+        'b: 'a;
+}
+
+pub struct SliceReadAccess<'a, T>(pub &'a [T]);
+
+impl<'a, T> crate::ReadAccess<'a, T> for SliceReadAccess<'a, T>
+where
+    T: Copy + 'a,
+{
+    fn get(&self, index: u32) -> T {
+        self.0[index as usize]
+    }
+
+    fn count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn copy_out(&self, out: &mut [T], offset: usize) {
+        out.copy_from_slice(&self.0[offset..(offset + out.len())]);
+    }
+
+    type Mapped<'b>
+        = &'b [T]
+    where
+        Self: 'b,
+        'b: 'a;
+
+    fn try_map<'call>(&'call self) -> Option<Self::Mapped<'call>>
+    where
+        'call: 'a,
+    {
+        Some(self.0)
+    }
+}
@@ -15,6 +15,8 @@ pub use loader::*;
 mod extract_flags;
 pub use extract_flags::*;
 
+pub mod access;
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
 pub struct ModelSize {
@@ -55,6 +55,26 @@ pub trait Loader<'container> {
         'call: 'output,
         'container: 'output;
 
+    /// The mass of each node, without touching whether it is fixed
+    type NodeMassAccess<'a>: WriteAccess<'a, f32> + 'a
+    where
+        Self: 'a,
+        'container: 'a;
+    fn access_node_mass<'call, 'output>(&'call mut self) -> Self::NodeMassAccess<'output>
+    where
+        'call: 'output,
+        'container: 'output;
+
+    /// Whether each node is fixed, without touching its mass
+    type NodeFixedAccess<'a>: WriteAccess<'a, bool> + 'a
+    where
+        Self: 'a,
+        'container: 'a;
+    fn access_node_fixed<'call, 'output>(&'call mut self) -> Self::NodeFixedAccess<'output>
+    where
+        'call: 'output,
+        'container: 'output;
+
     type NodeGeometryAccess<'a>: WriteAccess<'a, NodeGeometry> + 'a
     where
         Self: 'a,
@@ -86,6 +106,30 @@ pub trait Loader<'container> {
         'call: 'output,
         'container: 'output;
 
+    /// The stiffness (`k`) of each crease, without touching its other parameters
+    type CreaseStiffnessAccess<'a>: WriteAccess<'a, f32> + 'a
+    where
+        Self: 'a,
+        'container: 'a;
+    fn access_crease_stiffness<'call, 'output>(
+        &'call mut self,
+    ) -> Self::CreaseStiffnessAccess<'output>
+    where
+        'call: 'output,
+        'container: 'output;
+
+    /// The target fold angle of each crease, without touching its other parameters
+    type CreaseTargetFoldAngleAccess<'a>: WriteAccess<'a, f32> + 'a
+    where
+        Self: 'a,
+        'container: 'a;
+    fn access_crease_target_fold_angle<'call, 'output>(
+        &'call mut self,
+    ) -> Self::CreaseTargetFoldAngleAccess<'output>
+    where
+        'call: 'output,
+        'container: 'output;
+
     type FaceIndicesAccess<'a>: WriteAccess<'a, Vector3U> + 'a
     where
         Self: 'a,
@@ -106,6 +150,16 @@ pub trait Loader<'container> {
         'call: 'output,
         'container: 'output;
 
+    /// The stiffness of each face, scaled by the solver-wide face stiffness
+    type FaceStiffnessAccess<'a>: WriteAccess<'a, f32> + 'a
+    where
+        Self: 'a,
+        'container: 'a;
+    fn access_face_stiffness<'call, 'output>(&'call mut self) -> Self::FaceStiffnessAccess<'output>
+    where
+        'call: 'output,
+        'container: 'output;
+
     type NodeCreaseAccess<'a>: WriteAccess<'a, NodeCreaseSpec> + 'a
     where
         Self: 'a,
@@ -139,11 +193,16 @@ pub trait LoaderDyn<'container> {
     fn copy_node_position(&mut self, from: &[Vector3F], offset: NodeIndex);
     fn copy_node_external_forces(&mut self, from: &[Vector3F], offset: NodeIndex);
     fn copy_node_config(&mut self, from: &[NodeConfig], offset: NodeIndex);
+    fn copy_node_mass(&mut self, from: &[f32], offset: NodeIndex);
+    fn copy_node_fixed(&mut self, from: &[bool], offset: NodeIndex);
     fn copy_node_geometry(&mut self, from: &[NodeGeometry], offset: NodeIndex);
     fn copy_crease_geometry(&mut self, from: &[CreaseGeometry], offset: CreaseIndex);
     fn copy_crease_parameters(&mut self, from: &[CreaseParameters], offset: CreaseIndex);
+    fn copy_crease_stiffness(&mut self, from: &[f32], offset: CreaseIndex);
+    fn copy_crease_target_fold_angle(&mut self, from: &[f32], offset: CreaseIndex);
     fn copy_face_indices(&mut self, from: &[Vector3U], offset: FaceIndex);
     fn copy_face_nominal_angles(&mut self, from: &[Vector3F], offset: FaceIndex);
+    fn copy_face_stiffness(&mut self, from: &[f32], offset: FaceIndex);
     fn copy_node_crease(&mut self, from: &[NodeCreaseSpec], offset: NodeCreaseIndex);
     fn copy_node_beam(&mut self, from: &[NodeBeamSpec], offset: NodeBeamIndex);
     fn copy_node_face(&mut self, from: &[NodeFaceSpec], offset: NodeFaceIndex);
@@ -171,6 +230,14 @@ where
         Loader::access_node_config(self).copy_in(from, offset)
     }
 
+    fn copy_node_mass(&mut self, from: &[f32], offset: NodeIndex) {
+        Loader::access_node_mass(self).copy_in(from, offset)
+    }
+
+    fn copy_node_fixed(&mut self, from: &[bool], offset: NodeIndex) {
+        Loader::access_node_fixed(self).copy_in(from, offset)
+    }
+
     fn copy_node_geometry(&mut self, from: &[NodeGeometry], offset: NodeIndex) {
         Loader::access_node_geometry(self).copy_in(from, offset)
     }
@@ -183,6 +250,14 @@ where
         Loader::access_crease_parameters(self).copy_in(from, offset)
     }
 
+    fn copy_crease_stiffness(&mut self, from: &[f32], offset: CreaseIndex) {
+        Loader::access_crease_stiffness(self).copy_in(from, offset)
+    }
+
+    fn copy_crease_target_fold_angle(&mut self, from: &[f32], offset: CreaseIndex) {
+        Loader::access_crease_target_fold_angle(self).copy_in(from, offset)
+    }
+
     fn copy_face_indices(&mut self, from: &[Vector3U], offset: FaceIndex) {
         Loader::access_face_indices(self).copy_in(from, offset)
     }
@@ -191,6 +266,10 @@ where
         Loader::access_face_nominal_angles(self).copy_in(from, offset)
     }
 
+    fn copy_face_stiffness(&mut self, from: &[f32], offset: FaceIndex) {
+        Loader::access_face_stiffness(self).copy_in(from, offset)
+    }
+
     fn copy_node_crease(&mut self, from: &[NodeCreaseSpec], offset: NodeCreaseIndex) {
         Loader::access_node_crease(self).copy_in(from, offset)
     }
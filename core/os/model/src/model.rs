@@ -149,3 +149,32 @@ pub struct NodeFaceSpec {
     pub node_index: NodeIndex,
     pub face_index: FaceIndex,
 }
+
+/// Attaches a node to a target position, for instance while it is dragged around.
+/// The target is given in the same space as the nominal positions of the nodes.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeConstraint {
+    /// The node is pulled towards the target by a critically damped spring of the given stiffness.
+    /// The timestep is computed from the beams only, so the stiffness is clamped to the largest one that step
+    /// can integrate, `mass * (0.9 / (2 * PI * dt))^2`: stiffer springs behave as that one instead of diverging
+    Spring { target: Vector3F, stiffness: f32 },
+    /// The node is moved onto the target at every step: it behaves as a fixed node that follows the target
+    Pinned { target: Vector3F },
+}
+
+impl NodeConstraint {
+    pub const fn target(&self) -> Vector3F {
+        match self {
+            Self::Spring { target, .. } | Self::Pinned { target } => *target,
+        }
+    }
+
+    /// The stiffness of the spring, infinite for a pinned node
+    pub const fn stiffness(&self) -> f32 {
+        match self {
+            Self::Spring { stiffness, .. } => *stiffness,
+            Self::Pinned { .. } => f32::INFINITY,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use rtori_os_model::access::SliceReadAccess;
+
+pub struct Extractor<'backer> {
+    inner: &'backer crate::model::State,
+}
+
+impl<'backer> Extractor<'backer> {
+    pub const fn new(inner: &'backer crate::model::State) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'extractor, 'backer> rtori_os_model::Extractor<'extractor> for Extractor<'backer>
+where
+    'backer: 'extractor,
+{
+    fn count_nodes(&self) -> usize {
+        self.inner.node_position_offset.len()
+    }
+
+    type NodePositionAccess<'a>
+        = SliceReadAccess<'a, rtori_os_model::Vector3F>
+    where
+        Self: 'a,
+        'extractor: 'a;
+
+    /// As with the SIMD solver, these are the offsets from the nominal positions
+    fn access_node_position<'call, 'output>(
+        &'call self,
+    ) -> Option<Self::NodePositionAccess<'output>>
+    where
+        'call: 'output,
+        'extractor: 'output,
+    {
+        Some(SliceReadAccess(&self.inner.node_position_offset))
+    }
+
+    type NodeVelocityAccess<'a>
+        = SliceReadAccess<'a, rtori_os_model::Vector3F>
+    where
+        Self: 'a,
+        'extractor: 'a;
+
+    fn access_node_velocity<'call, 'output>(
+        &'call self,
+    ) -> Option<Self::NodeVelocityAccess<'output>>
+    where
+        'call: 'output,
+        'extractor: 'output,
+    {
+        Some(SliceReadAccess(&self.inner.node_velocity))
+    }
+
+    type NodeErrorAccess<'a>
+        = SliceReadAccess<'a, f32>
+    where
+        Self: 'a,
+        'extractor: 'a;
+
+    fn access_node_error<'call, 'output>(&'call self) -> Option<Self::NodeErrorAccess<'output>>
+    where
+        'call: 'output,
+        'extractor: 'output,
+    {
+        Some(SliceReadAccess(&self.inner.node_error))
+    }
+}
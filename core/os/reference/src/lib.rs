@@ -0,0 +1,21 @@
+//! A scalar implementation of the Origami Simulator step
+//!
+//! It follows the kernels of `rtori-os-simd` one element at a time, without any vectorization,
+//! so as to serve as a reference against which these kernels can be checked (and debugged).
+//! It is not meant to be fast.
+#![no_std]
+
+extern crate alloc;
+
+mod model;
+mod process;
+mod vector;
+
+mod runner;
+pub use runner::*;
+
+mod extractor;
+pub use extractor::Extractor;
+
+mod loader;
+pub use loader::Loader;
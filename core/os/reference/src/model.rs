@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+
+use rtori_os_model::{
+    CreaseGeometry, CreaseParameters, ModelSize, NodeBeamSpec, NodeConfig, NodeConstraint,
+    NodeCreaseSpec, NodeFaceSpec, NodeGeometry, Vector3F, Vector3U,
+};
+
+use crate::vector::Vec3;
+
+/// The whole state of the simulation, stored as plain arrays of the model's types
+#[derive(Debug, Clone)]
+pub struct State {
+    /* Per-Node: Geometry & Configs */
+    pub node_geometry: Vec<NodeGeometry>,
+    pub node_positions_unchanging: Vec<Vector3F>,
+    pub node_external_forces: Vec<Vector3F>,
+    pub node_config: Vec<NodeConfig>,
+    pub node_constraint: Vec<Option<NodeConstraint>>,
+
+    /* Per-Node: Results (the current ones, replaced at each step) */
+    pub node_position_offset: Vec<Vector3F>,
+    pub node_velocity: Vec<Vector3F>,
+    pub node_error: Vec<f32>,
+
+    /* Per-Crease */
+    pub crease_geometry: Vec<CreaseGeometry>,
+    pub crease_parameters: Vec<CreaseParameters>,
+    pub crease_fold_angle: Vec<f32>,
+
+    /* Per-Face */
+    pub face_indices: Vec<Vector3U>,
+    pub face_nominal_angles: Vec<Vector3F>,
+    pub face_k: Vec<f32>,
+
+    /* Per-Node-Crease, Per-Node-Beam & Per-Node-Face */
+    pub node_crease: Vec<NodeCreaseSpec>,
+    pub node_beam: Vec<NodeBeamSpec>,
+    pub node_face: Vec<NodeFaceSpec>,
+
+    /* Parameters, with the same defaults as the SIMD solver */
+    pub crease_percentage: f32,
+    pub dt: f32,
+    pub face_stiffness: f32,
+    pub calculate_face_strain: bool,
+    /// An acceleration applied to every node, scaled by its mass
+    pub gravity: Vec3,
+}
+
+fn filled<T: Clone + Default>(count: u32) -> Vec<T> {
+    let mut output = Vec::new();
+    output.resize(usize::try_from(count).unwrap(), T::default());
+    output
+}
+
+impl State {
+    pub fn with_size(size: &ModelSize) -> Self {
+        Self {
+            node_geometry: filled(size.nodes),
+            node_positions_unchanging: filled(size.nodes),
+            node_external_forces: filled(size.nodes),
+            node_config: filled(size.nodes),
+            node_constraint: filled(size.nodes),
+            node_position_offset: filled(size.nodes),
+            node_velocity: filled(size.nodes),
+            node_error: filled(size.nodes),
+            crease_geometry: filled(size.creases),
+            crease_parameters: filled(size.creases),
+            crease_fold_angle: filled(size.creases),
+            face_indices: filled(size.faces),
+            face_nominal_angles: filled(size.faces),
+            face_k: filled(size.faces),
+            node_crease: filled(size.node_creases),
+            node_beam: filled(size.node_beams),
+            node_face: filled(size.node_faces),
+            crease_percentage: 0.66,
+            dt: 0.001,
+            face_stiffness: 1.0,
+            calculate_face_strain: false,
+            gravity: crate::vector::ZERO,
+        }
+    }
+
+    pub fn size(&self) -> ModelSize {
+        let count = |len: usize| u32::try_from(len).unwrap();
+        ModelSize {
+            nodes: count(self.node_geometry.len()),
+            creases: count(self.crease_geometry.len()),
+            faces: count(self.face_indices.len()),
+            node_creases: count(self.node_crease.len()),
+            node_beams: count(self.node_beam.len()),
+            node_faces: count(self.node_face.len()),
+        }
+    }
+
+    /// The current position of the given node
+    #[inline]
+    pub fn position(&self, node_index: u32) -> Vec3 {
+        let node_index = node_index as usize;
+        crate::vector::add(
+            self.node_positions_unchanging[node_index].0,
+            self.node_position_offset[node_index].0,
+        )
+    }
+}
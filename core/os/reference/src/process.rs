@@ -0,0 +1,503 @@
+//! The step, split in the same stages as the SIMD kernels (see `rtori-os-simd`'s `kernels` module)
+//! and computed in the same order, so that each intermediate value can be compared
+
+use alloc::vec::Vec;
+
+use rtori_os_model::{NodeConstraint, Vector3F};
+
+use crate::model::State;
+use crate::vector::{self, Vec3};
+
+/// The results of a step, which replace the current values of the state
+pub struct StepOutput {
+    pub node_position_offset: Vec<Vector3F>,
+    pub node_velocity: Vec<Vector3F>,
+    pub node_error: Vec<f32>,
+    pub crease_fold_angle: Vec<f32>,
+}
+
+/// Per-crease values needed to distribute the crease's torque to its nodes
+#[derive(Debug, Clone, Copy)]
+struct CreasePhysics {
+    a_height: f32,
+    a_coef: f32,
+    b_height: f32,
+    b_coef: f32,
+}
+
+impl CreasePhysics {
+    const INVALID: Self = Self {
+        a_height: -1.0,
+        a_coef: -1.0,
+        b_height: -1.0,
+        b_coef: -1.0,
+    };
+
+    fn is_invalid(&self) -> bool {
+        // Only checking a_height as it should be less than zero if disabled
+        self.a_height <= 0.0
+    }
+}
+
+/// `crease_fold_angle_known` tells whether the state holds the fold angles of a previous step, which isn't the case before the first one
+pub fn process(state: &State, crease_fold_angle_known: bool) -> StepOutput {
+    let face_normals = per_face_normals(state);
+    let crease_fold_angle = per_crease_fold_angles(state, &face_normals);
+    let crease_physics = per_crease_physics(state);
+    // Without a previous step, the creases are considered still rather than moving from a fold angle of zero
+    let previous_fold_angle = if crease_fold_angle_known {
+        &state.crease_fold_angle
+    } else {
+        &crease_fold_angle
+    };
+    let node_crease_forces = per_node_crease_forces(
+        state,
+        &face_normals,
+        &crease_fold_angle,
+        previous_fold_angle,
+        &crease_physics,
+    );
+    let (node_beam_forces, node_beam_error) = per_node_beam_forces(state);
+    let (node_face_forces, node_face_error) = per_node_face_forces(state, &face_normals);
+
+    let mut output = StepOutput {
+        node_position_offset: Vec::with_capacity(state.node_geometry.len()),
+        node_velocity: Vec::with_capacity(state.node_geometry.len()),
+        node_error: Vec::with_capacity(state.node_geometry.len()),
+        crease_fold_angle,
+    };
+
+    for node_index in 0..state.node_geometry.len() {
+        let (position_offset, velocity, error) = per_node(
+            state,
+            node_index,
+            &node_crease_forces,
+            &node_beam_forces,
+            &node_face_forces,
+            &node_beam_error,
+            &node_face_error,
+        );
+        output.node_position_offset.push(Vector3F(position_offset));
+        output.node_velocity.push(Vector3F(velocity));
+        output.node_error.push(error);
+    }
+
+    output
+}
+
+/// a: the normal of each face
+fn per_face_normals(state: &State) -> Vec<Vec3> {
+    state
+        .face_indices
+        .iter()
+        .map(|indices| {
+            let [a, b, c] = indices.0.map(|index| state.position(index));
+            let cross = vector::cross(vector::sub(b, a), vector::sub(c, a));
+
+            let norm = vector::norm(cross);
+            let norm = if norm == 0.0 { 1.0 } else { norm };
+
+            vector::unscale(cross, norm)
+        })
+        .collect()
+}
+
+/// b(a): the fold angle of each crease, kept continuous with the one of the previous step
+fn per_crease_fold_angles(state: &State, face_normals: &[Vec3]) -> Vec<f32> {
+    state
+        .crease_geometry
+        .iter()
+        .zip(&state.crease_fold_angle)
+        .map(|(geometry, previous_fold_angle)| {
+            let normal_a = face_normals[geometry.face_indices[0] as usize];
+            let normal_b = face_normals[geometry.face_indices[1] as usize];
+            let normals_dot = vector::dot(normal_a, normal_b).clamp(-1.0, 1.0);
+
+            let vertex_a = state.position(geometry.adjacent_node_indices[0]);
+            let vertex_b = state.position(geometry.adjacent_node_indices[1]);
+            let ab = vector::sub(vertex_b, vertex_a);
+            let crease_vector = vector::unscale(ab, vector::norm(ab));
+
+            let x = normals_dot;
+            let y = vector::dot(vector::cross(normal_a, crease_vector), normal_b);
+            let fold_angle = libm::atan2f(y, x);
+
+            let diff = fold_angle - previous_fold_angle;
+            let delta = if diff <= -5.0 {
+                diff + core::f32::consts::TAU
+            } else if diff >= 5.0 {
+                diff - core::f32::consts::TAU
+            } else {
+                diff
+            };
+
+            previous_fold_angle + delta
+        })
+        .collect()
+}
+
+/// b(b): the heights and coefficients of the two complementary nodes of each crease
+fn per_crease_physics(state: &State) -> Vec<CreasePhysics> {
+    const TOL: f32 = 0.000001;
+
+    state
+        .crease_geometry
+        .iter()
+        .map(|geometry| {
+            // See the SIMD kernel for the naming of the nodes
+            let node_fa = state.position(geometry.complementary_node_indices[0]);
+            let node_fb = state.position(geometry.complementary_node_indices[1]);
+            let node_ea = state.position(geometry.adjacent_node_indices[0]);
+            let node_eb = state.position(geometry.adjacent_node_indices[1]);
+
+            let crease_vector = vector::sub(node_eb, node_ea);
+            let crease_length = vector::norm(crease_vector);
+            if crease_length.abs() < TOL {
+                return CreasePhysics::INVALID;
+            }
+            let crease_vector_normalized = vector::unscale(crease_vector, crease_length);
+
+            let calculate_projection = |complementary_node: Vec3| {
+                let vector = vector::sub(complementary_node, node_ea);
+                let vector_mag_sq = vector::dot(vector, vector);
+                let proj_length = vector::dot(crease_vector_normalized, vector);
+                let dist = libm::sqrtf((vector_mag_sq - proj_length * proj_length).abs());
+
+                (proj_length, dist)
+            };
+
+            let (proj_a_length, dist_a) = calculate_projection(node_fa);
+            let (proj_b_length, dist_b) = calculate_projection(node_fb);
+            if dist_a <= TOL || dist_b <= TOL {
+                return CreasePhysics::INVALID;
+            }
+
+            CreasePhysics {
+                a_height: dist_a,
+                a_coef: proj_a_length / crease_length,
+                b_height: dist_b,
+                b_coef: proj_b_length / crease_length,
+            }
+        })
+        .collect()
+}
+
+/// c(a): the force of each crease on each of its nodes
+fn per_node_crease_forces(
+    state: &State,
+    face_normals: &[Vec3],
+    crease_fold_angle: &[f32],
+    previous_fold_angle: &[f32],
+    crease_physics: &[CreasePhysics],
+) -> Vec<Vec3> {
+    state
+        .node_crease
+        .iter()
+        .map(|spec| {
+            let crease_index = spec.crease_index as usize;
+            let physics = crease_physics[crease_index];
+            if physics.is_invalid() {
+                return vector::ZERO;
+            }
+
+            let current_fold_angle = crease_fold_angle[crease_index];
+            let previous_fold_angle = previous_fold_angle[crease_index];
+            let parameters = state.crease_parameters[crease_index];
+
+            let adjusted_target_fold_angle = parameters.target_fold_angle * state.crease_percentage;
+            // The damping opposes the angular velocity of the crease
+            let angular_velocity = (current_fold_angle - previous_fold_angle) / state.dt;
+            let angular_force = parameters.k * (adjusted_target_fold_angle - current_fold_angle)
+                - parameters.d * angular_velocity;
+
+            let face_indices = state.crease_geometry[crease_index].face_indices;
+            let normal_a = face_normals[face_indices[0] as usize];
+            let normal_b = face_normals[face_indices[1] as usize];
+
+            match spec.node_number {
+                // On the crease
+                node_number if node_number > 2 => {
+                    let (coef_a, coef_b) = if node_number == 3 {
+                        (1.0 - physics.a_coef, 1.0 - physics.b_coef)
+                    } else {
+                        (physics.a_coef, physics.b_coef)
+                    };
+
+                    let side = vector::add(
+                        vector::scale(normal_a, coef_a / physics.a_height),
+                        vector::scale(normal_b, coef_b / physics.b_height),
+                    );
+                    vector::scale(side, -angular_force)
+                }
+                // Complementary to the crease, on face b
+                2 => vector::scale(normal_b, angular_force / physics.b_height),
+                // Complementary to the crease, on face a
+                _ => vector::scale(normal_a, angular_force / physics.a_height),
+            }
+        })
+        .collect()
+}
+
+/// c(b): the force of each beam on each of its nodes, and its strain
+fn per_node_beam_forces(state: &State) -> (Vec<Vec3>, Vec<f32>) {
+    state
+        .node_beam
+        .iter()
+        .map(|spec| {
+            let node_index = spec.node_index as usize;
+            let neighbour_index = spec.neighbour_index as usize;
+
+            // The current distance between the neighbour and this node is the nominal one, modified by the offsets
+            let nominal_distance = vector::sub(
+                state.node_positions_unchanging[neighbour_index].0,
+                state.node_positions_unchanging[node_index].0,
+            );
+            let offset_distance = vector::sub(
+                state.node_position_offset[neighbour_index].0,
+                state.node_position_offset[node_index].0,
+            );
+            let delta_p_uncorrected = vector::add(offset_distance, nominal_distance);
+            let delta_p_length = vector::norm(delta_p_uncorrected);
+
+            let beam_length_ratio = if delta_p_length != 0.0 {
+                spec.length / delta_p_length
+            } else {
+                1.0
+            };
+            let delta_p = vector::sub(
+                delta_p_uncorrected,
+                vector::scale(delta_p_uncorrected, beam_length_ratio),
+            );
+
+            let error = if spec.length != 0.0 {
+                (delta_p_length / spec.length - 1.0).abs()
+            } else {
+                0.0
+            };
+
+            let delta_v = vector::sub(
+                state.node_velocity[neighbour_index].0,
+                state.node_velocity[node_index].0,
+            );
+
+            let force = vector::add(
+                vector::scale(delta_p, spec.k),
+                vector::scale(delta_v, spec.d),
+            );
+
+            (force, error)
+        })
+        .unzip()
+}
+
+/// c(c): the force of each face on each of its nodes, pulling its angles back to their nominal values, and its strain
+fn per_node_face_forces(state: &State, face_normals: &[Vec3]) -> (Vec<Vec3>, Vec<f32>) {
+    const TOL: f32 = 0.0000001;
+
+    state
+        .node_face
+        .iter()
+        .map(|spec| {
+            let face_index = spec.face_index as usize;
+            let [index_a, index_b, index_c] = state.face_indices[face_index].0;
+            let [a, b, c] = [index_a, index_b, index_c].map(|index| state.position(index));
+
+            let [ab, ac, bc] = [vector::sub(b, a), vector::sub(c, a), vector::sub(c, b)];
+            let [ab_length, ac_length, bc_length] = [ab, ac, bc].map(vector::norm);
+            if ab_length <= TOL || ac_length <= TOL || bc_length <= TOL {
+                return (vector::ZERO, 0.0);
+            }
+
+            let [ab, ac, bc] = [
+                vector::unscale(ab, ab_length),
+                vector::unscale(ac, ac_length),
+                vector::unscale(bc, bc_length),
+            ];
+
+            let angles = [
+                vector::dot(ab, ac),
+                -vector::dot(ab, bc),
+                vector::dot(ac, bc),
+            ]
+            .map(|dot| libm::acosf(dot.clamp(-1.0, 1.0)));
+
+            let normal = face_normals[face_index];
+            let nominal_angles = state.face_nominal_angles[face_index].0;
+
+            let angles_diff = [0, 1, 2].map(|i| nominal_angles[i] - angles[i]);
+            let stiffness = state.face_stiffness * state.face_k[face_index];
+            let angles_diff_scaled = angles_diff.map(|diff| diff * stiffness);
+
+            let [is_a, is_b, is_c] =
+                [index_a, index_b, index_c].map(|index| spec.node_index == index);
+
+            let (left, left_length) = if is_b {
+                (ab, ab_length)
+            } else {
+                (ac, ac_length)
+            };
+            let (right, right_length) = if is_a {
+                (ab, ab_length)
+            } else {
+                (bc, bc_length)
+            };
+
+            let cross_left = vector::unscale(vector::cross(normal, left), left_length);
+            let cross_right = vector::unscale(vector::cross(normal, right), right_length);
+
+            let [x, y, z] = angles_diff_scaled;
+            let mut force = vector::ZERO;
+            if is_a {
+                let mut force_a = vector::ZERO;
+                force_a = vector::sub(
+                    force_a,
+                    vector::scale(vector::sub(cross_left, cross_right), x),
+                );
+                force_a = vector::sub(force_a, vector::scale(cross_right, y));
+                force_a = vector::add(force_a, vector::scale(cross_left, z));
+                force = vector::add(force, force_a);
+            }
+            if is_b {
+                let mut force_b = vector::ZERO;
+                force_b = vector::sub(force_b, vector::scale(cross_left, x));
+                force_b = vector::add(
+                    force_b,
+                    vector::scale(vector::add(cross_left, cross_right), y),
+                );
+                force_b = vector::sub(force_b, vector::scale(cross_right, z));
+                force = vector::add(force, force_b);
+            }
+            if is_c {
+                let mut force_c = vector::ZERO;
+                force_c = vector::add(force_c, vector::scale(cross_left, x));
+                force_c = vector::sub(force_c, vector::scale(cross_right, y));
+                force_c = vector::add(
+                    force_c,
+                    vector::scale(vector::sub(cross_right, cross_left), z),
+                );
+                force = vector::add(force, force_c);
+            }
+
+            // The error is the mean relative deviation of the face's angles from their nominal values
+            let relative = |diff: f32, nominal: f32| {
+                if nominal > TOL {
+                    diff.abs() / nominal
+                } else {
+                    0.0
+                }
+            };
+            let error = (relative(angles_diff[0], nominal_angles[0])
+                + relative(angles_diff[1], nominal_angles[1])
+                + relative(angles_diff[2], nominal_angles[2]))
+                / 3.0;
+
+            (force, error)
+        })
+        .unzip()
+}
+
+/// d: the integration of the forces applied to the given node
+fn per_node(
+    state: &State,
+    node_index: usize,
+    node_crease_forces: &[Vec3],
+    node_beam_forces: &[Vec3],
+    node_face_forces: &[Vec3],
+    node_beam_error: &[f32],
+    node_face_error: &[f32],
+) -> (Vec3, Vec3, f32) {
+    let geometry = &state.node_geometry[node_index];
+    let config = &state.node_config[node_index];
+
+    let range = |offset: u32, count: u32| (offset as usize)..((offset + count) as usize);
+    let sum_forces = |forces: &[Vec3], offset: u32, count: u32| {
+        forces[range(offset, count)]
+            .iter()
+            .fold(vector::ZERO, |acc, force| vector::add(acc, *force))
+    };
+    let sum_errors =
+        |errors: &[f32], offset: u32, count: u32| errors[range(offset, count)].iter().sum::<f32>();
+
+    let crease_force = sum_forces(
+        node_crease_forces,
+        geometry.crease.offset,
+        geometry.crease.count,
+    );
+    let beam_force = sum_forces(node_beam_forces, geometry.beam.offset, geometry.beam.count);
+    let face_force = sum_forces(node_face_forces, geometry.face.offset, geometry.face.count);
+
+    let force = vector::add(
+        vector::add(
+            vector::add(state.node_external_forces[node_index].0, crease_force),
+            beam_force,
+        ),
+        face_force,
+    );
+    let force = vector::add(force, vector::scale(state.gravity, config.mass));
+
+    // Constraints: pinned nodes are placed on their target below, the others are pulled by a critically damped spring
+    let current_offset = state.node_position_offset[node_index].0;
+    let current_velocity = state.node_velocity[node_index].0;
+    let constraint = state.node_constraint[node_index];
+    let constraint_offset = constraint.map(|constraint| {
+        vector::sub(
+            constraint.target().0,
+            state.node_positions_unchanging[node_index].0,
+        )
+    });
+    let force = match (constraint, constraint_offset) {
+        (Some(NodeConstraint::Spring { stiffness, .. }), Some(constraint_offset)) => {
+            // Clamped to the stiffest spring the timestep can integrate, with the same margin as the automatic one
+            let max_frequency = 0.9 / (2.0 * core::f32::consts::PI * state.dt);
+            let stiffness = f32::min(stiffness, config.mass * max_frequency * max_frequency);
+            let damping = 2.0 * libm::sqrtf(stiffness * config.mass);
+            let constraint_force = vector::sub(
+                vector::scale(vector::sub(constraint_offset, current_offset), stiffness),
+                vector::scale(current_velocity, damping),
+            );
+            vector::add(force, constraint_force)
+        }
+        _ => force,
+    };
+
+    let velocity_diff = vector::unscale(vector::scale(force, state.dt), config.mass);
+    // A fixed node doesn't move, so its velocity stays zero rather than integrating forces nothing balances
+    let velocity = if config.fixed == 1 {
+        vector::ZERO
+    } else {
+        vector::add(current_velocity, velocity_diff)
+    };
+
+    let position_offset_diff = vector::scale(velocity, state.dt);
+    let position_offset = vector::add(current_offset, position_offset_diff);
+
+    // A pinned node moves onto its target, at the speed needed to reach it during this step
+    let (position_offset, velocity) = match (constraint, constraint_offset) {
+        (Some(NodeConstraint::Pinned { .. }), Some(constraint_offset)) => (
+            constraint_offset,
+            vector::unscale(vector::sub(constraint_offset, current_offset), state.dt),
+        ),
+        _ => (position_offset, velocity),
+    };
+
+    // The error is the average strain of the beams (and faces, if enabled) around the node
+    let (error_sum, error_count) = if state.calculate_face_strain {
+        (
+            sum_errors(node_beam_error, geometry.beam.offset, geometry.beam.count)
+                + sum_errors(node_face_error, geometry.face.offset, geometry.face.count),
+            geometry.beam.count + geometry.face.count,
+        )
+    } else {
+        (
+            sum_errors(node_beam_error, geometry.beam.offset, geometry.beam.count),
+            geometry.beam.count,
+        )
+    };
+    let error = if error_count != 0 {
+        error_sum / error_count as f32
+    } else {
+        0.0
+    };
+
+    (position_offset, velocity, error)
+}
@@ -0,0 +1,96 @@
+use crate::{extractor, loader, model, process};
+
+#[derive(Debug, Clone)]
+pub struct Runner {
+    steps: u64,
+    pub(crate) state: model::State,
+}
+
+impl Runner {
+    pub fn with_size(sizes: &rtori_os_model::ModelSize) -> Self {
+        Self {
+            steps: 0,
+            state: model::State::with_size(sizes),
+        }
+    }
+
+    /// The number of steps done
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Steps the simulation once
+    pub fn step(&mut self) {
+        let output = process::process(&self.state, self.steps > 0);
+
+        let state = &mut self.state;
+        state.node_position_offset = output.node_position_offset;
+        state.node_velocity = output.node_velocity;
+        state.node_error = output.node_error;
+        state.crease_fold_angle = output.crease_fold_angle;
+
+        self.steps += 1;
+    }
+
+    pub fn load<'a>(&'a mut self) -> loader::Loader<'a> {
+        loader::Loader::new(self)
+    }
+
+    pub fn set_fold_percentage(&mut self, fold_percentage: f32) {
+        self.state.crease_percentage = fold_percentage;
+    }
+
+    /// The timestep used for integration
+    pub fn dt(&self) -> f32 {
+        self.state.dt
+    }
+
+    pub fn set_dt(&mut self, dt: f32) {
+        self.state.dt = dt;
+    }
+
+    /// The stiffness pulling each face's angles back towards their nominal values, scaling that of each face
+    pub fn face_stiffness(&self) -> f32 {
+        self.state.face_stiffness
+    }
+
+    pub fn set_face_stiffness(&mut self, face_stiffness: f32) {
+        self.state.face_stiffness = face_stiffness;
+    }
+
+    /// Whether the node error combines the face strain with the beam strain, or only uses the latter
+    pub fn calculate_face_strain(&self) -> bool {
+        self.state.calculate_face_strain
+    }
+
+    pub fn set_calculate_face_strain(&mut self, calculate_face_strain: bool) {
+        self.state.calculate_face_strain = calculate_face_strain;
+    }
+
+    /// The acceleration applied to every node, scaled by its mass
+    pub fn gravity(&self) -> rtori_os_model::Vector3F {
+        rtori_os_model::Vector3F(self.state.gravity)
+    }
+
+    pub fn set_gravity(&mut self, gravity: rtori_os_model::Vector3F) {
+        self.state.gravity = gravity.0;
+    }
+
+    /// Attaches the node to a target position, or detaches it with `None`
+    pub fn set_node_constraint(
+        &mut self,
+        node_index: u32,
+        constraint: Option<rtori_os_model::NodeConstraint>,
+    ) {
+        self.state.node_constraint[node_index as usize] = constraint;
+    }
+
+    /// Detaches every node from its target
+    pub fn clear_node_constraints(&mut self) {
+        self.state.node_constraint.fill(None);
+    }
+
+    pub fn extract<'a>(&'a self, _flags: rtori_os_model::ExtractFlags) -> extractor::Extractor<'a> {
+        extractor::Extractor::new(&self.state)
+    }
+}
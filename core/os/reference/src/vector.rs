@@ -0,0 +1,44 @@
+//! The few vector operations needed by the step
+
+pub type Vec3 = [f32; 3];
+
+pub const ZERO: Vec3 = [0.0; 3];
+
+#[inline]
+pub fn add(lhs: Vec3, rhs: Vec3) -> Vec3 {
+    [lhs[0] + rhs[0], lhs[1] + rhs[1], lhs[2] + rhs[2]]
+}
+
+#[inline]
+pub fn sub(lhs: Vec3, rhs: Vec3) -> Vec3 {
+    [lhs[0] - rhs[0], lhs[1] - rhs[1], lhs[2] - rhs[2]]
+}
+
+#[inline]
+pub fn scale(v: Vec3, factor: f32) -> Vec3 {
+    [v[0] * factor, v[1] * factor, v[2] * factor]
+}
+
+#[inline]
+pub fn unscale(v: Vec3, divisor: f32) -> Vec3 {
+    [v[0] / divisor, v[1] / divisor, v[2] / divisor]
+}
+
+#[inline]
+pub fn dot(lhs: Vec3, rhs: Vec3) -> f32 {
+    lhs[0] * rhs[0] + lhs[1] * rhs[1] + lhs[2] * rhs[2]
+}
+
+#[inline]
+pub fn cross(lhs: Vec3, rhs: Vec3) -> Vec3 {
+    [
+        lhs[1] * rhs[2] - lhs[2] * rhs[1],
+        lhs[2] * rhs[0] - lhs[0] * rhs[2],
+        lhs[0] * rhs[1] - lhs[1] * rhs[0],
+    ]
+}
+
+#[inline]
+pub fn norm(v: Vec3) -> f32 {
+    libm::sqrtf(dot(v, v))
+}
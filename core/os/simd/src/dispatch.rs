@@ -0,0 +1,159 @@
+//! Runtime selection of the SIMD lane width
+//!
+//! The lane width of a [`Runner`] is a compile-time parameter, but the instruction sets able to process
+//! these lanes natively are only known at runtime when distributing a single binary.
+//! [`LaneWidth`] allows choosing the widest width supported by the running processor, and the `step_dispatched`
+//! methods of the matching runners (as well as the jobs of the multi-threaded step) run code generated for that instruction set.
+
+use crate::Runner;
+
+/// The number of `f32` lanes processed at once by a [`Runner`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LaneWidth {
+    /// 4 lanes (128 bits), e.g. SSE or NEON
+    X4,
+    /// 8 lanes (256 bits), e.g. AVX
+    X8,
+    /// 16 lanes (512 bits), e.g. AVX-512
+    X16,
+}
+
+// Without `std`, only the compile-time target features are known
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "std"))]
+macro_rules! has_x86_feature {
+    ($feature:tt) => {
+        std::is_x86_feature_detected!($feature)
+    };
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(feature = "std")))]
+macro_rules! has_x86_feature {
+    ($feature:tt) => {
+        cfg!(target_feature = $feature)
+    };
+}
+
+impl LaneWidth {
+    /// Every lane width, from the narrowest to the widest
+    pub const ALL: [Self; 3] = [Self::X4, Self::X8, Self::X16];
+
+    pub const fn lane_count(self) -> usize {
+        match self {
+            Self::X4 => 4,
+            Self::X8 => 8,
+            Self::X16 => 16,
+        }
+    }
+
+    pub const fn from_lane_count(lane_count: usize) -> Option<Self> {
+        match lane_count {
+            4 => Some(Self::X4),
+            8 => Some(Self::X8),
+            16 => Some(Self::X16),
+            _ => None,
+        }
+    }
+
+    /// Whether the running processor can process this lane width natively.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::X4 => true,
+            Self::X8 => has_x86_feature!("avx"),
+            Self::X16 => has_x86_feature!("avx512f"),
+        }
+    }
+
+    /// Whether the running processor can process this lane width natively.
+    ///
+    /// Outside of x86, every width is reported as supported, as the lanes are then split by the compiler
+    /// according to the compile-time target features.
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    pub fn is_supported(self) -> bool {
+        true
+    }
+
+    /// Returns the widest lane width supported by the running processor
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    pub fn detect() -> Self {
+        Self::ALL
+            .into_iter()
+            .rev()
+            .find(|width| width.is_supported())
+            .unwrap_or(Self::X4)
+    }
+
+    /// Returns the widest lane width supported by the running processor
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    pub fn detect() -> Self {
+        Self::from_lane_count(crate::MIN_LANE_COUNT_32).unwrap_or(Self::X4)
+    }
+}
+
+impl core::fmt::Display for LaneWidth {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} lanes", self.lane_count())
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx")]
+unsafe fn call_avx<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx512f")]
+unsafe fn call_avx512<R>(f: impl FnOnce() -> R) -> R {
+    f()
+}
+
+/// Calls `f` with the instruction set processing `L` lanes natively (AVX for 8 lanes, AVX-512 for 16) enabled,
+/// when the running processor supports it.
+///
+/// The code of `f` is only generated for that instruction set once inlined, so `f` should be marked `#[inline(always)]`,
+/// as should everything it calls down to the kernels (see [`Runner::step`]).
+#[inline(always)]
+pub(crate) fn with_lane_features<const L: usize, R>(f: impl FnOnce() -> R) -> R {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    match LaneWidth::from_lane_count(L) {
+        Some(LaneWidth::X8) if LaneWidth::X8.is_supported() => {
+            // SAFETY: we just checked that AVX is supported
+            return unsafe { call_avx(f) };
+        }
+        Some(LaneWidth::X16) if LaneWidth::X16.is_supported() => {
+            // SAFETY: we just checked that AVX-512F is supported
+            return unsafe { call_avx512(f) };
+        }
+        _ => {}
+    }
+
+    f()
+}
+
+impl Runner<'_, 4> {
+    /// Steps the simulation, see [`LaneWidth`]
+    pub fn step_dispatched(&mut self) -> Result<(), ()> {
+        self.step()
+    }
+}
+
+impl Runner<'_, 8> {
+    /// Steps the simulation using AVX when the running processor supports it, see [`LaneWidth`]
+    pub fn step_dispatched(&mut self) -> Result<(), ()> {
+        with_lane_features::<8, _>(
+            #[inline(always)]
+            || self.step(),
+        )
+    }
+}
+
+impl Runner<'_, 16> {
+    /// Steps the simulation using AVX-512 when the running processor supports it, see [`LaneWidth`]
+    pub fn step_dispatched(&mut self) -> Result<(), ()> {
+        with_lane_features::<16, _>(
+            #[inline(always)]
+            || self.step(),
+        )
+    }
+}
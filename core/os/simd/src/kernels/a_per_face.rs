@@ -22,7 +22,7 @@ where
 }
 
 #[tracing::instrument]
-#[inline]
+#[inline(always)]
 pub fn calculate_normals<'a, const L: usize>(
     inputs: &'a PerFaceInputs<'a, L>,
 ) -> impl ExactSizeIterator<Item = PerFaceOutput<L>> + use<'a, L>
@@ -32,10 +32,9 @@ where
         simba::simd::SimdComplexField<SimdRealField = simba::simd::Simd<core::simd::Simd<f32, L>>>,
 {
     // First, we calculate every combined position using SIMD
-    inputs
-        .face_node_indices
-        .iter()
-        .map(move |idx: &SimdVec3U<L>| {
+    inputs.face_node_indices.iter().map(
+        #[inline(always)]
+        move |idx: &SimdVec3U<L>| {
             // /*2024-10-11*/ println!("PerFaceInput: processing faces {:?}", idx);
             let f = #[inline(always)]
             |indices: SimdU32<L>| {
@@ -72,7 +71,8 @@ where
             PerFaceOutput {
                 face_normals: [result.x.0, result.y.0, result.z.0],
             }
-        })
+        },
+    )
 }
 
 // TODO: tests
@@ -30,6 +30,7 @@ where
 }
 
 #[tracing::instrument]
+#[inline(always)]
 pub fn calculate_crease_fold_angles<'a, const L: usize>(
     inputs: &'a PerCreaseFoldAngleInput<'a, L>,
 ) -> impl ExactSizeIterator<Item = SimdF32<L>> + use<'a, L>
@@ -43,7 +44,8 @@ where
         inputs.crease_neighbourhoods,
         inputs.crease_fold_angle
     )
-    .map(|(face_indices, neighbourhoods, previous_fold_angles)| {
+    .map(#[inline(always)]
+    |(face_indices, neighbourhoods, previous_fold_angles)| {
         let g = #[inline(always)]
         |face_index| {
             let face_indices_a = face_indices.0[face_index];
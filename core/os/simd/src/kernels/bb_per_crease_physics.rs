@@ -22,6 +22,7 @@ where
 
 const TOL: f32 = 0.000001;
 
+#[inline(always)]
 pub fn calculate_crease_physics<'a, const L: usize>(
     inputs: &'a PerCreasePhysicsInput<'a, L>,
 ) -> impl ExactSizeIterator<Item = CreasesPhysicsLens<L>> + use<'a, L>
@@ -34,10 +35,9 @@ where
 
     let invalid_value = SimdF32::splat(-1.0);
 
-    inputs
-        .crease_neighbourhood
-        .iter()
-        .map(move |neighbourhood| {
+    inputs.crease_neighbourhood.iter().map(
+        #[inline(always)]
+        move |neighbourhood| {
             let get_position = #[inline(always)]
             |indices: SimdU32<L>| {
                 position::get_positions_for_indices(
@@ -201,5 +201,6 @@ where
             operations::debug::check_nans_simd_msg(res.b_coef, "bb_per_crease_physics", "b_coef");
 
             res
-        })
+        },
+    )
 }
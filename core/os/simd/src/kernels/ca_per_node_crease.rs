@@ -20,9 +20,11 @@ where
 
     /* per-crease */
     pub crease_fold_angles: &'backer [SimdF32<L>],
+    /// The fold angles of the previous step, used to compute the angular velocity for damping
+    pub crease_previous_fold_angles: &'backer [SimdF32<L>],
     pub crease_physics: &'backer [CreasesPhysicsLens<L>],
     pub crease_k: &'backer [SimdF32<L>],
-    //pub crease_d: &'backer [SimdF32<L>],
+    pub crease_d: &'backer [SimdF32<L>],
     pub crease_target_fold_angle: &'backer [SimdF32<L>],
     pub crease_face_indices: &'backer [CreaseFaceIndices<L>],
 
@@ -32,10 +34,11 @@ where
 
     /* parameters */
     pub crease_percentage: f32,
+    pub dt: f32,
 }
 
 #[tracing::instrument]
-#[inline]
+#[inline(always)]
 pub fn calculate_node_crease_forces<'a, const L: usize>(
     inputs: &'a PerNodeCreaseInput<'a, L>,
 ) -> impl ExactSizeIterator<Item = SimdVec3F<L>> + use<'a, L>
@@ -44,16 +47,19 @@ where
     simba::simd::Simd<core::simd::Simd<f32, L>>: nalgebra::SimdRealField,
 {
     itertools::izip!(inputs.node_crease_indices, inputs.node_crease_node_number).map(
+        #[inline(always)]
         |(crease_indices, node_number)| {
             let [
                 crease_current_fold_angle,
+                crease_previous_fold_angle,
                 crease_k,
-                //crease_d,
+                crease_d,
                 crease_target_fold_angle
             ] = gather_f32([
                     &inputs.crease_fold_angles,
+                    &inputs.crease_previous_fold_angles,
                     &inputs.crease_k,
-                    //&inputs.crease_d,
+                    &inputs.crease_d,
                     &inputs.crease_target_fold_angle
                 ], *crease_indices);
 
@@ -71,8 +77,12 @@ where
 
             let crease_percentage_splat = SimdF32::splat(inputs.crease_percentage);
             let adjusted_target_fold_angles = crease_target_fold_angle * crease_percentage_splat;
-            let angular_force =
-                crease_k * (adjusted_target_fold_angles - crease_current_fold_angle);
+            // The damping opposes the angular velocity of the crease
+            let angular_velocity = (crease_current_fold_angle - crease_previous_fold_angle)
+                / SimdF32::splat(inputs.dt);
+            let angular_force = crease_k
+                * (adjusted_target_fold_angles - crease_current_fold_angle)
+                - crease_d * angular_velocity;
             /* 2025-01-15 */
             tracing::event!(tracing::Level::TRACE, "Crease Percentage: {crease_percentage_splat:?}\n\tTarget Fold Angle:{adjusted_target_fold_angles:?}\n\tCurrent Fold Angle: {crease_current_fold_angle:?}\n\tAngular Force: {angular_force:?}");
  // Now it's time to load the geometry
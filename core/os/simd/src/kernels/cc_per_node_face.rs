@@ -22,7 +22,9 @@ where
     pub face_node_indices: &'backer [SimdVec3U<L>],
     pub face_normals: &'backer [SimdVec3F<L>],
     pub face_nominal_angles: &'backer [SimdVec3F<L>],
+    pub face_k: &'backer [SimdF32<L>],
 
+    /// Scales the stiffness of every face
     pub face_stiffness: f32,
 }
 
@@ -38,6 +40,7 @@ where
 const TOL: f32 = 0.0000001;
 
 #[tracing::instrument]
+#[inline(always)]
 pub fn calculate_node_face_forces<'a, const L: usize>(
     inputs: &'a PerNodeFaceInput<'a, L>,
 ) -> impl ExactSizeIterator<Item = PerNodeFaceOutput<L>> + use<'a, L>
@@ -51,7 +54,7 @@ where
     let zero_force = nalgebra::Vector3::new(zero, zero, zero);
 
     inputs.node_face_spec.iter().map(
-        #[inline]
+        #[inline(always)]
         move |spec| {
             use super::operations::select;
 
@@ -188,7 +191,9 @@ where
             /* 2025-01-15 */
             tracing::event!(tracing::Level::TRACE, "cc_per_node_face: Angles Difference: {angles_diff:?} (nominal: {nominal_angles:?}, current: {angles:?})");
 
-            let angles_diff_scaled = ensure_simd!(angles_diff.scale(face_stiffness); v3);
+            let [face_k] = super::gather::gather_f32([inputs.face_k], spec.face_indices);
+            let stiffness = face_stiffness * simba::simd::Simd(face_k);
+            let angles_diff_scaled = ensure_simd!(angles_diff.scale(stiffness); v3);
             tracing::event!(tracing::Level::TRACE, "cc_per_node_face: Angles Difference (scaled): {angles_diff_scaled:?}");
 
             let [
@@ -230,10 +235,10 @@ where
                     is_b,
                     {
                         let mut force = zero_force;
-                        force -= left * angles_diff_scaled.x;
-                        force += (left + right) * angles_diff_scaled.y;
+                        force -= cross_left * angles_diff_scaled.x;
+                        force += (cross_left + cross_right) * angles_diff_scaled.y;
                         // TODO: face strain
-                        force -= right * angles_diff_scaled.z;
+                        force -= cross_right * angles_diff_scaled.z;
                         force
                     },
                     zero_force,
@@ -244,9 +249,9 @@ where
                     is_c,
                     {
                         let mut force = zero_force;
-                        force += left * angles_diff.x;
-                        force -= right * angles_diff.y;
-                        force += (right - left) * angles_diff.z;
+                        force += cross_left * angles_diff_scaled.x;
+                        force -= cross_right * angles_diff_scaled.y;
+                        force += (cross_right - cross_left) * angles_diff_scaled.z;
                         force
                     },
                     zero_force,
@@ -256,7 +261,21 @@ where
                 let force = ensure_simd!(force_a + force_b + force_c; v3);
                 force
             };
-            let error = zero;
+            // The error is the mean relative deviation of the face's angles from their nominal values
+            let error = {
+                use core::simd::{cmp::SimdPartialOrd as _, num::SimdFloat as _};
+                let nominal_angles = to_nalgebra3(nominal_angles);
+                let relative = |diff: simba::simd::Simd<SimdF32<L>>, nominal: simba::simd::Simd<SimdF32<L>>| {
+                    let valid = nominal.0.simd_gt(tol);
+                    let nominal = valid.select(nominal.0, SimdF32::splat(1.0));
+                    simba::simd::Simd(valid.select(diff.0.abs() / nominal, zero.0))
+                };
+
+                (relative(angles_diff.x, nominal_angles.x)
+                    + relative(angles_diff.y, nominal_angles.y)
+                    + relative(angles_diff.z, nominal_angles.z))
+                    / simba::simd::Simd(SimdF32::splat(3.0))
+            };
 
             let force_selected =  ensure_simd!([
                 mask.select(force.x.0, zero.0),
@@ -1,4 +1,8 @@
-use core::simd::{cmp::SimdPartialEq, LaneCount, SupportedLaneCount};
+use core::simd::{
+    cmp::{SimdPartialEq, SimdPartialOrd},
+    num::SimdFloat,
+    LaneCount, SupportedLaneCount,
+};
 
 use nalgebra::{geometry, SimdComplexField, SimdRealField};
 use simd_common::*;
@@ -20,11 +24,21 @@ where
 
     pub node_geometry: &'backer [NodeGeometry<L>],
 
+    pub node_positions_unchanging: &'backer [SimdVec3F<L>],
+    pub node_constraint_target: &'backer [SimdVec3F<L>],
+    pub node_constraint_stiffness: &'backer [SimdF32<L>],
+
     pub node_crease_force: &'backer [SimdVec3F<L>],
     pub node_beam_force: &'backer [SimdVec3F<L>],
     pub node_face_force: &'backer [SimdVec3F<L>],
 
+    pub node_beam_error: &'backer [SimdF32<L>],
+    pub node_face_error: &'backer [SimdF32<L>],
+
     pub dt: f32,
+    pub calculate_face_strain: bool,
+    /// An acceleration applied to every node, scaled by its mass
+    pub gravity: [f32; 3],
 }
 
 #[derive(Debug)]
@@ -38,6 +52,9 @@ where
     pub mass: &'backer SimdF32<L>,
     pub fixed: &'backer SimdU32<L>,
     pub geometry: &'backer NodeGeometry<L>,
+    pub positions_unchanging: &'backer SimdVec3F<L>,
+    pub constraint_target: &'backer SimdVec3F<L>,
+    pub constraint_stiffness: &'backer SimdF32<L>,
 }
 
 impl<'backer, const L: usize> IntoIterator for PerNodeInput<'backer, L>
@@ -48,6 +65,7 @@ where
 
     type IntoIter = impl ExactSizeIterator<Item = Self::Item>;
 
+    #[inline(always)]
     fn into_iter(self) -> Self::IntoIter {
         itertools::izip!(
             self.node_positions_offset,
@@ -55,13 +73,26 @@ where
             self.node_external_forces,
             self.node_mass,
             self.node_fixed,
-            self.node_geometry
+            self.node_geometry,
+            self.node_positions_unchanging,
+            self.node_constraint_target,
+            self.node_constraint_stiffness
         )
         .enumerate()
         .map(
             move |(
                 _chunk_index,
-                (positions_offset, velocity, external_forces, mass, fixed, geometry),
+                (
+                    positions_offset,
+                    velocity,
+                    external_forces,
+                    mass,
+                    fixed,
+                    geometry,
+                    positions_unchanging,
+                    constraint_target,
+                    constraint_stiffness,
+                ),
             )| {
                 PerNodeInputLens {
                     positions_offset,
@@ -70,6 +101,9 @@ where
                     mass,
                     fixed,
                     geometry,
+                    positions_unchanging,
+                    constraint_target,
+                    constraint_stiffness,
                 }
             },
         )
@@ -85,6 +119,7 @@ where
     pub error: SimdF32<L>,
 }
 
+#[inline(always)]
 fn calculate_force_subset<const L: usize>(
     range: &crate::model::NodeGeometryRange<L>,
     forces: &[SimdVec3F<L>],
@@ -129,7 +164,36 @@ where
         .sum()
 }
 
+/// Sums the errors of the given range
+#[inline(always)]
+fn calculate_error_subset<const L: usize>(
+    range: &crate::model::NodeGeometryRange<L>,
+    errors: &[SimdF32<L>],
+) -> SimdF32<L>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    use core::simd::num::SimdUint;
+    let count_max = range.count.reduce_max();
+
+    (0..count_max)
+        .map(|i| {
+            use core::simd::cmp::SimdPartialOrd;
+
+            let simd_i = core::simd::Simd::splat(i);
+
+            let valid = range.count.simd_gt(simd_i);
+            let cursor = valid.select(simd_i, core::simd::Simd::splat(0));
+
+            let [error] = super::operations::gather::gather_f32([errors], range.offset + cursor);
+
+            valid.select(error, SimdF32::splat(0.0))
+        })
+        .sum()
+}
+
 #[tracing::instrument]
+#[inline(always)]
 pub fn calculate_node_position<'a, const L: usize>(
     inputs: PerNodeInput<'a, L>,
 ) -> impl ExactSizeIterator<Item = PerNodeOutput<L>> + use<'a, L>
@@ -142,48 +206,117 @@ where
     let zero_force = nalgebra::Vector3::new(zero, zero, zero);
 
     let dt = simba::simd::Simd(SimdF32::splat(inputs.dt));
+    let max_constraint_frequency_sq = {
+        let frequency = 0.9 / (2.0 * core::f32::consts::PI * inputs.dt);
+        SimdF32::splat(frequency * frequency)
+    };
+    let gravity = inputs
+        .gravity
+        .map(|component| simba::simd::Simd(SimdF32::splat(component)));
+    let gravity = nalgebra::Vector3::new(gravity[0], gravity[1], gravity[2]);
 
     let crease_forces = inputs.node_crease_force;
     let beam_forces = inputs.node_beam_force;
-    let _face_forces = inputs.node_face_force;
-
-    inputs.into_iter().map(move |per_node| {
-        let crease_force = calculate_force_subset(&per_node.geometry.creases, &crease_forces);
-        let beam_force = calculate_force_subset(&per_node.geometry.beams, &beam_forces);
-        //let face_force = calculate_force_subset(&per_node.geometry.faces, &face_forces);
-        let face_force = simd_common::convert_nalgebra::to_nalgebra3([
-            core::simd::Simd::splat(0.0),
-            core::simd::Simd::splat(0.0),
-            core::simd::Simd::splat(0.0),
-        ]);
-        let valid_input = per_node.mass.simd_ne(SimdF32::splat(0.0));
-
-        let force = simd_common::convert_nalgebra::to_nalgebra3(*per_node.external_forces)
-            + crease_force
-            + beam_force
-            + face_force;
-        ensure_simd!(force; v3);
-
-        let velocity_diff = force.scale(dt) / simba::simd::Simd(*per_node.mass);
-        ensure_simd!(velocity_diff; v3; @mask(valid_input));
-
-        let velocity_new =
-            simd_common::convert_nalgebra::to_nalgebra3(*per_node.velocity) + velocity_diff;
-        ensure_simd!(velocity_new; v3; @mask(valid_input));
-
-        let is_fixed_mask = per_node.fixed.simd_eq(SimdU32::splat(1));
-
-        let position_offset_diff =
-            super::operations::select(is_fixed_mask, zero_force, velocity_new * dt);
-
-        let position_offset =
-            simd_common::convert_nalgebra::to_nalgebra3(*per_node.positions_offset)
-                + position_offset_diff;
-        /* 2025-01-15 */
-
-        tracing::event!(
-            tracing::Level::TRACE,
-            "
+    let face_forces = inputs.node_face_force;
+    let beam_errors = inputs.node_beam_error;
+    let face_errors = inputs.node_face_error;
+    let calculate_face_strain = inputs.calculate_face_strain;
+
+    inputs.into_iter().map(
+        #[inline(always)]
+        move |per_node| {
+            let crease_force = calculate_force_subset(&per_node.geometry.creases, &crease_forces);
+            let beam_force = calculate_force_subset(&per_node.geometry.beams, &beam_forces);
+            let face_force = calculate_force_subset(&per_node.geometry.faces, &face_forces);
+            let valid_input = per_node.mass.simd_ne(SimdF32::splat(0.0));
+
+            let positions_offset =
+                simd_common::convert_nalgebra::to_nalgebra3(*per_node.positions_offset);
+            let velocity = simd_common::convert_nalgebra::to_nalgebra3(*per_node.velocity);
+
+            // Constraints: pinned nodes are placed on their target below, the others are pulled by a critically damped spring
+            let is_pinned_mask = per_node
+                .constraint_stiffness
+                .simd_eq(SimdF32::splat(f32::INFINITY));
+            let constraint_offset =
+                simd_common::convert_nalgebra::to_nalgebra3(*per_node.constraint_target)
+                    - simd_common::convert_nalgebra::to_nalgebra3(*per_node.positions_unchanging);
+            // Clamped to the stiffest spring the timestep can integrate, with the same margin as the automatic one
+            let constraint_stiffness = simba::simd::Simd(
+                is_pinned_mask
+                    .select(zero.0, *per_node.constraint_stiffness)
+                    .simd_min(*per_node.mass * max_constraint_frequency_sq),
+            );
+            let constraint_damping = simba::simd::Simd(SimdF32::splat(2.0))
+                * (constraint_stiffness * simba::simd::Simd(*per_node.mass)).simd_sqrt();
+            // Restricted to the constrained lanes, as the velocity of the padding lanes isn't finite
+            let is_constrained_mask = constraint_stiffness.0.simd_gt(zero.0);
+            let constraint_force = super::operations::select(
+                is_constrained_mask,
+                (constraint_offset - positions_offset).scale(constraint_stiffness)
+                    - velocity.scale(constraint_damping),
+                zero_force,
+            );
+
+            let force = simd_common::convert_nalgebra::to_nalgebra3(*per_node.external_forces)
+                + crease_force
+                + beam_force
+                + face_force
+                + gravity.scale(simba::simd::Simd(*per_node.mass))
+                + constraint_force;
+            ensure_simd!(force; v3);
+
+            let velocity_diff = force.scale(dt) / simba::simd::Simd(*per_node.mass);
+            ensure_simd!(velocity_diff; v3; @mask(valid_input));
+
+            // A fixed node doesn't move, so its velocity stays zero rather than integrating forces nothing balances
+            let is_fixed_mask = per_node.fixed.simd_eq(SimdU32::splat(1));
+            let velocity_new =
+                super::operations::select(is_fixed_mask, zero_force, velocity + velocity_diff);
+            ensure_simd!(velocity_new; v3; @mask(valid_input));
+
+            let position_offset_diff = velocity_new * dt;
+
+            // A pinned node moves onto its target, at the speed needed to reach it during this step
+            let position_offset = super::operations::select(
+                is_pinned_mask,
+                constraint_offset,
+                positions_offset + position_offset_diff,
+            );
+            let velocity_new = super::operations::select(
+                is_pinned_mask,
+                (constraint_offset - positions_offset) / dt,
+                velocity_new,
+            );
+            /* 2025-01-15 */
+
+            // The error is the average strain of the beams (and faces, if enabled) around the node
+            let error = {
+                use core::simd::num::SimdUint;
+
+                let (error_sum, error_count) = if calculate_face_strain {
+                    (
+                        calculate_error_subset(&per_node.geometry.beams, &beam_errors)
+                            + calculate_error_subset(&per_node.geometry.faces, &face_errors),
+                        per_node.geometry.beams.count + per_node.geometry.faces.count,
+                    )
+                } else {
+                    (
+                        calculate_error_subset(&per_node.geometry.beams, &beam_errors),
+                        per_node.geometry.beams.count,
+                    )
+                };
+
+                let has_error = error_count.simd_ne(SimdU32::splat(0));
+                let error_count = has_error
+                    .select(error_count, SimdU32::splat(1))
+                    .cast::<f32>();
+                has_error.select(error_sum / error_count, zero.0)
+            };
+
+            tracing::event!(
+                tracing::Level::TRACE,
+                "
  mass: {:?}
  positions (diff): {:?}
  positions (new): {:?}
@@ -194,26 +327,27 @@ where
  force from face {:?}
  force (unscaled by dt): {:?}
  dt: {:?}",
-            *per_node.mass,
-            position_offset_diff,
-            position_offset,
-            velocity_diff,
-            velocity_new,
-            crease_force,
-            beam_force,
-            face_force,
-            force,
-            dt
-        );
-
-        PerNodeOutput {
-            position_offset: [
-                position_offset.x.0,
-                position_offset.y.0,
-                position_offset.z.0,
-            ],
-            velocity: [velocity_new.x.0, velocity_new.y.0, velocity_new.z.0],
-            error: zero.0,
-        }
-    })
+                *per_node.mass,
+                position_offset_diff,
+                position_offset,
+                velocity_diff,
+                velocity_new,
+                crease_force,
+                beam_force,
+                face_force,
+                force,
+                dt
+            );
+
+            PerNodeOutput {
+                position_offset: [
+                    position_offset.x.0,
+                    position_offset.y.0,
+                    position_offset.z.0,
+                ],
+                velocity: [velocity_new.x.0, velocity_new.y.0, velocity_new.z.0],
+                error,
+            }
+        },
+    )
 }
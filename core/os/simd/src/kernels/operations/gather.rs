@@ -1,7 +1,7 @@
 use core::simd::{LaneCount, SupportedLaneCount};
 use simd_common::{gather::*, SimdF32, SimdU32, SimdVec3F};
 
-#[inline]
+#[inline(always)]
 pub fn gather_vec3f<const N: usize, const L: usize>(
     slices: [&[SimdVec3F<L>]; N],
     indices: SimdU32<L>,
@@ -12,7 +12,7 @@ where
     gather_vec3_multi(slices, indices)
 }
 
-#[inline]
+#[inline(always)]
 pub fn gather_vec3f_1<const L: usize>(
     input: &[SimdVec3F<L>],
     indices: simd_common::SimdU32<L>,
@@ -23,7 +23,7 @@ where
     gather_vec3(input, indices)
 }
 
-#[inline]
+#[inline(always)]
 pub fn gather_f32<const N: usize, const L: usize>(
     slices: [&[SimdF32<L>]; N],
     indices: SimdU32<L>,
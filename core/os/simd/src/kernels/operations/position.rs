@@ -6,7 +6,7 @@ use simd_common::{convert_nalgebra::to_nalgebra3, *};
 
 use nalgebra as na;
 
-#[inline]
+#[inline(always)]
 pub fn get_positions_for_indices<const L: usize>(
     positions_unchanging: &[SimdVec3F<L>],
     positions_offsets: &[SimdVec3F<L>],
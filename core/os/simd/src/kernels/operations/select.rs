@@ -2,7 +2,7 @@ use core::simd::{LaneCount, SupportedLaneCount};
 
 use simd_common::*;
 
-#[inline]
+#[inline(always)]
 pub fn select_n<const L: usize, const N: usize>(
     mask: core::simd::Mask<i32, L>,
     true_values: [SimdF32<L>; N],
@@ -18,7 +18,7 @@ where
     output
 }
 
-#[inline]
+#[inline(always)]
 pub fn select<const L: usize>(
     mask: core::simd::Mask<i32, L>,
     true_values: nalgebra::Vector3<simba::simd::Simd<SimdF32<L>>>,
@@ -8,13 +8,21 @@
 
 extern crate static_assertions;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 mod kernels;
 mod model;
 mod process;
+#[cfg(feature = "std")]
+mod process_mt;
 
 mod runner;
 pub use runner::*;
 
+mod dispatch;
+pub use dispatch::LaneWidth;
+
 #[cfg(feature = "alloc")]
 pub mod owned;
 
@@ -69,6 +69,14 @@ fn set_vec3f<const L: usize>(
     set_vec(slice, index, value.0)
 }
 
+#[inline]
+fn set_f32<const L: usize>(slice: &mut &mut [core::simd::Simd<f32, L>], index: usize, value: f32)
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    set_scalar(slice, index, value)
+}
+
 #[inline]
 fn set_vec3u<const L: usize>(
     slice: &mut &mut [[core::simd::Simd<u32, L>; 3]],
@@ -142,6 +150,17 @@ where
     fn(&mut &mut [simd_common::SimdVec3F<L>], usize, rtori_os_model::Vector3F),
 >;
 
+pub type F32Access<'a, const L: usize>
+where
+    LaneCount<L>: SupportedLaneCount,
+= LoaderWriteAccess<
+    'a,
+    L,
+    f32,
+    &'a mut [simd_common::SimdF32<L>],
+    fn(&mut &mut [simd_common::SimdF32<L>], usize, f32),
+>;
+
 pub type Vec3UAccess<'a, const L: usize>
 where
     LaneCount<L>: SupportedLaneCount,
@@ -174,6 +193,8 @@ where
         'call: 'output,
         'loader: 'output,
     {
+        self.inner.state.node_positions_unchanging.dirty = true;
+
         LoaderWriteAccess {
             data: &mut self.inner.state.node_positions_unchanging.data,
             len: self.size.nodes.try_into().unwrap(),
@@ -195,6 +216,8 @@ where
         'call: 'output,
         'loader: 'output,
     {
+        self.inner.state.node_external_forces.dirty = true;
+
         LoaderWriteAccess {
             data: &mut self.inner.state.node_external_forces,
             len: self.size.nodes.try_into().unwrap(),
@@ -246,6 +269,9 @@ where
             set_scalar(&mut data.1, index, value.fixed.into());
         }
 
+        self.inner.state.node_mass.dirty = true;
+        self.inner.state.node_fixed.dirty = true;
+
         LoaderWriteAccess {
             data: (
                 &mut self.inner.state.node_mass,
@@ -257,6 +283,65 @@ where
         }
     }
 
+    type NodeMassAccess<'a>
+        = F32Access<'a, L>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_mass<'call, 'output>(&'call mut self) -> Self::NodeMassAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        self.inner.state.node_mass.dirty = true;
+
+        LoaderWriteAccess {
+            data: &mut self.inner.state.node_mass,
+            len: self.size.nodes.try_into().unwrap(),
+            setter: set_f32,
+            _marker: PhantomData,
+        }
+    }
+
+    type NodeFixedAccess<'a>
+        = LoaderWriteAccess<
+        'a,
+        L,
+        bool,
+        &'a mut [simd_common::SimdU32<L>],
+        fn(&mut &mut [simd_common::SimdU32<L>], usize, bool),
+    >
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_fixed<'call, 'output>(&'call mut self) -> Self::NodeFixedAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        #[inline]
+        fn set_node_fixed<const L: usize>(
+            data: &mut &mut [core::simd::Simd<u32, L>],
+            index: usize,
+            fixed: bool,
+        ) where
+            LaneCount<L>: SupportedLaneCount,
+        {
+            set_scalar(data, index, fixed.into());
+        }
+
+        self.inner.state.node_fixed.dirty = true;
+
+        LoaderWriteAccess {
+            data: &mut self.inner.state.node_fixed,
+            len: self.size.nodes.try_into().unwrap(),
+            setter: set_node_fixed,
+            _marker: PhantomData,
+        }
+    }
+
     type NodeGeometryAccess<'a>
         = LoaderWriteAccess<
         'a,
@@ -376,11 +461,13 @@ where
         (
             &'a mut [simd_common::SimdF32<L>],
             &'a mut [simd_common::SimdF32<L>],
+            &'a mut [simd_common::SimdF32<L>],
         ),
         impl Fn(
             &mut (
                 &'a mut [simd_common::SimdF32<L>],
                 &'a mut [simd_common::SimdF32<L>],
+                &'a mut [simd_common::SimdF32<L>],
             ),
             usize,
             rtori_os_model::CreaseParameters,
@@ -402,6 +489,7 @@ where
             data: &mut (
                 &mut [simd_common::SimdF32<L>],
                 &mut [simd_common::SimdF32<L>],
+                &mut [simd_common::SimdF32<L>],
             ),
             crease_idx: usize,
             parameters: rtori_os_model::CreaseParameters,
@@ -409,13 +497,18 @@ where
             LaneCount<L>: SupportedLaneCount,
         {
             set_scalar(&mut data.0, crease_idx, parameters.k);
-            //set_scalar(&mut self.inner.state.crease_d, crease_idx, parameters.d);
-            set_scalar(&mut data.1, crease_idx, parameters.target_fold_angle);
+            set_scalar(&mut data.1, crease_idx, parameters.d);
+            set_scalar(&mut data.2, crease_idx, parameters.target_fold_angle);
         }
 
+        self.inner.state.crease_k.dirty = true;
+        self.inner.state.crease_d.dirty = true;
+        self.inner.state.crease_target_fold_angle.dirty = true;
+
         LoaderWriteAccess {
             data: (
                 &mut self.inner.state.crease_k,
+                &mut self.inner.state.crease_d,
                 &mut self.inner.state.crease_target_fold_angle,
             ),
             len: self.size.creases.try_into().unwrap(),
@@ -424,6 +517,52 @@ where
         }
     }
 
+    type CreaseStiffnessAccess<'a>
+        = F32Access<'a, L>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_crease_stiffness<'call, 'output>(
+        &'call mut self,
+    ) -> Self::CreaseStiffnessAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        self.inner.state.crease_k.dirty = true;
+
+        LoaderWriteAccess {
+            data: &mut self.inner.state.crease_k,
+            len: self.size.creases.try_into().unwrap(),
+            setter: set_f32,
+            _marker: PhantomData,
+        }
+    }
+
+    type CreaseTargetFoldAngleAccess<'a>
+        = F32Access<'a, L>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_crease_target_fold_angle<'call, 'output>(
+        &'call mut self,
+    ) -> Self::CreaseTargetFoldAngleAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        self.inner.state.crease_target_fold_angle.dirty = true;
+
+        LoaderWriteAccess {
+            data: &mut self.inner.state.crease_target_fold_angle,
+            len: self.size.creases.try_into().unwrap(),
+            setter: set_f32,
+            _marker: PhantomData,
+        }
+    }
+
     type FaceIndicesAccess<'a>
         = Vec3UAccess<'a, L>
     where
@@ -464,6 +603,27 @@ where
         }
     }
 
+    type FaceStiffnessAccess<'a>
+        = F32Access<'a, L>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_face_stiffness<'call, 'output>(&'call mut self) -> Self::FaceStiffnessAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        self.inner.state.face_k.dirty = true;
+
+        LoaderWriteAccess {
+            data: &mut self.inner.state.face_k,
+            len: self.size.faces.try_into().unwrap(),
+            setter: set_f32,
+            _marker: PhantomData,
+        }
+    }
+
     type NodeCreaseAccess<'a>
         = LoaderWriteAccess<
         'a,
@@ -576,6 +736,10 @@ where
             set_scalar(node_beam_d, node_beam_idx, spec.d);
         }
 
+        self.inner.state.node_beam_length.dirty = true;
+        self.inner.state.node_beam_k.dirty = true;
+        self.inner.state.node_beam_d.dirty = true;
+
         LoaderWriteAccess {
             data: (
                 &mut self.inner.state.node_beam_spec,
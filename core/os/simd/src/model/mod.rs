@@ -149,6 +149,15 @@ impl<T> MemorableData<'_, T> {
         core::mem::swap(&mut self.front, &mut self.back)
     }
 
+    /// Sets every element of both buffers to the given value
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.front.fill(value.clone());
+        self.back.fill(value);
+    }
+
     pub const fn empty() -> Self {
         Self {
             front: &mut [],
@@ -235,7 +244,7 @@ where
 
     /* Per-Crease: RO Config */
     pub crease_k: ParameterData<'backer, SimdF32<L>>,
-    // pub crease_d: &'backer [SimdF32], // unused for now
+    pub crease_d: ParameterData<'backer, SimdF32<L>>,
     pub crease_target_fold_angle: ParameterData<'backer, SimdF32<L>>,
 
     /* Per-Crease: RW (fold angles)*/
@@ -248,6 +257,9 @@ where
     pub face_indices: GeometryData<'backer, [SimdU32<L>; 3]>,
     pub face_nominal_angles: GeometryData<'backer, SimdVec3F<L>>,
 
+    /* Per-Face: RO Config */
+    pub face_k: ParameterData<'backer, SimdF32<L>>,
+
     /* Per-Face: RW (normals) */
     pub face_normals: ScratchData<'backer, SimdVec3F<L>>,
 
@@ -277,9 +289,18 @@ where
     pub node_face_forces: ScratchData<'backer, SimdVec3F<L>>,
     pub node_face_error: ScratchData<'backer, SimdF32<L>>,
 
+    /* Per-Node: RO Constraints (the target in the space of the nominal positions, and a stiffness of zero when unconstrained or infinite when pinned) */
+    pub node_constraint_target: ParameterData<'backer, SimdVec3F<L>>,
+    pub node_constraint_stiffness: ParameterData<'backer, SimdF32<L>>,
+
     pub crease_percentage: f32,
     pub dt: f32,
+    /// Scales the stiffness of every face
     pub face_stiffness: f32,
+    /// When set, the face strain is combined with the beam strain in `node_error`
+    pub calculate_face_strain: bool,
+    /// An acceleration applied to every node, scaled by its mass
+    pub gravity: [f32; 3],
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -299,7 +320,7 @@ pub struct DataCharacteristic {
     pub unit_alignment: usize,
 }
 
-pub const DATA_COUNT: usize = 29;
+pub const DATA_COUNT: usize = 33;
 
 macro_rules! define_inner(
     /* per_node */
@@ -316,32 +337,40 @@ macro_rules! define_inner(
     (8) => {m!((8) crease_face_indices PerCrease(G) CreaseFaceIndices<L>)};
     (9) => {m!((9) crease_neighbourhoods PerCrease(G) CreaseNeighbourhood<L>)};
     (10) => {m!((10) crease_k PerCrease(P) SimdF32<L>)};
-    (11) => {m!((12) crease_target_fold_angle PerCrease(P) SimdF32<L>)};
-    (12) => {m!((12) crease_fold_angle PerCrease(M) SimdF32<L>)};
-    (13) => {m!((13) crease_physics PerCrease(S) CreasesPhysicsLens<L>)};
+    (11) => {m!((11) crease_d PerCrease(P) SimdF32<L>)};
+    (12) => {m!((12) crease_target_fold_angle PerCrease(P) SimdF32<L>)};
+    (13) => {m!((13) crease_fold_angle PerCrease(M) SimdF32<L>)};
+    (14) => {m!((14) crease_physics PerCrease(S) CreasesPhysicsLens<L>)};
 
     /* per_face */
-    (14) => {m!((14) face_indices PerFace(G) [SimdU32<L>; 3])};
-    (15) => {m!((15) face_nominal_angles PerFace(G) SimdVec3F<L>)};
-    (16) => {m!((16) face_normals PerFace(S) SimdVec3F<L>)};
+    (15) => {m!((15) face_indices PerFace(G) [SimdU32<L>; 3])};
+    (16) => {m!((16) face_nominal_angles PerFace(G) SimdVec3F<L>)};
+    (17) => {m!((17) face_normals PerFace(S) SimdVec3F<L>)};
 
     /* per_node_crease */
-    (17) => {m!((17) node_crease_crease_indices PerNodeCrease(G) SimdU32<L>)};
-    (18) => {m!((18) node_crease_node_number PerNodeCrease(G) SimdU32<L>)};
-    (19) => {m!((19) node_crease_forces PerNodeCrease(S) SimdVec3F<L>)};
+    (18) => {m!((18) node_crease_crease_indices PerNodeCrease(G) SimdU32<L>)};
+    (19) => {m!((19) node_crease_node_number PerNodeCrease(G) SimdU32<L>)};
+    (20) => {m!((20) node_crease_forces PerNodeCrease(S) SimdVec3F<L>)};
 
     /* per_node_beam */
-    (20) => {m!((20) node_beam_spec PerNodeBeam(G) NodeBeamSpec<L>)};
-    (21) => {m!((21) node_beam_length PerNodeBeam(P) SimdF32<L>)};
-    (22) => {m!((22) node_beam_k PerNodeBeam(P) SimdF32<L>)};
-    (23) => {m!((23) node_beam_d PerNodeBeam(P) SimdF32<L>)};
-    (24) => {m!((24) node_beam_forces PerNodeBeam(S) SimdVec3F<L>)};
-    (25) => {m!((25) node_beam_error PerNodeBeam(S) SimdF32<L>)};
+    (21) => {m!((21) node_beam_spec PerNodeBeam(G) NodeBeamSpec<L>)};
+    (22) => {m!((22) node_beam_length PerNodeBeam(P) SimdF32<L>)};
+    (23) => {m!((23) node_beam_k PerNodeBeam(P) SimdF32<L>)};
+    (24) => {m!((24) node_beam_d PerNodeBeam(P) SimdF32<L>)};
+    (25) => {m!((25) node_beam_forces PerNodeBeam(S) SimdVec3F<L>)};
+    (26) => {m!((26) node_beam_error PerNodeBeam(S) SimdF32<L>)};
 
     /* per_node_face */
-    (26) => {m!((26) node_face_spec PerNodeFace(G) NodeFaceSpec<L>)};
-    (27) => {m!((27) node_face_forces PerNodeFace(S) SimdVec3F<L>)};
-    (28) => {m!((28) node_face_error PerNodeFace(S) SimdF32<L>)}
+    (27) => {m!((27) node_face_spec PerNodeFace(G) NodeFaceSpec<L>)};
+    (28) => {m!((28) node_face_forces PerNodeFace(S) SimdVec3F<L>)};
+    (29) => {m!((29) node_face_error PerNodeFace(S) SimdF32<L>)};
+
+    /* per_node (constraints) */
+    (30) => {m!((30) node_constraint_target PerNode(P) SimdVec3F<L>)};
+    (31) => {m!((31) node_constraint_stiffness PerNode(P) SimdF32<L>)};
+
+    /* per_face (config) */
+    (32) => {m!((32) face_k PerFace(P) SimdF32<L>)}
 );
 
 macro_rules! define (
@@ -374,7 +403,11 @@ macro_rules! define (
         define_inner!(25),
         define_inner!(26),
         define_inner!(27),
-        define_inner!(28)
+        define_inner!(28),
+        define_inner!(29),
+        define_inner!(30),
+        define_inner!(31),
+        define_inner!(32)
     ])
 );
 
@@ -431,11 +464,13 @@ where
             crease_face_indices: GeometryData::empty(),
             crease_neighbourhoods: GeometryData::empty(),
             crease_k: ParameterData::empty(),
+            crease_d: ParameterData::empty(),
             crease_target_fold_angle: ParameterData::empty(),
             crease_fold_angle: MemorableData::empty(),
             crease_physics: ScratchData::empty(),
             face_indices: GeometryData::empty(),
             face_nominal_angles: GeometryData::empty(),
+            face_k: ParameterData::empty(),
             face_normals: ScratchData::empty(),
             node_crease_crease_indices: GeometryData::empty(),
             node_crease_node_number: GeometryData::empty(),
@@ -449,9 +484,13 @@ where
             node_face_spec: GeometryData::empty(),
             node_face_forces: ScratchData::empty(),
             node_face_error: ScratchData::empty(),
+            node_constraint_target: ParameterData::empty(),
+            node_constraint_stiffness: ParameterData::empty(),
             crease_percentage: 0.66,
             dt: 0.001,
             face_stiffness: 1.0,
+            calculate_face_strain: false,
+            gravity: [0.0; 3],
         }
     }
 
@@ -656,6 +695,10 @@ where
                 26 => define_inner!(26),
                 27 => define_inner!(27),
                 28 => define_inner!(28),
+                29 => define_inner!(29),
+                30 => define_inner!(30),
+                31 => define_inner!(31),
+                32 => define_inner!(32),
                 DATA_COUNT.. => unreachable!(),
             }
         }
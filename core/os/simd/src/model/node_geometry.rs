@@ -47,15 +47,101 @@ where
                 count: arr[1],
             },
             beams: NodeGeometryRange {
-                offset: arr[0],
-                count: arr[1],
+                offset: arr[2],
+                count: arr[3],
             },
             faces: NodeGeometryRange {
-                offset: arr[0],
-                count: arr[1],
+                offset: arr[4],
+                count: arr[5],
             },
         }
     }
 
     impl_aosoa!(u32, L, 6);
 }
+
+#[cfg(test)]
+mod test {
+    use core::simd::{LaneCount, SupportedLaneCount};
+
+    use super::{NodeGeometry, NodeGeometryRange};
+    use simd_common::SimdU32;
+
+    /// Every field of every node holds a distinct value: `node_index * 6 + field_order`
+    pub fn test_gather<const L: usize>()
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let value = |node_index: usize, field_order: usize| (node_index * 6 + field_order) as u32;
+        let field = |chunk: usize, field_order: usize| {
+            SimdU32::<{ L }>::from_array(core::array::from_fn(|lane| {
+                value(chunk * L + lane, field_order)
+            }))
+        };
+        let range = |chunk, field_order| NodeGeometryRange {
+            offset: field(chunk, field_order),
+            count: field(chunk, field_order + 1),
+        };
+        let source = [0, 1].map(|chunk| NodeGeometry {
+            creases: range(chunk, 0),
+            beams: range(chunk, 2),
+            faces: range(chunk, 4),
+        });
+
+        for i in 0..L {
+            let node_indices: [usize; L] = core::array::from_fn(|k| k + i);
+            let indices = SimdU32::<{ L }>::from_array(node_indices.map(|idx| idx as u32));
+            let expected = |field_order| {
+                SimdU32::<{ L }>::from_array(node_indices.map(|n| value(n, field_order)))
+            };
+
+            let result = NodeGeometry::gather(&source, indices);
+
+            assert_eq!(
+                result.creases.offset,
+                expected(0),
+                "indices {indices:?}: creases.offset should match"
+            );
+            assert_eq!(
+                result.creases.count,
+                expected(1),
+                "indices {indices:?}: creases.count should match"
+            );
+            assert_eq!(
+                result.beams.offset,
+                expected(2),
+                "indices {indices:?}: beams.offset should match"
+            );
+            assert_eq!(
+                result.beams.count,
+                expected(3),
+                "indices {indices:?}: beams.count should match"
+            );
+            assert_eq!(
+                result.faces.offset,
+                expected(4),
+                "indices {indices:?}: faces.offset should match"
+            );
+            assert_eq!(
+                result.faces.count,
+                expected(5),
+                "indices {indices:?}: faces.count should match"
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_gather_l4() {
+        test_gather::<4>();
+    }
+
+    #[test]
+    pub fn test_gather_l8() {
+        test_gather::<8>();
+    }
+
+    #[test]
+    pub fn test_gather_l16() {
+        test_gather::<16>();
+    }
+}
@@ -35,6 +35,17 @@ where
     pub node_face_error: &'backer mut [SimdF32<L>],
 }
 
+/// Where the per-node results of a step are written
+#[derive(Debug)]
+pub struct PerNodeDest<'backer, const L: usize>
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    pub position_offset: &'backer mut [SimdVec3F<L>],
+    pub velocity: &'backer mut [SimdVec3F<L>],
+    pub error: &'backer mut [SimdF32<L>],
+}
+
 #[derive(Debug)]
 pub struct ReadOnlyInput<'backer, const L: usize>
 where
@@ -59,16 +70,21 @@ where
 
     /* Per-Crease: RO Config */
     pub crease_k: &'backer [SimdF32<L>],
-    // pub crease_d: &'backer [SimdF32], // unused for now
+    pub crease_d: &'backer [SimdF32<L>],
     pub crease_target_fold_angle: &'backer [SimdF32<L>],
 
     /* Per-Crease: RO (per-iteration) (fold angles)*/
     pub crease_fold_angle: &'backer [SimdF32<L>],
+    /// Whether `crease_fold_angle` holds the fold angles of a previous step, which isn't the case before the first one
+    pub crease_fold_angle_known: bool,
 
     /* Per-Face: RO Geometry (split as they are used in different contexts) */
     pub face_indices: &'backer [[SimdU32<L>; 3]],
     pub face_nominal_angles: &'backer [SimdVec3F<L>],
 
+    /* Per-Face: RO Config */
+    pub face_k: &'backer [SimdF32<L>],
+
     /* Per-Node-Crease: RO Geometry (todo: merge) */
     pub node_crease_crease_indices: &'backer [SimdU32<L>],
     pub node_crease_node_number: &'backer [SimdU32<L>],
@@ -84,12 +100,19 @@ where
     /* Per-Node-Face: RO */
     pub node_face_spec: &'backer [NodeFaceSpec<L>],
 
+    /* Per-Node: RO Constraints */
+    pub node_constraint_target: &'backer [SimdVec3F<L>],
+    pub node_constraint_stiffness: &'backer [SimdF32<L>],
+
     pub crease_percentage: f32,
     pub dt: f32,
     pub face_stiffness: f32,
+    pub calculate_face_strain: bool,
+    pub gravity: [f32; 3],
 }
 
 /// The parameter L should be the native vector size of the platform for highest efficiency
+#[inline(always)]
 #[tracing::instrument]
 pub fn process<'a, const L: usize>(
     input: &'a ReadOnlyInput<'a, L>,          // RO
@@ -181,13 +204,21 @@ where
                 node_crease_indices: &input.node_crease_crease_indices,
                 node_crease_node_number: &input.node_crease_node_number,
                 crease_fold_angles: fold_angles,
+                // Without a previous step, the creases are considered still rather than moving from a fold angle of zero
+                crease_previous_fold_angles: if input.crease_fold_angle_known {
+                    input.crease_fold_angle
+                } else {
+                    fold_angles
+                },
                 crease_physics: crease_physics,
                 crease_k: &input.crease_k,
+                crease_d: &input.crease_d,
                 crease_target_fold_angle: &input.crease_target_fold_angle,
                 crease_face_indices: &input.crease_face_indices,
                 face_indices: &input.face_indices,
                 face_normals: face_normals,
                 crease_percentage: input.crease_percentage,
+                dt: input.dt,
             };
 
             let it =
@@ -246,6 +277,7 @@ where
                 face_node_indices: &input.face_indices,
                 face_normals: &face_normals,
                 face_nominal_angles: &input.face_nominal_angles,
+                face_k: &input.face_k,
                 face_stiffness: input.face_stiffness,
             };
 
@@ -273,10 +305,17 @@ where
             node_mass: &input.node_mass,
             node_fixed: &input.node_fixed,
             node_geometry: &input.node_geometry,
+            node_positions_unchanging: &input.node_positions_unchanging,
+            node_constraint_target: &input.node_constraint_target,
+            node_constraint_stiffness: &input.node_constraint_stiffness,
             node_crease_force: per_node_crease_forces,
             node_beam_force: per_node_beam_forces,
             node_face_force: per_node_face_forces,
+            node_beam_error: per_node_beam_error,
+            node_face_error: per_node_face_error,
             dt: input.dt,
+            calculate_face_strain: input.calculate_face_strain,
+            gravity: input.gravity,
         };
         kernels::d_per_node::calculate_node_position(per_node_input)
     }
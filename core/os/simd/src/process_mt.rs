@@ -0,0 +1,333 @@
+//! Multi-threaded counterpart of [`crate::process::process`]
+//!
+//! Each stage is split in contiguous chunks of lanes, each processed as a job of a persistent thread pool,
+//! so that no thread is spawned while stepping.
+//! Like [`crate::Runner::step_dispatched`], the jobs run with the instruction set matching the lane width, see [`crate::LaneWidth`].
+//! As every kernel only writes to the element it iterates over (gathering the rest from the full inputs),
+//! the results are identical to those of the single-threaded path.
+//! The stages themselves stay sequential, as each depends on the results of the previous ones.
+
+use core::num::NonZeroUsize;
+use core::ops::Range;
+use core::simd::{LaneCount, SupportedLaneCount};
+
+use crate::dispatch::with_lane_features;
+use crate::kernels;
+use crate::process::{MemorableInput, PerNodeDest, ReadOnlyInput, ScratchInput};
+
+/// Returns the size of the chunks needed to split `len` elements between at most `thread_count` threads
+fn chunk_size(len: usize, thread_count: NonZeroUsize) -> usize {
+    len.div_ceil(thread_count.get()).max(1)
+}
+
+/// Returns the range covered by the chunk of the given index
+fn chunk_range(chunk_index: usize, chunk_size: usize, chunk_len: usize) -> Range<usize> {
+    let start = chunk_index * chunk_size;
+    start..(start + chunk_len)
+}
+
+#[tracing::instrument]
+pub fn process_mt<const L: usize>(
+    input: &ReadOnlyInput<'_, L>,
+    scratch: &mut ScratchInput<'_, L>,
+    memorable: &mut MemorableInput<'_, L>,
+    dest: &mut PerNodeDest<'_, L>,
+    pool: &rayon::ThreadPool,
+) where
+    LaneCount<L>: SupportedLaneCount,
+    simba::simd::Simd<core::simd::Simd<f32, L>>: nalgebra::SimdRealField,
+{
+    let thread_count = NonZeroUsize::new(pool.current_num_threads()).unwrap_or(NonZeroUsize::MIN);
+
+    // a: per-face
+    {
+        let len = input.face_indices.len();
+        let chunk_size = chunk_size(len, thread_count);
+        let face_normals_dest = &mut scratch.face_normals[..len];
+
+        pool.scope(|scope| {
+            for (chunk_index, face_normals_dest) in
+                face_normals_dest.chunks_mut(chunk_size).enumerate()
+            {
+                let range = chunk_range(chunk_index, chunk_size, face_normals_dest.len());
+                scope.spawn(move |_| {
+                    with_lane_features::<L, _>(
+                        #[inline(always)]
+                        || {
+                            let per_face_input = kernels::a_per_face::PerFaceInputs {
+                                face_node_indices: &input.face_indices[range],
+                                node_positions_offset: input.node_position_offset,
+                                node_positions_unchanging: input.node_positions_unchanging,
+                            };
+
+                            let it = kernels::a_per_face::calculate_normals(&per_face_input);
+                            for (i, output) in it.enumerate() {
+                                face_normals_dest[i] = output.face_normals;
+                            }
+                        },
+                    )
+                });
+            }
+        });
+    }
+    let face_normals: &[_] = scratch.face_normals;
+
+    // b(a): per-crease fold-angles & b(b): per-crease physics
+    {
+        let len = input.crease_face_indices.len();
+        let chunk_size = chunk_size(len, thread_count);
+        let fold_angle_dest = &mut memorable.crease_fold_angle[..len];
+        let physics_dest = &mut scratch.crease_physics[..len];
+
+        pool.scope(|scope| {
+            for (chunk_index, (fold_angle_dest, physics_dest)) in fold_angle_dest
+                .chunks_mut(chunk_size)
+                .zip(physics_dest.chunks_mut(chunk_size))
+                .enumerate()
+            {
+                let range = chunk_range(chunk_index, chunk_size, fold_angle_dest.len());
+                scope.spawn(move |_| {
+                    with_lane_features::<L, _>(
+                        #[inline(always)]
+                        || {
+                            let per_crease_fold_angles_input =
+                                kernels::ba_per_crease_fold_angles::PerCreaseFoldAngleInput {
+                                    crease_face_indices: &input.crease_face_indices[range.clone()],
+                                    crease_neighbourhoods: &input.crease_neighbourhoods
+                                        [range.clone()],
+                                    crease_fold_angle: &input.crease_fold_angle[range.clone()],
+
+                                    node_positions_offset: input.node_position_offset,
+                                    node_positions_unchanging: input.node_positions_unchanging,
+
+                                    face_normals,
+                                };
+
+                            let it =
+                                kernels::ba_per_crease_fold_angles::calculate_crease_fold_angles(
+                                    &per_crease_fold_angles_input,
+                                );
+                            for (i, output) in it.enumerate() {
+                                fold_angle_dest[i] = output;
+                            }
+
+                            let per_crease_physics_input =
+                                kernels::bb_per_crease_physics::PerCreasePhysicsInput {
+                                    crease_neighbourhood: &input.crease_neighbourhoods[range],
+                                    node_positions_unchanging: input.node_positions_unchanging,
+                                    node_positions_offset: input.node_position_offset,
+                                };
+
+                            let it = kernels::bb_per_crease_physics::calculate_crease_physics(
+                                &per_crease_physics_input,
+                            );
+                            for (i, output) in it.enumerate() {
+                                physics_dest[i] = output;
+                            }
+                        },
+                    )
+                });
+            }
+        });
+    }
+    let fold_angles: &[_] = memorable.crease_fold_angle;
+    let crease_physics: &[_] = scratch.crease_physics;
+
+    // c(a): per-node-crease
+    {
+        let len = input.node_crease_crease_indices.len();
+        let chunk_size = chunk_size(len, thread_count);
+        let crease_forces_dest = &mut scratch.node_crease_forces[..len];
+
+        pool.scope(|scope| {
+            for (chunk_index, crease_forces_dest) in
+                crease_forces_dest.chunks_mut(chunk_size).enumerate()
+            {
+                let range = chunk_range(chunk_index, chunk_size, crease_forces_dest.len());
+                scope.spawn(move |_| {
+                    with_lane_features::<L, _>(
+                        #[inline(always)]
+                        || {
+                            let per_node_crease_input =
+                                kernels::ca_per_node_crease::PerNodeCreaseInput {
+                                    node_crease_indices: &input.node_crease_crease_indices
+                                        [range.clone()],
+                                    node_crease_node_number: &input.node_crease_node_number[range],
+                                    crease_fold_angles: fold_angles,
+                                    // Without a previous step, the creases are considered still, see `process`
+                                    crease_previous_fold_angles: if input.crease_fold_angle_known {
+                                        input.crease_fold_angle
+                                    } else {
+                                        fold_angles
+                                    },
+                                    crease_physics,
+                                    crease_k: input.crease_k,
+                                    crease_d: input.crease_d,
+                                    crease_target_fold_angle: input.crease_target_fold_angle,
+                                    crease_face_indices: input.crease_face_indices,
+                                    face_indices: input.face_indices,
+                                    face_normals,
+                                    crease_percentage: input.crease_percentage,
+                                    dt: input.dt,
+                                };
+
+                            let it = kernels::ca_per_node_crease::calculate_node_crease_forces(
+                                &per_node_crease_input,
+                            );
+                            for (i, output) in it.enumerate() {
+                                crease_forces_dest[i] = output;
+                            }
+                        },
+                    )
+                });
+            }
+        });
+    }
+
+    // c(b): per-node-beam
+    {
+        let len = input.node_beam_spec.len();
+        let chunk_size = chunk_size(len, thread_count);
+        let beam_forces_dest = &mut scratch.node_beam_forces[..len];
+        let beam_error_dest = &mut scratch.node_beam_error[..len];
+
+        pool.scope(|scope| {
+            for (chunk_index, (beam_forces_dest, beam_error_dest)) in beam_forces_dest
+                .chunks_mut(chunk_size)
+                .zip(beam_error_dest.chunks_mut(chunk_size))
+                .enumerate()
+            {
+                let range = chunk_range(chunk_index, chunk_size, beam_forces_dest.len());
+                scope.spawn(move |_| {
+                    with_lane_features::<L, _>(
+                        #[inline(always)]
+                        || {
+                            let per_node_beam_input = kernels::cb_per_node_beam::PerNodeBeamInput {
+                                beam_spec: &input.node_beam_spec[range.clone()],
+                                beam_k: &input.node_beam_k[range.clone()],
+                                beam_d: &input.node_beam_d[range.clone()],
+                                beam_length: &input.node_beam_length[range],
+                                node_positions_unchanging: input.node_positions_unchanging,
+                                node_positions_offset: input.node_position_offset,
+                                node_velocity: input.node_velocity,
+                            };
+
+                            let it = kernels::cb_per_node_beam::calculate_node_beam_forces(
+                                &per_node_beam_input,
+                            );
+                            for (i, output) in it.enumerate() {
+                                beam_forces_dest[i] = output.force;
+                                beam_error_dest[i] = output.error;
+                            }
+                        },
+                    )
+                });
+            }
+        });
+    }
+
+    // c(c): per-node-face
+    {
+        let len = input.node_face_spec.len();
+        let chunk_size = chunk_size(len, thread_count);
+        let face_forces_dest = &mut scratch.node_face_forces[..len];
+        let face_error_dest = &mut scratch.node_face_error[..len];
+
+        pool.scope(|scope| {
+            for (chunk_index, (face_forces_dest, face_error_dest)) in face_forces_dest
+                .chunks_mut(chunk_size)
+                .zip(face_error_dest.chunks_mut(chunk_size))
+                .enumerate()
+            {
+                let range = chunk_range(chunk_index, chunk_size, face_forces_dest.len());
+                scope.spawn(move |_| {
+                    with_lane_features::<L, _>(
+                        #[inline(always)]
+                        || {
+                            let per_node_face_input = kernels::cc_per_node_face::PerNodeFaceInput {
+                                node_face_spec: &input.node_face_spec[range],
+                                node_positions_unchanging: input.node_positions_unchanging,
+                                node_positions_offset: input.node_position_offset,
+                                node_velocity: input.node_velocity,
+                                face_node_indices: input.face_indices,
+                                face_normals,
+                                face_nominal_angles: input.face_nominal_angles,
+                                face_k: input.face_k,
+                                face_stiffness: input.face_stiffness,
+                            };
+
+                            let it = kernels::cc_per_node_face::calculate_node_face_forces(
+                                &per_node_face_input,
+                            );
+                            for (i, output) in it.enumerate() {
+                                face_forces_dest[i] = output.force;
+                                face_error_dest[i] = output.error;
+                            }
+                        },
+                    )
+                });
+            }
+        });
+    }
+
+    let per_node_crease_forces: &[_] = scratch.node_crease_forces;
+    let per_node_beam_forces: &[_] = scratch.node_beam_forces;
+    let per_node_beam_error: &[_] = scratch.node_beam_error;
+    let per_node_face_forces: &[_] = scratch.node_face_forces;
+    let per_node_face_error: &[_] = scratch.node_face_error;
+
+    // d: per-node
+    {
+        let len = input.node_position_offset.len();
+        let chunk_size = chunk_size(len, thread_count);
+        let position_dest = &mut dest.position_offset[..len];
+        let velocity_dest = &mut dest.velocity[..len];
+        let error_dest = &mut dest.error[..len];
+
+        pool.scope(|scope| {
+            for (chunk_index, ((position_dest, velocity_dest), error_dest)) in position_dest
+                .chunks_mut(chunk_size)
+                .zip(velocity_dest.chunks_mut(chunk_size))
+                .zip(error_dest.chunks_mut(chunk_size))
+                .enumerate()
+            {
+                let range = chunk_range(chunk_index, chunk_size, position_dest.len());
+                scope.spawn(move |_| {
+                    with_lane_features::<L, _>(
+                        #[inline(always)]
+                        || {
+                            let per_node_input = kernels::d_per_node::PerNodeInput {
+                                node_positions_offset: &input.node_position_offset[range.clone()],
+                                node_velocity: &input.node_velocity[range.clone()],
+                                node_external_forces: &input.node_external_forces[range.clone()],
+                                node_mass: &input.node_mass[range.clone()],
+                                node_fixed: &input.node_fixed[range.clone()],
+                                node_geometry: &input.node_geometry[range.clone()],
+                                node_positions_unchanging: &input.node_positions_unchanging
+                                    [range.clone()],
+                                node_constraint_target: &input.node_constraint_target
+                                    [range.clone()],
+                                node_constraint_stiffness: &input.node_constraint_stiffness[range],
+                                node_crease_force: per_node_crease_forces,
+                                node_beam_force: per_node_beam_forces,
+                                node_face_force: per_node_face_forces,
+                                node_beam_error: per_node_beam_error,
+                                node_face_error: per_node_face_error,
+                                dt: input.dt,
+                                calculate_face_strain: input.calculate_face_strain,
+                                gravity: input.gravity,
+                            };
+
+                            let it = kernels::d_per_node::calculate_node_position(per_node_input);
+                            for (i, output) in it.enumerate() {
+                                position_dest[i] = output.position_offset;
+                                velocity_dest[i] = output.velocity;
+                                error_dest[i] = output.error;
+                            }
+                        },
+                    )
+                });
+            }
+        });
+    }
+}
@@ -1,8 +1,213 @@
 use crate::{extractor, loader, model, process};
 use core::simd::{LaneCount, SupportedLaneCount};
+use rtori_os_model::{NodeConstraint, Vector3F};
+use simd_common::{SimdF32, SimdVec3F};
 
 pub use simd_common::MIN_LANE_COUNT_32;
 
+/// Measures of how far the simulation is from equilibrium, after the last step
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Residuals {
+    /// The highest speed of a node
+    pub max_velocity: f32,
+    /// The longest distance travelled by a node during the last step
+    pub max_displacement: f32,
+    /// The sum of the kinetic energy of every node
+    pub kinetic_energy: f32,
+}
+
+/// The parameter buffers written since the last step (or since the creation of the runner),
+/// so that the backends keeping a copy of them elsewhere only upload what changed before the next one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DirtyParameters {
+    pub node_positions: bool,
+    pub node_external_forces: bool,
+    pub node_mass: bool,
+    pub node_fixed: bool,
+    pub crease_k: bool,
+    pub crease_d: bool,
+    pub crease_target_fold_angle: bool,
+    pub face_k: bool,
+    pub node_beam_length: bool,
+    pub node_beam_k: bool,
+    pub node_beam_d: bool,
+    pub node_constraint_target: bool,
+    pub node_constraint_stiffness: bool,
+}
+
+impl DirtyParameters {
+    pub const fn any(&self) -> bool {
+        self.node_positions
+            || self.node_external_forces
+            || self.node_mass
+            || self.node_fixed
+            || self.crease_k
+            || self.crease_d
+            || self.crease_target_fold_angle
+            || self.face_k
+            || self.node_beam_length
+            || self.node_beam_k
+            || self.node_beam_d
+            || self.node_constraint_target
+            || self.node_constraint_stiffness
+    }
+}
+
+/// Which half of the buffers carried over from one step to the next is accessed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepBuffer {
+    /// The values after the last step
+    Current,
+    /// The values before the last step
+    Previous,
+}
+
+/// The half of a memorable buffer holding the given values
+fn memorable_half<'a, T>(data: &'a model::MemorableData<'_, T>, buffer: StepBuffer) -> &'a [T] {
+    match buffer {
+        StepBuffer::Current => &*data.back,
+        StepBuffer::Previous => &*data.front,
+    }
+}
+
+fn memorable_half_mut<'a, T>(
+    data: &'a mut model::MemorableData<'_, T>,
+    buffer: StepBuffer,
+) -> &'a mut [T] {
+    match buffer {
+        StepBuffer::Current => &mut *data.back,
+        StepBuffer::Previous => &mut *data.front,
+    }
+}
+
+#[inline]
+fn get_lane_vec3<const L: usize>(slice: &[SimdVec3F<L>], index: usize) -> Vector3F
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let chunk = &slice[index / L];
+    Vector3F(core::array::from_fn(|c| chunk[c][index % L]))
+}
+
+#[inline]
+fn set_lane_vec3<const L: usize>(slice: &mut [SimdVec3F<L>], index: usize, value: Vector3F)
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let chunk = &mut slice[index / L];
+    for c in 0..3 {
+        chunk[c][index % L] = value.0[c];
+    }
+}
+
+/// The per-element parameters that can be changed once the model is loaded, to copy them out of a runner
+#[derive(Debug)]
+pub struct ParametersMut<'a> {
+    pub node_mass: &'a mut [f32],
+    pub node_fixed: &'a mut [bool],
+    pub node_external_forces: &'a mut [Vector3F],
+    /// Only meaningful when the stiffness isn't zero
+    pub node_constraint_target: &'a mut [Vector3F],
+    /// Zero when unconstrained, infinite when pinned
+    pub node_constraint_stiffness: &'a mut [f32],
+    pub crease_k: &'a mut [f32],
+    pub crease_target_fold_angle: &'a mut [f32],
+    pub face_k: &'a mut [f32],
+}
+
+/// The per-element parameters that can be changed once the model is loaded, to copy them into a runner
+#[derive(Debug, Clone, Copy)]
+pub struct Parameters<'a> {
+    pub node_mass: &'a [f32],
+    pub node_fixed: &'a [bool],
+    pub node_external_forces: &'a [Vector3F],
+    pub node_constraint_target: &'a [Vector3F],
+    pub node_constraint_stiffness: &'a [f32],
+    pub crease_k: &'a [f32],
+    pub crease_target_fold_angle: &'a [f32],
+    pub face_k: &'a [f32],
+}
+
+#[inline]
+fn get_lanes<const L: usize, T: core::simd::SimdElement>(
+    slice: &[core::simd::Simd<T, L>],
+    to: &mut [T],
+) where
+    LaneCount<L>: SupportedLaneCount,
+{
+    for (index, to) in to.iter_mut().enumerate() {
+        *to = slice[index / L][index % L];
+    }
+}
+
+#[inline]
+fn set_lanes<const L: usize, T: core::simd::SimdElement>(
+    slice: &mut [core::simd::Simd<T, L>],
+    from: &[T],
+) where
+    LaneCount<L>: SupportedLaneCount,
+{
+    for (index, from) in from.iter().enumerate() {
+        slice[index / L][index % L] = *from;
+    }
+}
+
+/// `damping_percentage` of the critical damping of a spring of stiffness `k` between a mass and a fixed point
+#[inline]
+fn damping(damping_percentage: f32, k: f32, mass: f32) -> f32 {
+    damping_percentage * 2.0 * num_traits::Float::sqrt(k * mass)
+}
+
+#[inline]
+fn node_mass<const L: usize>(state: &model::State<'_, L>, node_index: u32) -> f32
+where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let node_index = node_index as usize;
+    state.node_mass[node_index / L][node_index % L]
+}
+
+/// See [`Runner::update_damping`]
+fn derive_beam_damping<const L: usize>(
+    state: &mut model::State<'_, L>,
+    node_beam_index: usize,
+    damping_percentage: f32,
+) where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let (chunk, lane) = (node_beam_index / L, node_beam_index % L);
+    let spec = &state.node_beam_spec[chunk];
+    let min_mass = f32::min(
+        node_mass(state, spec.node_indices[lane]),
+        node_mass(state, spec.neighbour_indices[lane]),
+    );
+    state.node_beam_d.data[chunk][lane] = damping(
+        damping_percentage,
+        state.node_beam_k.data[chunk][lane],
+        min_mass,
+    );
+}
+
+/// See [`Runner::update_damping`]
+fn derive_crease_damping<const L: usize>(
+    state: &mut model::State<'_, L>,
+    crease_index: usize,
+    damping_percentage: f32,
+) where
+    LaneCount<L>: SupportedLaneCount,
+{
+    let (chunk, lane) = (crease_index / L, crease_index % L);
+    let [a, b] = state.crease_neighbourhoods[chunk]
+        .adjacent_node_indices
+        .map(|indices| indices[lane]);
+    let min_mass = f32::min(node_mass(state, a), node_mass(state, b));
+    state.crease_d.data[chunk][lane] = damping(
+        damping_percentage,
+        state.crease_k.data[chunk][lane],
+        min_mass,
+    );
+}
+
 #[derive(Debug)]
 pub struct Runner<'backer, const L: usize = { MIN_LANE_COUNT_32 }>
 where
@@ -22,10 +227,264 @@ where
         self.steps
     }
 
+    /// Sets the number of steps done, for instance when resuming a simulation
+    pub fn set_steps(&mut self, steps: u64) {
+        self.steps = steps;
+    }
+
+    /// The parameter buffers written since the last step (or since the creation of the runner)
+    pub fn dirty_parameters(&self) -> DirtyParameters {
+        let state = &self.state;
+        DirtyParameters {
+            node_positions: state.node_positions_unchanging.dirty,
+            node_external_forces: state.node_external_forces.dirty,
+            node_mass: state.node_mass.dirty,
+            node_fixed: state.node_fixed.dirty,
+            crease_k: state.crease_k.dirty,
+            crease_d: state.crease_d.dirty,
+            crease_target_fold_angle: state.crease_target_fold_angle.dirty,
+            face_k: state.face_k.dirty,
+            node_beam_length: state.node_beam_length.dirty,
+            node_beam_k: state.node_beam_k.dirty,
+            node_beam_d: state.node_beam_d.dirty,
+            node_constraint_target: state.node_constraint_target.dirty,
+            node_constraint_stiffness: state.node_constraint_stiffness.dirty,
+        }
+    }
+
+    /// Marks every parameter buffer as clean, once a step has taken their changes into account
+    fn clear_dirty(&mut self) {
+        let state = &mut self.state;
+        for dirty in [
+            &mut state.node_positions_unchanging.dirty,
+            &mut state.node_external_forces.dirty,
+            &mut state.node_mass.dirty,
+            &mut state.node_fixed.dirty,
+            &mut state.crease_k.dirty,
+            &mut state.crease_d.dirty,
+            &mut state.crease_target_fold_angle.dirty,
+            &mut state.face_k.dirty,
+            &mut state.node_beam_length.dirty,
+            &mut state.node_beam_k.dirty,
+            &mut state.node_beam_d.dirty,
+            &mut state.node_constraint_target.dirty,
+            &mut state.node_constraint_stiffness.dirty,
+        ] {
+            *dirty = false;
+        }
+    }
+
+    /// Brings the model back to its rest state, as it was just after loading.
+    /// The geometry and the parameters are kept.
+    pub fn reset(&mut self) {
+        let state = &mut self.state;
+
+        state.node_position_offset.fill(Default::default());
+        state.node_velocity.fill(Default::default());
+        state.crease_fold_angle.fill(Default::default());
+        state.node_error.fill(Default::default());
+
+        self.steps = 0;
+    }
+
+    /// Attaches the node to a target position, or detaches it with `None`.
+    /// Unlike the geometry and the parameters loaded with the model, it can be changed at every step.
+    pub fn set_node_constraint(&mut self, node_index: u32, constraint: Option<NodeConstraint>) {
+        let state = &mut self.state;
+        let node_index = node_index as usize;
+
+        let (target, stiffness) = constraint
+            .map(|constraint| (constraint.target(), constraint.stiffness()))
+            .unwrap_or_default();
+        set_lane_vec3(&mut state.node_constraint_target, node_index, target);
+        state.node_constraint_stiffness[node_index / L][node_index % L] = stiffness;
+
+        state.node_constraint_target.dirty = true;
+        state.node_constraint_stiffness.dirty = true;
+    }
+
+    /// Detaches every node from its target
+    pub fn clear_node_constraints(&mut self) {
+        let state = &mut self.state;
+
+        state.node_constraint_stiffness.fill(Default::default());
+        state.node_constraint_stiffness.dirty = true;
+    }
+
+    /// Copies the state carried over from one step to the next into per-element slices, so that it doesn't depend on the lane count.
+    /// Only as many nodes & creases as the slices hold are copied.
+    pub fn copy_memorable_out(
+        &self,
+        buffer: StepBuffer,
+        node_position_offset: &mut [Vector3F],
+        node_velocity: &mut [Vector3F],
+        crease_fold_angle: &mut [f32],
+    ) {
+        let state = &self.state;
+
+        let offsets = memorable_half(&state.node_position_offset, buffer);
+        for (node_index, to) in node_position_offset.iter_mut().enumerate() {
+            *to = get_lane_vec3(offsets, node_index);
+        }
+
+        let velocities = memorable_half(&state.node_velocity, buffer);
+        for (node_index, to) in node_velocity.iter_mut().enumerate() {
+            *to = get_lane_vec3(velocities, node_index);
+        }
+
+        let fold_angles: &[SimdF32<L>] = memorable_half(&state.crease_fold_angle, buffer);
+        for (crease_index, to) in crease_fold_angle.iter_mut().enumerate() {
+            *to = fold_angles[crease_index / L][crease_index % L];
+        }
+    }
+
+    /// The reverse of [`Self::copy_memorable_out`]
+    pub fn copy_memorable_in(
+        &mut self,
+        buffer: StepBuffer,
+        node_position_offset: &[Vector3F],
+        node_velocity: &[Vector3F],
+        crease_fold_angle: &[f32],
+    ) {
+        let state = &mut self.state;
+
+        let offsets = memorable_half_mut(&mut state.node_position_offset, buffer);
+        for (node_index, from) in node_position_offset.iter().enumerate() {
+            set_lane_vec3(offsets, node_index, *from);
+        }
+
+        let velocities = memorable_half_mut(&mut state.node_velocity, buffer);
+        for (node_index, from) in node_velocity.iter().enumerate() {
+            set_lane_vec3(velocities, node_index, *from);
+        }
+
+        let fold_angles: &mut [SimdF32<L>] =
+            memorable_half_mut(&mut state.crease_fold_angle, buffer);
+        for (crease_index, from) in crease_fold_angle.iter().enumerate() {
+            fold_angles[crease_index / L][crease_index % L] = *from;
+        }
+    }
+
+    /// Copies the parameters that can be changed after loading into per-element slices, so that they don't depend on the lane count.
+    /// Only as many elements as the slices hold are copied.
+    pub fn copy_parameters_out(&self, to: ParametersMut<'_>) {
+        let state = &self.state;
+
+        get_lanes(&state.node_mass, to.node_mass);
+        for (node_index, to) in to.node_fixed.iter_mut().enumerate() {
+            *to = state.node_fixed[node_index / L][node_index % L] != 0;
+        }
+        for (node_index, to) in to.node_external_forces.iter_mut().enumerate() {
+            *to = get_lane_vec3(&state.node_external_forces, node_index);
+        }
+        for (node_index, to) in to.node_constraint_target.iter_mut().enumerate() {
+            *to = get_lane_vec3(&state.node_constraint_target, node_index);
+        }
+        get_lanes(
+            &state.node_constraint_stiffness,
+            to.node_constraint_stiffness,
+        );
+        get_lanes(&state.crease_k, to.crease_k);
+        get_lanes(&state.crease_target_fold_angle, to.crease_target_fold_angle);
+        get_lanes(&state.face_k, to.face_k);
+    }
+
+    /// The reverse of [`Self::copy_parameters_out`].
+    /// The damping isn't derived from the copied masses & stiffnesses, see [`Self::update_damping`].
+    pub fn copy_parameters_in(&mut self, from: Parameters<'_>) {
+        let state = &mut self.state;
+
+        set_lanes(&mut state.node_mass, from.node_mass);
+        for (node_index, from) in from.node_fixed.iter().enumerate() {
+            state.node_fixed[node_index / L][node_index % L] = u32::from(*from);
+        }
+        for (node_index, from) in from.node_external_forces.iter().enumerate() {
+            set_lane_vec3(&mut state.node_external_forces, node_index, *from);
+        }
+        for (node_index, from) in from.node_constraint_target.iter().enumerate() {
+            set_lane_vec3(&mut state.node_constraint_target, node_index, *from);
+        }
+        set_lanes(
+            &mut state.node_constraint_stiffness,
+            from.node_constraint_stiffness,
+        );
+        set_lanes(&mut state.crease_k, from.crease_k);
+        set_lanes(
+            &mut state.crease_target_fold_angle,
+            from.crease_target_fold_angle,
+        );
+        set_lanes(&mut state.face_k, from.face_k);
+
+        for dirty in [
+            &mut state.node_mass.dirty,
+            &mut state.node_fixed.dirty,
+            &mut state.node_external_forces.dirty,
+            &mut state.node_constraint_target.dirty,
+            &mut state.node_constraint_stiffness.dirty,
+            &mut state.crease_k.dirty,
+            &mut state.crease_target_fold_angle.dirty,
+            &mut state.face_k.dirty,
+        ] {
+            *dirty = true;
+        }
+    }
+
+    /// Steps the simulation once.
+    /// Inlined, down to the kernels, so that they are generated for the target features of the caller, see [`crate::LaneWidth`]
+    #[inline(always)]
     #[tracing::instrument]
     pub fn step(&mut self) -> Result<(), ()> {
-        let state = &mut self.state;
+        let (input, mut scratch, mut memorable, dest) =
+            Self::split_state(&mut self.state, self.steps);
+
+        let it = process::process(&input, &mut scratch, &mut memorable);
+
+        let position_dest = dest.position_offset;
+        assert!(it.len() <= position_dest.len());
+
+        let velocity_dest = dest.velocity;
+        assert!(it.len() <= velocity_dest.len());
+
+        let error_dest = dest.error;
+        assert!(it.len() <= error_dest.len());
+
+        for (i, output) in it.enumerate() {
+            position_dest[i] = output.position_offset;
+            velocity_dest[i] = output.velocity;
+            error_dest[i] = output.error;
+        }
+
+        self.finish_step();
+
+        Ok(())
+    }
+
+    /// Steps the simulation, splitting each stage between the threads of `pool`.
+    /// The results are the same as with [`Self::step`].
+    #[cfg(feature = "std")]
+    #[tracing::instrument]
+    pub fn step_mt(&mut self, pool: &rayon::ThreadPool) -> Result<(), ()> {
+        let (input, mut scratch, mut memorable, mut dest) =
+            Self::split_state(&mut self.state, self.steps);
+
+        crate::process_mt::process_mt(&input, &mut scratch, &mut memorable, &mut dest, pool);
+
+        self.finish_step();
+
+        Ok(())
+    }
 
+    /// Splits the state into the inputs and outputs of the step following the `steps` done
+    #[inline(always)]
+    fn split_state<'state>(
+        state: &'state mut model::State<'backer, L>,
+        steps: u64,
+    ) -> (
+        process::ReadOnlyInput<'state, L>,
+        process::ScratchInput<'state, L>,
+        process::MemorableInput<'state, L>,
+        process::PerNodeDest<'state, L>,
+    ) {
         let input = process::ReadOnlyInput {
             node_geometry: &state.node_geometry,
             node_positions_unchanging: &state.node_positions_unchanging,
@@ -37,10 +496,13 @@ where
             crease_face_indices: &state.crease_face_indices,
             crease_neighbourhoods: &state.crease_neighbourhoods,
             crease_k: &state.crease_k,
+            crease_d: &state.crease_d,
             crease_target_fold_angle: &state.crease_target_fold_angle,
             crease_fold_angle: state.crease_fold_angle.back,
+            crease_fold_angle_known: steps > 0,
             face_indices: &state.face_indices,
             face_nominal_angles: &state.face_nominal_angles,
+            face_k: &state.face_k,
             node_crease_crease_indices: &state.node_crease_crease_indices,
             node_crease_node_number: &state.node_crease_node_number,
             node_beam_spec: &state.node_beam_spec,
@@ -48,12 +510,16 @@ where
             node_beam_k: &state.node_beam_k,
             node_beam_d: &state.node_beam_d,
             node_face_spec: &state.node_face_spec,
+            node_constraint_target: &state.node_constraint_target,
+            node_constraint_stiffness: &state.node_constraint_stiffness,
             crease_percentage: state.crease_percentage,
             dt: state.dt,
             face_stiffness: state.face_stiffness,
+            calculate_face_strain: state.calculate_face_strain,
+            gravity: state.gravity,
         };
 
-        let mut scratch = process::ScratchInput {
+        let scratch = process::ScratchInput {
             crease_physics: &mut state.crease_physics,
             face_normals: &mut state.face_normals,
             node_crease_forces: &mut state.node_crease_forces,
@@ -63,35 +529,30 @@ where
             node_face_error: &mut state.node_face_error,
         };
 
-        let mut memorable = process::MemorableInput {
+        let memorable = process::MemorableInput {
             crease_fold_angle: &mut state.crease_fold_angle.front,
         };
 
-        let it = process::process(&input, &mut scratch, &mut memorable);
-
-        let position_dest = &mut state.node_position_offset.front;
-        assert!(it.len() <= position_dest.len());
-
-        let velocity_dest = &mut state.node_velocity.front;
-        assert!(it.len() <= velocity_dest.len());
+        let dest = process::PerNodeDest {
+            position_offset: &mut state.node_position_offset.front,
+            velocity: &mut state.node_velocity.front,
+            error: &mut state.node_error,
+        };
 
-        let error_dest = &mut state.node_error;
-        assert!(it.len() <= error_dest.len());
+        (input, scratch, memorable, dest)
+    }
 
-        for (i, output) in it.enumerate() {
-            position_dest[i] = output.position_offset;
-            velocity_dest[i] = output.velocity;
-            error_dest[i] = output.error;
-        }
+    #[inline(always)]
+    fn finish_step(&mut self) {
+        let state = &mut self.state;
 
         // Swap
         state.node_position_offset.swap();
         state.node_velocity.swap();
         state.crease_fold_angle.swap();
 
+        self.clear_dirty();
         self.steps += 1;
-
-        Ok(())
     }
 
     pub fn query_backing_size_requirement(sizes: &rtori_os_model::ModelSize) -> usize {
@@ -128,10 +589,188 @@ where
         loader::Loader::new(self)
     }
 
+    /// The fraction of their target fold angle the creases are driven towards
+    pub fn fold_percentage(&self) -> f32 {
+        self.state.crease_percentage
+    }
+
     pub fn set_fold_percentage(&mut self, fold_percentage: f32) {
         self.state.crease_percentage = fold_percentage;
     }
 
+    /// The timestep used for integration
+    pub fn dt(&self) -> f32 {
+        self.state.dt
+    }
+
+    pub fn set_dt(&mut self, dt: f32) {
+        self.state.dt = dt;
+    }
+
+    /// The stiffness pulling each face's angles back towards their nominal values, scaling that of each face
+    pub fn face_stiffness(&self) -> f32 {
+        self.state.face_stiffness
+    }
+
+    pub fn set_face_stiffness(&mut self, face_stiffness: f32) {
+        self.state.face_stiffness = face_stiffness;
+    }
+
+    /// Whether the node error combines the face strain with the beam strain, or only uses the latter
+    pub fn calculate_face_strain(&self) -> bool {
+        self.state.calculate_face_strain
+    }
+
+    pub fn set_calculate_face_strain(&mut self, calculate_face_strain: bool) {
+        self.state.calculate_face_strain = calculate_face_strain;
+    }
+
+    /// The acceleration applied to every node, scaled by its mass
+    pub fn gravity(&self) -> Vector3F {
+        Vector3F(self.state.gravity)
+    }
+
+    pub fn set_gravity(&mut self, gravity: Vector3F) {
+        self.state.gravity = gravity.0;
+    }
+
+    /// Derives the damping of every beam & crease from its stiffness and the current masses of its nodes, as the import does:
+    /// `damping_percentage` of the critical damping of a spring between the lightest of them and a fixed point.
+    /// To be called once masses or stiffnesses change, as the damping doesn't follow them otherwise.
+    pub fn update_damping(&mut self, damping_percentage: f32) {
+        let state = &mut self.state;
+        for node_beam_index in 0..state.size().node_beams as usize {
+            derive_beam_damping(state, node_beam_index, damping_percentage);
+        }
+        for crease_index in 0..state.size().creases as usize {
+            derive_crease_damping(state, crease_index, damping_percentage);
+        }
+
+        state.node_beam_d.dirty = true;
+        state.crease_d.dirty = true;
+    }
+
+    /// Derives the damping of a single crease from its stiffness, see [`Self::update_damping`].
+    /// To be called once the stiffness of that crease changes.
+    pub fn update_crease_damping(&mut self, crease_index: u32, damping_percentage: f32) {
+        let state = &mut self.state;
+        derive_crease_damping(state, crease_index as usize, damping_percentage);
+        state.crease_d.dirty = true;
+    }
+
+    /// Derives the damping of the beams & creases a single node is part of, see [`Self::update_damping`].
+    /// To be called once the mass of that node changes.
+    pub fn update_node_damping(&mut self, node_index: u32, damping_percentage: f32) {
+        let state = &mut self.state;
+        let node_index = node_index as usize;
+        let geometry = state.node_geometry[node_index / L];
+        let lane_range = |range: model::NodeGeometryRange<L>, lane: usize| {
+            let offset = range.offset[lane] as usize;
+            offset..offset + range.count[lane] as usize
+        };
+
+        for node_crease_index in lane_range(geometry.creases, node_index % L) {
+            let crease_index =
+                state.node_crease_crease_indices[node_crease_index / L][node_crease_index % L];
+            derive_crease_damping(state, crease_index as usize, damping_percentage);
+        }
+
+        for node_beam_index in lane_range(geometry.beams, node_index % L) {
+            derive_beam_damping(state, node_beam_index, damping_percentage);
+
+            // The same beam, as seen from its other node
+            let neighbour_index = state.node_beam_spec[node_beam_index / L].neighbour_indices
+                [node_beam_index % L] as usize;
+            let neighbour_geometry = state.node_geometry[neighbour_index / L];
+            for neighbour_beam_index in lane_range(neighbour_geometry.beams, neighbour_index % L) {
+                let spec = &state.node_beam_spec[neighbour_beam_index / L];
+                if spec.neighbour_indices[neighbour_beam_index % L] as usize == node_index {
+                    derive_beam_damping(state, neighbour_beam_index, damping_percentage);
+                }
+            }
+        }
+
+        state.node_beam_d.dirty = true;
+        state.crease_d.dirty = true;
+    }
+
+    /// Computes the largest stable timestep from the current stiffnesses & masses, following Origami Simulator:
+    /// `0.9 / (2 * PI * maximum_natural_frequency)`, every beam being a spring of natural frequency `sqrt(k / min_mass)`,
+    /// 0.9 being there for good measure.
+    ///
+    /// Returns `None` when no beam constrains the timestep.
+    pub fn compute_dt(&self) -> Option<f32> {
+        let state = &self.state;
+        let mass = |node_index: u32| {
+            let node_index = node_index as usize;
+            state.node_mass[node_index / L][node_index % L]
+        };
+
+        let maximum_natural_frequency = state
+            .node_beam_spec
+            .iter()
+            .zip(state.node_beam_k.iter())
+            .flat_map(|(spec, k)| {
+                (0..L).map(move |lane| {
+                    let min_mass = f32::min(
+                        mass(spec.node_indices[lane]),
+                        mass(spec.neighbour_indices[lane]),
+                    );
+                    num_traits::Float::sqrt(k[lane] / min_mass)
+                })
+            })
+            .filter(|frequency| frequency.is_finite())
+            .fold(0.0f32, f32::max);
+
+        if maximum_natural_frequency <= 0.0 {
+            return None;
+        }
+
+        Some((1.0 / (2.0 * core::f32::consts::PI * maximum_natural_frequency)) * 0.9)
+    }
+
+    /// Computes the residuals of the last step
+    pub fn residuals(&self) -> Residuals {
+        let state = &self.state;
+        let node_count = usize::try_from(state.size().nodes).unwrap();
+
+        let (max_velocity_sq, max_displacement_sq, kinetic_energy) = (0..node_count).fold(
+            (0.0f32, 0.0f32, 0.0f32),
+            |(max_velocity_sq, max_displacement_sq, kinetic_energy), node_index| {
+                let (chunk, lane) = (node_index / L, node_index % L);
+
+                // The padding lanes have no mass (and thus meaningless velocities), so they are skipped
+                let mass = state.node_mass[chunk][lane];
+                if mass == 0.0 {
+                    return (max_velocity_sq, max_displacement_sq, kinetic_energy);
+                }
+
+                let velocity = state.node_velocity.back[chunk];
+                let velocity_sq: f32 = velocity.iter().map(|c| c[lane] * c[lane]).sum();
+
+                // The front buffer holds the offsets before the last step
+                let current = state.node_position_offset.back[chunk];
+                let previous = state.node_position_offset.front[chunk];
+                let displacement_sq: f32 = (0..3)
+                    .map(|c| current[c][lane] - previous[c][lane])
+                    .map(|d| d * d)
+                    .sum();
+
+                (
+                    max_velocity_sq.max(velocity_sq),
+                    max_displacement_sq.max(displacement_sq),
+                    kinetic_energy + 0.5 * mass * velocity_sq,
+                )
+            },
+        );
+
+        Residuals {
+            max_velocity: num_traits::Float::sqrt(max_velocity_sq),
+            max_displacement: num_traits::Float::sqrt(max_displacement_sq),
+            kinetic_energy,
+        }
+    }
+
     pub fn extract<'a>(
         &'a self,
         _flags: rtori_os_model::ExtractFlags,
@@ -142,3 +781,295 @@ where
         extractor::Extractor::new(&self.state)
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    extern crate alloc;
+
+    use alloc::vec::Vec;
+    use core::simd::{LaneCount, SupportedLaneCount};
+    use rtori_os_model::{
+        CreaseGeometry, LoaderDyn as _, ModelSize, NodeBeamPointer, NodeBeamSpec,
+        NodeCreasePointer, NodeCreaseSpec, NodeFacePointer, NodeGeometry, Vector3F,
+    };
+
+    use super::DirtyParameters;
+    use crate::owned::OwnedRunner;
+
+    fn test_compute_dt<const L: usize>()
+    where
+        LaneCount<L>: SupportedLaneCount,
+        simba::simd::Simd<core::simd::Simd<f32, L>>: simba::simd::SimdRealField,
+    {
+        // Without beams, nothing constrains the timestep
+        let size = ModelSize {
+            nodes: 3,
+            ..Default::default()
+        };
+        let runner = OwnedRunner::<L, alloc::alloc::Global>::with_size(&size);
+        assert_eq!(runner.compute_dt(), None);
+
+        // Two beams, each seen from both of their nodes
+        let size = ModelSize {
+            nodes: 3,
+            node_beams: 4,
+            ..Default::default()
+        };
+        let mut runner = OwnedRunner::<L, alloc::alloc::Global>::with_size(&size);
+        {
+            let mut loader = runner.load();
+            loader.copy_node_mass(&[1.0, 0.5, 2.0], 0);
+
+            let beam = |node_index, neighbour_index, k| NodeBeamSpec {
+                node_index,
+                k,
+                d: 0.0,
+                length: 1.0,
+                neighbour_index,
+            };
+            loader.copy_node_beam(
+                &[
+                    beam(0, 1, 20.0),
+                    beam(1, 0, 20.0),
+                    beam(1, 2, 30.0),
+                    beam(2, 1, 30.0),
+                ],
+                0,
+            );
+        }
+
+        // The stiffest beam relative to the lightest of its nodes is the second one, with k / m = 30 / 0.5
+        let maximum_natural_frequency: f32 = num_traits::Float::sqrt(60.0);
+        assert_eq!(
+            runner.compute_dt(),
+            Some((1.0 / (2.0 * core::f32::consts::PI * maximum_natural_frequency)) * 0.9)
+        );
+    }
+
+    #[test]
+    pub fn test_compute_dt_l4() {
+        test_compute_dt::<4>();
+    }
+
+    #[test]
+    pub fn test_compute_dt_l8() {
+        test_compute_dt::<8>();
+    }
+
+    #[test]
+    pub fn test_compute_dt_l16() {
+        test_compute_dt::<16>();
+    }
+
+    /// A chain of three beams 0-1-2-3, each seen from both of their nodes, and a crease along the middle one
+    fn chain_size(creases: u32) -> ModelSize {
+        ModelSize {
+            nodes: 4,
+            creases,
+            node_beams: 6,
+            node_creases: creases * 4,
+            ..Default::default()
+        }
+    }
+
+    fn load_chain<const L: usize>(
+        runner: &mut OwnedRunner<'_, L, alloc::alloc::Global>,
+        creases: u32,
+    ) where
+        LaneCount<L>: SupportedLaneCount,
+        simba::simd::Simd<core::simd::Simd<f32, L>>: simba::simd::SimdRealField,
+    {
+        let mut loader = runner.load();
+        loader.copy_node_position(
+            &[
+                Vector3F([0.0, 0.0, 0.0]),
+                Vector3F([1.0, 0.0, 0.0]),
+                Vector3F([1.0, 1.0, 0.0]),
+                Vector3F([0.0, 1.0, 0.0]),
+            ],
+            0,
+        );
+        loader.copy_node_mass(&[1.0, 2.0, 3.0, 4.0], 0);
+
+        let geometry = |beam_offset, beam_count, crease_offset| NodeGeometry {
+            crease: NodeCreasePointer {
+                offset: crease_offset,
+                count: creases,
+            },
+            beam: NodeBeamPointer {
+                offset: beam_offset,
+                count: beam_count,
+            },
+            face: NodeFacePointer::default(),
+        };
+        loader.copy_node_geometry(
+            &[
+                geometry(0, 1, 0),
+                geometry(1, 2, creases),
+                geometry(3, 2, creases * 2),
+                geometry(5, 1, creases * 3),
+            ],
+            0,
+        );
+
+        let beam = |node_index, neighbour_index, k| NodeBeamSpec {
+            node_index,
+            k,
+            d: 0.0,
+            length: 1.0,
+            neighbour_index,
+        };
+        loader.copy_node_beam(
+            &[
+                beam(0, 1, 10.0),
+                beam(1, 0, 10.0),
+                beam(1, 2, 20.0),
+                beam(2, 1, 20.0),
+                beam(2, 3, 30.0),
+                beam(3, 2, 30.0),
+            ],
+            0,
+        );
+
+        if creases > 0 {
+            loader.copy_crease_geometry(
+                &[CreaseGeometry {
+                    face_indices: [0, 0],
+                    complementary_node_indices: [0, 3],
+                    adjacent_node_indices: [1, 2],
+                }],
+                0,
+            );
+            loader.copy_crease_stiffness(&[5.0], 0);
+            let node_crease = |node_number| NodeCreaseSpec {
+                crease_index: 0,
+                node_number,
+            };
+            loader.copy_node_crease(
+                &[
+                    node_crease(0),
+                    node_crease(2),
+                    node_crease(3),
+                    node_crease(1),
+                ],
+                0,
+            );
+        }
+    }
+
+    /// The damping of every beam, then of every crease
+    fn damping<const L: usize>(runner: &crate::Runner<'_, L>) -> (Vec<f32>, Vec<f32>)
+    where
+        LaneCount<L>: SupportedLaneCount,
+    {
+        let lanes = |chunks: &[core::simd::Simd<f32, L>]| {
+            chunks
+                .iter()
+                .flat_map(|chunk| chunk.to_array())
+                .collect::<Vec<_>>()
+        };
+        (
+            lanes(&runner.state.node_beam_d.data),
+            lanes(&runner.state.crease_d.data),
+        )
+    }
+
+    /// Updating the damping of a single node or crease gives the same damping as updating every one
+    fn test_update_element_damping<const L: usize>()
+    where
+        LaneCount<L>: SupportedLaneCount,
+        simba::simd::Simd<core::simd::Simd<f32, L>>: simba::simd::SimdRealField,
+    {
+        const DAMPING_PERCENTAGE: f32 = 0.45;
+        let mut runner = OwnedRunner::<L, alloc::alloc::Global>::with_size(&chain_size(1));
+        load_chain(&mut runner, 1);
+        runner.update_damping(DAMPING_PERCENTAGE);
+        let (initial_beam_d, initial_crease_d) = damping(&runner);
+
+        // Node 2 becomes the lightest of the beams 1-2 & 2-3 and of the crease, but not of the beam 0-1
+        runner.load().copy_node_mass(&[0.5], 2);
+        runner.update_node_damping(2, DAMPING_PERCENTAGE);
+        let (beam_d, crease_d) = damping(&runner);
+        assert_eq!(beam_d[..2], initial_beam_d[..2]);
+        assert!(beam_d[2..6]
+            .iter()
+            .zip(&initial_beam_d[2..6])
+            .all(|(d, initial_d)| d < initial_d));
+        assert!(crease_d[0] < initial_crease_d[0]);
+
+        runner.update_damping(DAMPING_PERCENTAGE);
+        assert_eq!(damping(&runner), (beam_d, crease_d));
+
+        runner.load().copy_crease_stiffness(&[8.0], 0);
+        runner.update_crease_damping(0, DAMPING_PERCENTAGE);
+        let updated = damping(&runner);
+        assert_eq!(
+            updated.1[0],
+            DAMPING_PERCENTAGE * 2.0 * num_traits::Float::sqrt(8.0f32 * 0.5)
+        );
+
+        runner.update_damping(DAMPING_PERCENTAGE);
+        assert_eq!(damping(&runner), updated);
+    }
+
+    #[test]
+    pub fn test_update_element_damping_l4() {
+        test_update_element_damping::<4>();
+    }
+
+    #[test]
+    pub fn test_update_element_damping_l8() {
+        test_update_element_damping::<8>();
+    }
+
+    #[test]
+    pub fn test_update_element_damping_l16() {
+        test_update_element_damping::<16>();
+    }
+
+    /// The parameters written are dirty until the next step
+    fn test_dirty_parameters<const L: usize>()
+    where
+        LaneCount<L>: SupportedLaneCount,
+        simba::simd::Simd<core::simd::Simd<f32, L>>: simba::simd::SimdRealField,
+    {
+        let mut runner = OwnedRunner::<L, alloc::alloc::Global>::with_size(&chain_size(0));
+        load_chain(&mut runner, 0);
+        let dirty = runner.dirty_parameters();
+        assert!(dirty.node_positions && dirty.node_mass && dirty.node_beam_k);
+
+        runner.step().unwrap();
+        assert_eq!(runner.dirty_parameters(), DirtyParameters::default());
+        assert!(!runner.dirty_parameters().any());
+
+        runner.load().copy_node_mass(&[0.5], 2);
+        runner.update_node_damping(2, 0.45);
+        assert_eq!(
+            runner.dirty_parameters(),
+            DirtyParameters {
+                node_mass: true,
+                node_beam_d: true,
+                crease_d: true,
+                ..Default::default()
+            }
+        );
+
+        runner.step().unwrap();
+        assert!(!runner.dirty_parameters().any());
+    }
+
+    #[test]
+    pub fn test_dirty_parameters_l4() {
+        test_dirty_parameters::<4>();
+    }
+
+    #[test]
+    pub fn test_dirty_parameters_l8() {
+        test_dirty_parameters::<8>();
+    }
+
+    #[test]
+    pub fn test_dirty_parameters_l16() {
+        test_dirty_parameters::<16>();
+    }
+}
@@ -0,0 +1,323 @@
+//! The potential energies of the bar-and-hinge model, whose gradients are the forces of Origami Simulator:
+//! - each beam is an axial spring, of energy `½·k·(l - l₀)²`
+//! - each crease is an angular spring, of energy `½·k·(θ - θ₀)²`
+//! - each face pulls its angles back to their nominal values, with an energy `½·k·Σ(α - α₀)²`
+//!
+//! They are written once, generic over the [`Scalar`], so that their gradients and Hessians
+//! are obtained exactly by evaluating them with hyper-dual numbers (see [`derivatives`]).
+
+use crate::scalar::{HyperDual, Scalar};
+
+pub type Vec3<T> = [T; 3];
+
+#[inline]
+fn sub<T: Scalar>(lhs: Vec3<T>, rhs: Vec3<T>) -> Vec3<T> {
+    [lhs[0] - rhs[0], lhs[1] - rhs[1], lhs[2] - rhs[2]]
+}
+
+#[inline]
+fn dot<T: Scalar>(lhs: Vec3<T>, rhs: Vec3<T>) -> T {
+    lhs[0] * rhs[0] + lhs[1] * rhs[1] + lhs[2] * rhs[2]
+}
+
+#[inline]
+fn cross<T: Scalar>(lhs: Vec3<T>, rhs: Vec3<T>) -> Vec3<T> {
+    [
+        lhs[1] * rhs[2] - lhs[2] * rhs[1],
+        lhs[2] * rhs[0] - lhs[0] * rhs[2],
+        lhs[0] * rhs[1] - lhs[1] * rhs[0],
+    ]
+}
+
+#[inline]
+fn norm<T: Scalar>(v: Vec3<T>) -> T {
+    dot(v, v).sqrt()
+}
+
+/// The angle between two vectors, well-conditioned even when they are (almost) aligned
+#[inline]
+fn angle<T: Scalar>(lhs: Vec3<T>, rhs: Vec3<T>) -> T {
+    norm(cross(lhs, rhs)).atan2(dot(lhs, rhs))
+}
+
+/// A beam between two nodes
+#[derive(Debug, Clone, Copy)]
+pub struct Beam {
+    pub nodes: [u32; 2],
+    pub k: f64,
+    pub length: f64,
+}
+
+impl Beam {
+    pub fn energy<T: Scalar>(&self, [a, b]: [Vec3<T>; 2]) -> T {
+        let elongation = norm(sub(b, a)) - T::constant(self.length);
+        T::constant(0.5 * self.k) * elongation * elongation
+    }
+
+    /// How much the beam is stretched or compressed, relative to its length
+    pub fn strain(&self, [a, b]: [Vec3<f64>; 2]) -> f64 {
+        if self.length != 0.0 {
+            (norm(sub(b, a)) / self.length - 1.0).abs()
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A crease, between the faces `a` and `b`.
+/// Its nodes are, in order, the complementary nodes of `a` and `b` and the two nodes of the crease.
+#[derive(Debug, Clone, Copy)]
+pub struct Crease {
+    pub nodes: [u32; 4],
+    /// The vertices of each face, as positions in `nodes`, in the order the face gives them
+    pub faces: [[u8; 3]; 2],
+    pub k: f64,
+    /// The fold angle the crease is pulled towards
+    pub rest_fold_angle: f64,
+    /// A fold angle close to the current one, which the computed angle is kept continuous with
+    pub reference_fold_angle: f64,
+}
+
+impl Crease {
+    /// The fold angle, as computed by Origami Simulator, or `None` if either face is degenerate
+    pub fn fold_angle<T: Scalar>(&self, positions: [Vec3<T>; 4]) -> Option<T> {
+        let [normal_a, normal_b] = self.faces.map(|[a, b, c]| {
+            let [a, b, c] = [a, b, c].map(|vertex| positions[usize::from(vertex)]);
+            let normal = cross(sub(b, a), sub(c, a));
+            let length = norm(normal);
+            (length.value() != 0.0).then(|| normal.map(|component| component / length))
+        });
+        let (normal_a, normal_b) = (normal_a?, normal_b?);
+
+        let crease_vector = sub(positions[3], positions[2]);
+        let crease_length = norm(crease_vector);
+        if crease_length.value() == 0.0 {
+            return None;
+        }
+        let crease_vector = crease_vector.map(|component| component / crease_length);
+
+        let x = dot(normal_a, normal_b);
+        let y = dot(cross(normal_a, crease_vector), normal_b);
+        let fold_angle = y.atan2(x);
+
+        // Shifted by whole turns to be as close as possible to the reference
+        let turns =
+            libm::round((self.reference_fold_angle - fold_angle.value()) / core::f64::consts::TAU);
+        Some(fold_angle + T::constant(turns * core::f64::consts::TAU))
+    }
+
+    pub fn energy<T: Scalar>(&self, positions: [Vec3<T>; 4]) -> T {
+        self.fold_angle(positions)
+            .map_or(T::constant(0.0), |fold_angle| {
+                let difference = fold_angle - T::constant(self.rest_fold_angle);
+                T::constant(0.5 * self.k) * difference * difference
+            })
+    }
+}
+
+/// A triangular face
+#[derive(Debug, Clone, Copy)]
+pub struct Face {
+    pub nodes: [u32; 3],
+    pub nominal_angles: [f64; 3],
+    pub k: f64,
+}
+
+impl Face {
+    pub fn energy<T: Scalar>(&self, [a, b, c]: [Vec3<T>; 3]) -> T {
+        let angles = [
+            angle(sub(b, a), sub(c, a)),
+            angle(sub(a, b), sub(c, b)),
+            angle(sub(a, c), sub(b, c)),
+        ];
+
+        let deviation = angles.into_iter().zip(self.nominal_angles).fold(
+            T::constant(0.0),
+            |sum, (angle, nominal)| {
+                let difference = angle - T::constant(nominal);
+                sum + difference * difference
+            },
+        );
+        T::constant(0.5 * self.k) * deviation
+    }
+}
+
+/// The value, gradient and Hessian of an energy of `N` nodes, at the given positions.
+/// The Hessian is given by blocks of 3×3, one for each pair of nodes.
+#[allow(clippy::type_complexity)]
+pub fn derivatives<const N: usize>(
+    positions: [Vec3<f64>; N],
+    energy: impl Fn([Vec3<HyperDual>; N]) -> HyperDual,
+) -> (f64, [Vec3<f64>; N], [[[[f64; 3]; 3]; N]; N]) {
+    let mut value = 0.0;
+    let mut gradient = [[0.0; 3]; N];
+    let mut hessian = [[[[0.0; 3]; 3]; N]; N];
+
+    // Each second derivative is computed by seeding the two degrees of freedom it is taken along
+    for first in 0..(3 * N) {
+        for second in first..(3 * N) {
+            let seeded = core::array::from_fn(|node| {
+                core::array::from_fn(|axis| {
+                    let degree = 3 * node + axis;
+                    HyperDual::variable(
+                        positions[node][axis],
+                        f64::from(u8::from(degree == first)),
+                        f64::from(u8::from(degree == second)),
+                    )
+                })
+            });
+            let result = energy(seeded);
+
+            let (first_node, first_axis) = (first / 3, first % 3);
+            let (second_node, second_axis) = (second / 3, second % 3);
+            hessian[first_node][second_node][first_axis][second_axis] = result.e12;
+            hessian[second_node][first_node][second_axis][first_axis] = result.e12;
+            if first == second {
+                value = result.re;
+                gradient[first_node][first_axis] = result.e1;
+            }
+        }
+    }
+
+    (value, gradient, hessian)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Compares the derivatives with finite differences of the energy
+    fn check_derivatives<const N: usize>(
+        positions: [Vec3<f64>; N],
+        energy_f64: impl Fn([Vec3<f64>; N]) -> f64,
+        energy: impl Fn([Vec3<HyperDual>; N]) -> HyperDual,
+    ) {
+        const STEP: f64 = 1e-6;
+        let (value, gradient, hessian) = derivatives(positions, &energy);
+        assert!((value - energy_f64(positions)).abs() < 1e-12);
+
+        let moved = |node: usize, axis: usize, by: f64| {
+            let mut positions = positions;
+            positions[node][axis] += by;
+            positions
+        };
+        for node in 0..N {
+            for axis in 0..3 {
+                let expected = (energy_f64(moved(node, axis, STEP))
+                    - energy_f64(moved(node, axis, -STEP)))
+                    / (2.0 * STEP);
+                let got = gradient[node][axis];
+                assert!(
+                    (expected - got).abs() < 1e-6,
+                    "gradient along ({node}, {axis}): expected {expected}, got {got}"
+                );
+
+                // Finite differences of the gradient, which is itself checked above
+                let gradient_at = |by: f64| derivatives(moved(node, axis, by), &energy).1;
+                let (after, before) = (gradient_at(STEP), gradient_at(-STEP));
+                for other_node in 0..N {
+                    for other_axis in 0..3 {
+                        let expected = (after[other_node][other_axis]
+                            - before[other_node][other_axis])
+                            / (2.0 * STEP);
+                        let got = hessian[other_node][node][other_axis][axis];
+                        assert!(
+                            (expected - got).abs() < 1e-5,
+                            "hessian along ({other_node}, {other_axis}), ({node}, {axis}): expected {expected}, got {got}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_beam_derivatives() {
+        let beam = Beam {
+            nodes: [0, 1],
+            k: 20.0,
+            length: 1.0,
+        };
+        check_derivatives(
+            [[0.1, 0.0, -0.2], [1.2, 0.3, 0.1]],
+            |positions| beam.energy(positions),
+            |positions| beam.energy(positions),
+        );
+    }
+
+    #[test]
+    fn test_crease_derivatives() {
+        let crease = Crease {
+            nodes: [0, 1, 2, 3],
+            faces: [[2, 3, 0], [3, 2, 1]],
+            k: 0.7,
+            rest_fold_angle: 1.0,
+            reference_fold_angle: 0.0,
+        };
+        let positions = [
+            [0.5, 1.0, 0.1],
+            [0.4, -1.0, 0.6],
+            [0.0, 0.0, 0.0],
+            [1.0, 0.1, 0.0],
+        ];
+        check_derivatives(
+            positions,
+            |positions| crease.energy(positions),
+            |positions| crease.energy(positions),
+        );
+    }
+
+    #[test]
+    fn test_face_derivatives() {
+        let face = Face {
+            nodes: [0, 1, 2],
+            nominal_angles: [
+                core::f64::consts::FRAC_PI_2,
+                core::f64::consts::FRAC_PI_4,
+                core::f64::consts::FRAC_PI_4,
+            ],
+            k: 1.0,
+        };
+        check_derivatives(
+            [[0.0, 0.0, 0.0], [1.1, 0.1, 0.0], [0.2, 0.9, 0.3]],
+            |positions| face.energy(positions),
+            |positions| face.energy(positions),
+        );
+    }
+
+    /// test_crease_forces checks that the crease's gradient gives the forces of Origami Simulator
+    #[test]
+    fn test_crease_forces() {
+        let crease = Crease {
+            nodes: [0, 1, 2, 3],
+            faces: [[2, 3, 0], [3, 2, 1]],
+            k: 1.0,
+            rest_fold_angle: 0.0,
+            reference_fold_angle: 0.0,
+        };
+        let positions = [
+            [0.3, 1.0, 0.2],
+            [0.6, -1.0, 0.4],
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+        ];
+        let (_, gradient, _) = derivatives(positions, |positions| crease.energy(positions));
+        let fold_angle = crease.fold_angle(positions).unwrap();
+
+        // With a rest angle of 0, the complementary node of face a is pushed along its normal by `-k·θ / height`
+        let [a, _, c, d] = positions;
+        let normal = cross(sub(d, c), sub(a, c));
+        let normal = normal.map(|component| component / norm(normal));
+        let height = norm(cross(sub(d, c), sub(a, c))) / norm(sub(d, c));
+        for axis in 0..3 {
+            let expected_force = -fold_angle * normal[axis] / height;
+            assert!((-gradient[0][axis] - expected_force).abs() < 1e-9);
+        }
+
+        // Only internal forces: they sum up to zero
+        for axis in 0..3 {
+            let sum: f64 = gradient.iter().map(|node| node[axis]).sum();
+            assert!(sum.abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use core::alloc::Allocator;
+
+use rtori_os_model::access::SliceReadAccess;
+
+pub struct Extractor<'backer, A: Allocator> {
+    inner: &'backer crate::model::State<A>,
+}
+
+impl<'backer, A: Allocator> Extractor<'backer, A> {
+    pub const fn new(inner: &'backer crate::model::State<A>) -> Self {
+        Self { inner }
+    }
+
+    /// The force the supports apply to each fixed node, zero for the other nodes
+    pub fn node_reaction(&self) -> &'backer [rtori_os_model::Vector3F] {
+        &self.inner.node_reaction
+    }
+
+    /// The number of modes computed (see [`crate::Solver::compute_modes`])
+    pub fn mode_count(&self) -> usize {
+        self.inner.mode_eigenvalues.len()
+    }
+
+    /// The eigenvalue of a mode (from the lowest): the square of its angular frequency
+    pub fn mode_eigenvalue(&self, mode: usize) -> Option<f64> {
+        self.inner.mode_eigenvalues.get(mode).copied()
+    }
+
+    /// The frequency of a mode (from the lowest), in cycles per unit of time
+    pub fn mode_frequency(&self, mode: usize) -> Option<f64> {
+        self.mode_eigenvalue(mode)
+            .map(|eigenvalue| libm::sqrt(eigenvalue.max(0.0)) / core::f64::consts::TAU)
+    }
+
+    /// The displacement of each node in a mode (from the lowest),
+    /// normalized so that `Σ m·|φ|²` over the nodes is 1
+    pub fn node_mode_shape(&self, mode: usize) -> Option<&'backer [rtori_os_model::Vector3F]> {
+        let node_count = self.inner.node_position_offset.len();
+        self.inner
+            .mode_shapes
+            .get(mode * node_count..(mode + 1) * node_count)
+    }
+}
+
+impl<'extractor, 'backer, A: Allocator> rtori_os_model::Extractor<'extractor>
+    for Extractor<'backer, A>
+where
+    'backer: 'extractor,
+{
+    fn count_nodes(&self) -> usize {
+        self.inner.node_position_offset.len()
+    }
+
+    type NodePositionAccess<'a>
+        = SliceReadAccess<'a, rtori_os_model::Vector3F>
+    where
+        Self: 'a,
+        'extractor: 'a;
+
+    /// As with the dynamic solvers, these are the offsets from the nominal positions
+    fn access_node_position<'call, 'output>(
+        &'call self,
+    ) -> Option<Self::NodePositionAccess<'output>>
+    where
+        'call: 'output,
+        'extractor: 'output,
+    {
+        Some(SliceReadAccess(&self.inner.node_position_offset))
+    }
+
+    type NodeVelocityAccess<'a>
+        = SliceReadAccess<'a, rtori_os_model::Vector3F>
+    where
+        Self: 'a,
+        'extractor: 'a;
+
+    /// The equilibrium has no velocity
+    fn access_node_velocity<'call, 'output>(
+        &'call self,
+    ) -> Option<Self::NodeVelocityAccess<'output>>
+    where
+        'call: 'output,
+        'extractor: 'output,
+    {
+        None
+    }
+
+    type NodeErrorAccess<'a>
+        = SliceReadAccess<'a, f32>
+    where
+        Self: 'a,
+        'extractor: 'a;
+
+    fn access_node_error<'call, 'output>(&'call self) -> Option<Self::NodeErrorAccess<'output>>
+    where
+        'call: 'output,
+        'extractor: 'output,
+    {
+        Some(SliceReadAccess(&self.inner.node_error))
+    }
+}
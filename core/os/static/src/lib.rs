@@ -0,0 +1,28 @@
+//! A static solver of the Origami Simulator model
+//!
+//! Rather than relaxing the bar-and-hinge model over time, as the dynamic solvers do, it directly
+//! searches for its equilibrium with Newton-Raphson iterations, on the stiffness matrix of the beams,
+//! creases and faces. The fold percentage is raised in increments, each starting from the equilibrium of the previous one.
+//! Along with the positions, it gives the reaction forces at the fixed nodes,
+//! and the vibration modes around the equilibrium can then be computed.
+#![no_std]
+#![feature(allocator_api)]
+
+extern crate alloc;
+
+mod energy;
+mod model;
+mod scalar;
+mod sparse;
+
+mod solver;
+pub use solver::*;
+
+mod modal;
+pub use modal::{ModalConfig, ModalError};
+
+mod extractor;
+pub use extractor::Extractor;
+
+mod loader;
+pub use loader::Loader;
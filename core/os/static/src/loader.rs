@@ -0,0 +1,272 @@
+use core::alloc::Allocator;
+
+use rtori_os_model::access::{FieldWriteAccess, SliceWriteAccess};
+use rtori_os_model::ModelSize;
+
+pub struct Loader<'loader, A: Allocator> {
+    inner: &'loader mut crate::model::State<A>,
+    size: ModelSize,
+}
+
+impl<'loader, A: Allocator> Loader<'loader, A> {
+    pub fn new(inner: &'loader mut crate::model::State<A>) -> Self {
+        let size = inner.size();
+        Self { inner, size }
+    }
+}
+
+impl<'loader, A: Allocator> rtori_os_model::Loader<'loader> for Loader<'loader, A> {
+    fn model(&self) -> rtori_os_model::ModelSize {
+        self.size
+    }
+
+    type NodePositionAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::Vector3F>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_position<'call, 'output>(&'call mut self) -> Self::NodePositionAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.node_positions_unchanging)
+    }
+
+    type NodeExternalForcesAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::Vector3F>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_external_forces<'call, 'output>(
+        &'call mut self,
+    ) -> Self::NodeExternalForcesAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.node_external_forces)
+    }
+
+    type NodeConfigAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::NodeConfig>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_config<'call, 'output>(&'call mut self) -> Self::NodeConfigAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.node_config)
+    }
+
+    type NodeMassAccess<'a>
+        = FieldWriteAccess<'a, rtori_os_model::NodeConfig, f32>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_mass<'call, 'output>(&'call mut self) -> Self::NodeMassAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        FieldWriteAccess {
+            slice: &mut self.inner.node_config,
+            setter: |config, mass| config.mass = mass,
+        }
+    }
+
+    type NodeFixedAccess<'a>
+        = FieldWriteAccess<'a, rtori_os_model::NodeConfig, bool>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_fixed<'call, 'output>(&'call mut self) -> Self::NodeFixedAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        FieldWriteAccess {
+            slice: &mut self.inner.node_config,
+            setter: |config, fixed| config.fixed = fixed.into(),
+        }
+    }
+
+    type NodeGeometryAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::NodeGeometry>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_geometry<'call, 'output>(&'call mut self) -> Self::NodeGeometryAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.node_geometry)
+    }
+
+    type CreaseGeometryAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::CreaseGeometry>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_crease_geometry<'call, 'output>(
+        &'call mut self,
+    ) -> Self::CreaseGeometryAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.crease_geometry)
+    }
+
+    type CreaseParametersAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::CreaseParameters>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_crease_parameters<'call, 'output>(
+        &'call mut self,
+    ) -> Self::CreaseParametersAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.crease_parameters)
+    }
+
+    type CreaseStiffnessAccess<'a>
+        = FieldWriteAccess<'a, rtori_os_model::CreaseParameters, f32>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_crease_stiffness<'call, 'output>(
+        &'call mut self,
+    ) -> Self::CreaseStiffnessAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        FieldWriteAccess {
+            slice: &mut self.inner.crease_parameters,
+            setter: |parameters, k| parameters.k = k,
+        }
+    }
+
+    type CreaseTargetFoldAngleAccess<'a>
+        = FieldWriteAccess<'a, rtori_os_model::CreaseParameters, f32>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_crease_target_fold_angle<'call, 'output>(
+        &'call mut self,
+    ) -> Self::CreaseTargetFoldAngleAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        FieldWriteAccess {
+            slice: &mut self.inner.crease_parameters,
+            setter: |parameters, target_fold_angle| {
+                parameters.target_fold_angle = target_fold_angle
+            },
+        }
+    }
+
+    type FaceIndicesAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::Vector3U>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_face_indices<'call, 'output>(&'call mut self) -> Self::FaceIndicesAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.face_indices)
+    }
+
+    type FaceNominalAnglesAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::Vector3F>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_face_nominal_angles<'call, 'output>(
+        &'call mut self,
+    ) -> Self::FaceNominalAnglesAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.face_nominal_angles)
+    }
+
+    type FaceStiffnessAccess<'a>
+        = SliceWriteAccess<'a, f32>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_face_stiffness<'call, 'output>(&'call mut self) -> Self::FaceStiffnessAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.face_k)
+    }
+
+    type NodeCreaseAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::NodeCreaseSpec>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_crease<'call, 'output>(&'call mut self) -> Self::NodeCreaseAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.node_crease)
+    }
+
+    type NodeBeamAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::NodeBeamSpec>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_beam<'call, 'output>(&'call mut self) -> Self::NodeBeamAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.node_beam)
+    }
+
+    type NodeFaceAccess<'a>
+        = SliceWriteAccess<'a, rtori_os_model::NodeFaceSpec>
+    where
+        Self: 'a,
+        'loader: 'a;
+
+    fn access_node_face<'call, 'output>(&'call mut self) -> Self::NodeFaceAccess<'output>
+    where
+        'call: 'output,
+        'loader: 'output,
+    {
+        SliceWriteAccess(&mut self.inner.node_face)
+    }
+}
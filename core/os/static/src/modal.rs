@@ -0,0 +1,396 @@
+//! The vibration modes of the model around its current configuration.
+//!
+//! They are the eigenpairs of the stiffness matrix weighted by the masses of the nodes, `M^-½·K·M^-½`,
+//! the fixed nodes being left out: each eigenvalue is the square of the angular frequency of its mode.
+//!
+//! The lowest ones are found by subspace iteration: a few more vectors than the modes asked for are repeatedly
+//! multiplied by the inverse of the (slightly shifted) matrix, with the conjugate gradient method,
+//! and the modes are taken from the projection of the matrix on the subspace they span (Rayleigh-Ritz).
+
+use alloc::vec::Vec;
+use core::alloc::Allocator;
+
+use rtori_os_model::Vector3F;
+
+use crate::energy::Vec3;
+use crate::model::filled;
+use crate::sparse::{self, BlockMatrix, Termination, Workspace};
+
+/// The relative tolerance of the linear solves
+const LINEAR_TOLERANCE: f64 = 1e-12;
+/// The shift making the weighted stiffness matrix invertible despite the rigid-body modes,
+/// relative to the average of its diagonal
+const RELATIVE_SHIFT: f64 = 1e-4;
+
+/// Which modes are computed, and how
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModalConfig {
+    /// The number of modes computed, from the lowest
+    pub mode_count: u32,
+    /// The maximum number of iterations of the subspace
+    pub max_iterations: u32,
+    /// The residual of the modes, relative to the average stiffness of the model, under which they are found
+    pub tolerance: f64,
+}
+
+impl Default for ModalConfig {
+    fn default() -> Self {
+        Self {
+            mode_count: 6,
+            max_iterations: 100,
+            tolerance: 1e-6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModalError {
+    /// A free node has no mass, so that its modes would be infinitely fast
+    Massless { node_index: u32 },
+    /// The stiffness matrix isn't positive around the current configuration, which isn't a stable equilibrium
+    NotPositive,
+    /// The modes didn't settle within the iterations
+    NotConverged {
+        /// The largest residual of the modes, relative to the average stiffness of the model
+        residual: f64,
+    },
+}
+
+impl core::fmt::Display for ModalError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Massless { node_index } => write!(f, "node {node_index} is free but has no mass"),
+            Self::NotPositive => write!(
+                f,
+                "the stiffness matrix isn't positive, the configuration isn't a stable equilibrium"
+            ),
+            Self::NotConverged { residual } => {
+                write!(
+                    f,
+                    "the modes didn't converge (relative residual of {residual})"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for ModalError {}
+
+impl<A: Allocator + Clone> crate::Solver<A> {
+    /// Computes the lowest modes around the current configuration, which the extractor then gives
+    /// (see [`crate::Extractor::node_mode_shape`]). They are dropped by the next solve.
+    pub fn compute_modes(&mut self, config: &ModalConfig) -> Result<(), ModalError> {
+        self.state.mode_eigenvalues.clear();
+        self.state.mode_shapes.clear();
+
+        let node_count = self.displacements.len();
+        let filled_nodes = |count: usize| {
+            filled::<Vec3<f64>, _>((count * node_count) as u32, self.allocator.clone())
+        };
+
+        // The inverse of the square root of the mass of each node, zero for the fixed ones
+        let mut weights = filled::<f64, _>(node_count as u32, self.allocator.clone());
+        for (node_index, weight) in weights.iter_mut().enumerate() {
+            if self.is_free(node_index) {
+                let mass = f64::from(self.state.node_config[node_index].mass);
+                if mass <= 0.0 || mass.is_nan() {
+                    return Err(ModalError::Massless {
+                        node_index: node_index as u32,
+                    });
+                }
+                *weight = 1.0 / libm::sqrt(mass);
+            }
+        }
+        let free = |node: usize| weights[node] != 0.0;
+        let degrees_of_freedom = 3 * (0..node_count).filter(|node| free(*node)).count();
+        let mode_count = (config.mode_count as usize).min(degrees_of_freedom);
+        if mode_count == 0 {
+            return Ok(());
+        }
+        let subspace_size = (2 * mode_count).max(mode_count + 8).min(degrees_of_freedom);
+
+        // The weighted stiffness matrix, shifted
+        let elements = self.elements();
+        let mut matrix =
+            BlockMatrix::with_pattern_in(node_count, elements.node_pairs(), self.allocator.clone());
+        let mut gradient = filled_nodes(1);
+        self.assemble(
+            &elements,
+            &self.displacements,
+            &mut gradient,
+            Some(&mut matrix),
+        );
+        let mut trace = 0.0;
+        matrix.for_each_block_mut(|row, column, block| {
+            let weight = weights[row as usize] * weights[column as usize];
+            for (i, line) in block.iter_mut().enumerate() {
+                for entry in line.iter_mut() {
+                    *entry *= weight;
+                }
+                if row == column {
+                    trace += line[i];
+                }
+            }
+        });
+        let stiffness = trace / degrees_of_freedom as f64;
+        if stiffness <= 0.0 || stiffness.is_nan() {
+            return Err(ModalError::NotPositive);
+        }
+        let shift = RELATIVE_SHIFT * stiffness;
+        matrix.for_each_block_mut(|row, column, block| {
+            if row == column {
+                (0..3).for_each(|i| block[i][i] += shift);
+            }
+        });
+
+        let mut seed = 0x5EED;
+        let mut basis = filled_nodes(subspace_size);
+        for vector in basis.chunks_mut(node_count) {
+            randomize(vector, free, &mut seed);
+        }
+        orthonormalize(&mut basis, node_count, free, &mut seed);
+
+        let mut solved = filled_nodes(subspace_size);
+        let mut images = filled_nodes(subspace_size);
+        let mut projected = filled::<f64, _>(
+            (subspace_size * subspace_size) as u32,
+            self.allocator.clone(),
+        );
+        let mut rotation = projected.clone();
+        let mut order = Vec::with_capacity_in(subspace_size, self.allocator.clone());
+        let mut workspace = Workspace::with_size_in(node_count, self.allocator.clone());
+
+        let mut residual = f64::INFINITY;
+        for _ in 0..config.max_iterations {
+            for (vector, solved) in basis.chunks(node_count).zip(solved.chunks_mut(node_count)) {
+                let termination = sparse::solve_conjugate_gradient(
+                    &matrix,
+                    vector,
+                    free,
+                    solved,
+                    &mut workspace,
+                    LINEAR_TOLERANCE,
+                    10 * degrees_of_freedom,
+                );
+                if termination == Termination::NegativeCurvature {
+                    return Err(ModalError::NotPositive);
+                }
+            }
+            orthonormalize(&mut solved, node_count, free, &mut seed);
+
+            // The images by the unshifted matrix, and the projection of the matrix on the subspace
+            for (vector, image) in solved.chunks(node_count).zip(images.chunks_mut(node_count)) {
+                matrix.multiply(vector, image);
+                for (image, vector) in image.iter_mut().zip(vector) {
+                    (0..3).for_each(|i| image[i] -= shift * vector[i]);
+                }
+            }
+            for i in 0..subspace_size {
+                for j in i..subspace_size {
+                    let entry = 0.5
+                        * (sparse::dot(
+                            &solved[i * node_count..][..node_count],
+                            &images[j * node_count..][..node_count],
+                        ) + sparse::dot(
+                            &solved[j * node_count..][..node_count],
+                            &images[i * node_count..][..node_count],
+                        ));
+                    projected[i * subspace_size + j] = entry;
+                    projected[j * subspace_size + i] = entry;
+                }
+            }
+            symmetric_eigen(&mut projected, &mut rotation, subspace_size);
+            let eigenvalue = |k: usize| projected[k * subspace_size + k];
+            order.clear();
+            order.extend(0..subspace_size);
+            order.sort_unstable_by(|a, b| eigenvalue(*a).total_cmp(&eigenvalue(*b)));
+
+            // The approximations of the modes (Ritz vectors), and how far they are from being eigenvectors
+            residual = 0.0;
+            for (rank, (mode, vector)) in order.iter().zip(basis.chunks_mut(node_count)).enumerate()
+            {
+                let mut error = 0.0;
+                for node in 0..node_count {
+                    let mut combined = [0.0; 3];
+                    let mut image = [0.0; 3];
+                    for j in 0..subspace_size {
+                        let coefficient = rotation[j * subspace_size + mode];
+                        for i in 0..3 {
+                            combined[i] += coefficient * solved[j * node_count + node][i];
+                            image[i] += coefficient * images[j * node_count + node][i];
+                        }
+                    }
+                    vector[node] = combined;
+                    error += (0..3)
+                        .map(|i| {
+                            let difference = image[i] - eigenvalue(*mode) * combined[i];
+                            difference * difference
+                        })
+                        .sum::<f64>();
+                }
+                if rank < mode_count {
+                    residual = f64::max(residual, libm::sqrt(error) / stiffness);
+                }
+            }
+
+            if residual <= config.tolerance {
+                let state = &mut self.state;
+                state
+                    .mode_eigenvalues
+                    .extend(order[..mode_count].iter().map(|mode| eigenvalue(*mode)));
+                for vector in basis.chunks(node_count).take(mode_count) {
+                    state
+                        .mode_shapes
+                        .extend(vector.iter().zip(weights.iter()).map(|(vector, weight)| {
+                            Vector3F(vector.map(|component| (weight * component) as f32))
+                        }));
+                }
+                return Ok(());
+            }
+        }
+
+        Err(ModalError::NotConverged { residual })
+    }
+}
+
+/// Fills the vector with pseudo-random components, zero at the fixed nodes
+fn randomize(vector: &mut [Vec3<f64>], free: impl Fn(usize) -> bool, seed: &mut u64) {
+    for (node, value) in vector.iter_mut().enumerate() {
+        *value = [0; 3].map(|_| {
+            // A linear congruential generator is enough to start from vectors in every direction
+            *seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (*seed >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+        });
+        if !free(node) {
+            *value = [0.0; 3];
+        }
+    }
+}
+
+/// Makes the vectors (each of `node_count` nodes) orthonormal, with the modified Gram-Schmidt process.
+/// A vector lying in the span of the previous ones is replaced by a random one.
+fn orthonormalize(
+    vectors: &mut [Vec3<f64>],
+    node_count: usize,
+    free: impl Fn(usize) -> bool + Copy,
+    seed: &mut u64,
+) {
+    for index in 0..(vectors.len() / node_count) {
+        let (previous, rest) = vectors.split_at_mut(index * node_count);
+        let vector = &mut rest[..node_count];
+        loop {
+            let initial_norm = libm::sqrt(sparse::dot(vector, vector));
+            // Twice, as once isn't enough to stay orthogonal when the vectors are close
+            for _ in 0..2 {
+                for other in previous.chunks(node_count) {
+                    let projection = sparse::dot(vector, other);
+                    for (value, other) in vector.iter_mut().zip(other) {
+                        (0..3).for_each(|i| value[i] -= projection * other[i]);
+                    }
+                }
+            }
+
+            let norm = libm::sqrt(sparse::dot(vector, vector));
+            if norm > 1e-10 * initial_norm && norm > 0.0 {
+                vector
+                    .iter_mut()
+                    .for_each(|value| *value = value.map(|component| component / norm));
+                break;
+            }
+            randomize(vector, free, seed);
+        }
+    }
+}
+
+/// Diagonalizes the symmetric matrix of the given size (stored by rows) with the cyclic Jacobi method:
+/// the eigenvalues are left on its diagonal, and the eigenvectors are written in the columns of `vectors`
+fn symmetric_eigen(matrix: &mut [f64], vectors: &mut [f64], size: usize) {
+    const MAX_SWEEPS: usize = 64;
+
+    vectors.fill(0.0);
+    (0..size).for_each(|i| vectors[i * size + i] = 1.0);
+
+    for _ in 0..MAX_SWEEPS {
+        let squares = |off_diagonal: bool| {
+            (0..size)
+                .flat_map(|i| (0..size).map(move |j| (i, j)))
+                .filter(|(i, j)| !off_diagonal || i != j)
+                .map(|(i, j)| matrix[i * size + j] * matrix[i * size + j])
+                .sum::<f64>()
+        };
+        let off_diagonal = squares(true);
+        if off_diagonal <= f64::EPSILON * f64::EPSILON * squares(false) {
+            return;
+        }
+
+        for p in 0..size {
+            for q in (p + 1)..size {
+                let apq = matrix[p * size + q];
+                if apq == 0.0 {
+                    continue;
+                }
+
+                // The rotation zeroing the entry (p, q)
+                let theta = (matrix[q * size + q] - matrix[p * size + p]) / (2.0 * apq);
+                let t = theta.signum() / (theta.abs() + libm::sqrt(theta * theta + 1.0));
+                let c = 1.0 / libm::sqrt(t * t + 1.0);
+                let s = t * c;
+
+                for k in 0..size {
+                    let (akp, akq) = (matrix[k * size + p], matrix[k * size + q]);
+                    matrix[k * size + p] = c * akp - s * akq;
+                    matrix[k * size + q] = s * akp + c * akq;
+                }
+                for k in 0..size {
+                    let (apk, aqk) = (matrix[p * size + k], matrix[q * size + k]);
+                    matrix[p * size + k] = c * apk - s * aqk;
+                    matrix[q * size + k] = s * apk + c * aqk;
+                }
+                for k in 0..size {
+                    let (vkp, vkq) = (vectors[k * size + p], vectors[k * size + q]);
+                    vectors[k * size + p] = c * vkp - s * vkq;
+                    vectors[k * size + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// test_symmetric_eigen checks the eigenpairs found by the Jacobi method
+    #[test]
+    fn test_symmetric_eigen() {
+        const SIZE: usize = 4;
+        let original = [
+            4.0, 1.0, -2.0, 2.0, //
+            1.0, 2.0, 0.0, 1.0, //
+            -2.0, 0.0, 3.0, -2.0, //
+            2.0, 1.0, -2.0, -1.0,
+        ];
+        let mut matrix = original;
+        let mut vectors = [0.0; SIZE * SIZE];
+        symmetric_eigen(&mut matrix, &mut vectors, SIZE);
+
+        let trace: f64 = (0..SIZE).map(|i| original[i * SIZE + i]).sum();
+        let eigenvalue_sum: f64 = (0..SIZE).map(|i| matrix[i * SIZE + i]).sum();
+        assert!((trace - eigenvalue_sum).abs() < 1e-12);
+
+        for k in 0..SIZE {
+            let eigenvalue = matrix[k * SIZE + k];
+            for i in 0..SIZE {
+                let image: f64 = (0..SIZE)
+                    .map(|j| original[i * SIZE + j] * vectors[j * SIZE + k])
+                    .sum();
+                assert!(
+                    (image - eigenvalue * vectors[i * SIZE + k]).abs() < 1e-12,
+                    "eigenpair {k} isn't one"
+                );
+            }
+        }
+    }
+}
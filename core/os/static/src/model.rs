@@ -0,0 +1,88 @@
+use alloc::vec::Vec;
+use core::alloc::Allocator;
+
+use rtori_os_model::{
+    CreaseGeometry, CreaseParameters, ModelSize, NodeBeamSpec, NodeConfig, NodeCreaseSpec,
+    NodeFaceSpec, NodeGeometry, Vector3F, Vector3U,
+};
+
+/// The loaded model, stored as plain arrays of the model's types, and the results of the last solve
+#[derive(Debug, Clone)]
+pub struct State<A: Allocator> {
+    /* Per-Node: Geometry & Configs */
+    pub node_geometry: Vec<NodeGeometry, A>,
+    pub node_positions_unchanging: Vec<Vector3F, A>,
+    pub node_external_forces: Vec<Vector3F, A>,
+    pub node_config: Vec<NodeConfig, A>,
+
+    /* Per-Node: Results */
+    pub node_position_offset: Vec<Vector3F, A>,
+    /// The force the supports apply to the fixed nodes, zero for the others
+    pub node_reaction: Vec<Vector3F, A>,
+    pub node_error: Vec<f32, A>,
+
+    /* Modes, when computed around the current configuration */
+    /// The eigenvalue of each mode, from the lowest
+    pub mode_eigenvalues: Vec<f64, A>,
+    /// The displacement of every node in each mode, one mode after the other
+    pub mode_shapes: Vec<Vector3F, A>,
+
+    /* Per-Crease */
+    pub crease_geometry: Vec<CreaseGeometry, A>,
+    pub crease_parameters: Vec<CreaseParameters, A>,
+
+    /* Per-Face */
+    pub face_indices: Vec<Vector3U, A>,
+    pub face_nominal_angles: Vec<Vector3F, A>,
+    pub face_k: Vec<f32, A>,
+
+    /* Per-Node-Crease, Per-Node-Beam & Per-Node-Face */
+    pub node_crease: Vec<NodeCreaseSpec, A>,
+    pub node_beam: Vec<NodeBeamSpec, A>,
+    pub node_face: Vec<NodeFaceSpec, A>,
+}
+
+pub(crate) fn filled<T: Clone + Default, A: Allocator>(count: u32, allocator: A) -> Vec<T, A> {
+    let count = usize::try_from(count).unwrap();
+    let mut output = Vec::with_capacity_in(count, allocator);
+    output.resize(count, T::default());
+    output
+}
+
+impl<A: Allocator + Clone> State<A> {
+    pub fn with_size_in(size: &ModelSize, allocator: A) -> Self {
+        Self {
+            node_geometry: filled(size.nodes, allocator.clone()),
+            node_positions_unchanging: filled(size.nodes, allocator.clone()),
+            node_external_forces: filled(size.nodes, allocator.clone()),
+            node_config: filled(size.nodes, allocator.clone()),
+            node_position_offset: filled(size.nodes, allocator.clone()),
+            node_reaction: filled(size.nodes, allocator.clone()),
+            node_error: filled(size.nodes, allocator.clone()),
+            mode_eigenvalues: Vec::new_in(allocator.clone()),
+            mode_shapes: Vec::new_in(allocator.clone()),
+            crease_geometry: filled(size.creases, allocator.clone()),
+            crease_parameters: filled(size.creases, allocator.clone()),
+            face_indices: filled(size.faces, allocator.clone()),
+            face_nominal_angles: filled(size.faces, allocator.clone()),
+            face_k: filled(size.faces, allocator.clone()),
+            node_crease: filled(size.node_creases, allocator.clone()),
+            node_beam: filled(size.node_beams, allocator.clone()),
+            node_face: filled(size.node_faces, allocator),
+        }
+    }
+}
+
+impl<A: Allocator> State<A> {
+    pub fn size(&self) -> ModelSize {
+        let count = |len: usize| u32::try_from(len).unwrap();
+        ModelSize {
+            nodes: count(self.node_geometry.len()),
+            creases: count(self.crease_geometry.len()),
+            faces: count(self.face_indices.len()),
+            node_creases: count(self.node_crease.len()),
+            node_beams: count(self.node_beam.len()),
+            node_faces: count(self.node_face.len()),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! The scalars the energies are computed with: plain `f64`s for their value,
+//! or hyper-dual numbers to also get their first and second derivatives, exactly.
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+pub trait Scalar:
+    Copy
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    fn constant(value: f64) -> Self;
+    fn value(self) -> f64;
+    fn sqrt(self) -> Self;
+    /// The angle of the point `(x, self)`, as with [`f64::atan2`]
+    fn atan2(self, x: Self) -> Self;
+}
+
+impl Scalar for f64 {
+    #[inline]
+    fn constant(value: f64) -> Self {
+        value
+    }
+
+    #[inline]
+    fn value(self) -> f64 {
+        self
+    }
+
+    #[inline]
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+
+    #[inline]
+    fn atan2(self, x: Self) -> Self {
+        libm::atan2(self, x)
+    }
+}
+
+/// `re + e1·ε1 + e2·ε2 + e12·ε1ε2`, with `ε1² = ε2² = 0`.
+///
+/// Seeding `e1` and `e2` of the variables with two directions, a function of them gives
+/// its derivatives along each direction in `e1` and `e2`, and its second derivative along both in `e12`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HyperDual {
+    pub re: f64,
+    pub e1: f64,
+    pub e2: f64,
+    pub e12: f64,
+}
+
+impl HyperDual {
+    /// A variable with the given value, and derivatives `e1` and `e2` along the seeded directions
+    pub const fn variable(re: f64, e1: f64, e2: f64) -> Self {
+        Self {
+            re,
+            e1,
+            e2,
+            e12: 0.0,
+        }
+    }
+
+    /// Applies a function given its value, first and second derivatives at `self.re`
+    #[inline]
+    fn chain(self, value: f64, first: f64, second: f64) -> Self {
+        Self {
+            re: value,
+            e1: first * self.e1,
+            e2: first * self.e2,
+            e12: first * self.e12 + second * self.e1 * self.e2,
+        }
+    }
+}
+
+impl Add for HyperDual {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            re: self.re + rhs.re,
+            e1: self.e1 + rhs.e1,
+            e2: self.e2 + rhs.e2,
+            e12: self.e12 + rhs.e12,
+        }
+    }
+}
+
+impl Sub for HyperDual {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl Neg for HyperDual {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self {
+            re: -self.re,
+            e1: -self.e1,
+            e2: -self.e2,
+            e12: -self.e12,
+        }
+    }
+}
+
+impl Mul for HyperDual {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            re: self.re * rhs.re,
+            e1: self.e1 * rhs.re + self.re * rhs.e1,
+            e2: self.e2 * rhs.re + self.re * rhs.e2,
+            e12: self.e12 * rhs.re + self.e1 * rhs.e2 + self.e2 * rhs.e1 + self.re * rhs.e12,
+        }
+    }
+}
+
+impl Div for HyperDual {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        let inverse = 1.0 / rhs.re;
+        let reciprocal = rhs.chain(
+            inverse,
+            -inverse * inverse,
+            2.0 * inverse * inverse * inverse,
+        );
+        self * reciprocal
+    }
+}
+
+impl Scalar for HyperDual {
+    #[inline]
+    fn constant(value: f64) -> Self {
+        Self::variable(value, 0.0, 0.0)
+    }
+
+    #[inline]
+    fn value(self) -> f64 {
+        self.re
+    }
+
+    #[inline]
+    fn sqrt(self) -> Self {
+        let root = libm::sqrt(self.re);
+        self.chain(root, 0.5 / root, -0.25 / (root * self.re))
+    }
+
+    #[inline]
+    fn atan2(self, x: Self) -> Self {
+        let y = self;
+        let squared_radius = x.re * x.re + y.re * y.re;
+        let [dy, dx] = [x.re, -y.re].map(|partial| partial / squared_radius);
+        let squared = squared_radius * squared_radius;
+        let dyy = -2.0 * x.re * y.re / squared;
+        let dxy = (y.re * y.re - x.re * x.re) / squared;
+
+        Self {
+            re: libm::atan2(y.re, x.re),
+            e1: dy * y.e1 + dx * x.e1,
+            e2: dy * y.e2 + dx * x.e2,
+            e12: dy * y.e12
+                + dx * x.e12
+                + dyy * (y.e1 * y.e2 - x.e1 * x.e2)
+                + dxy * (x.e1 * y.e2 + y.e1 * x.e2),
+        }
+    }
+}
@@ -0,0 +1,620 @@
+use alloc::vec::Vec;
+use core::alloc::Allocator;
+
+use rtori_os_model::{ModelSize, Vector3F};
+
+use crate::energy::{self, Beam, Crease, Face, Vec3};
+use crate::model::{filled, State};
+use crate::sparse::{self, BlockMatrix, Workspace};
+
+/// How the equilibrium is searched for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolveConfig {
+    /// The largest change of the fold percentage solved for at once: larger changes are split in increments
+    pub max_increment: f32,
+    /// The maximum number of Newton iterations for each increment
+    pub max_iterations: u32,
+    /// The residual force (at any free node) under which the model is in equilibrium
+    pub force_tolerance: f64,
+}
+
+impl Default for SolveConfig {
+    fn default() -> Self {
+        Self {
+            max_increment: 0.05,
+            max_iterations: 50,
+            force_tolerance: 1e-6,
+        }
+    }
+}
+
+/// What it took to reach the equilibrium
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolveReport {
+    pub increments: u32,
+    /// The Newton iterations done, over all increments
+    pub iterations: u32,
+    /// The largest residual force at a free node
+    pub residual: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolveError {
+    /// The equilibrium of the increment wasn't found: the model is left where the iterations stopped,
+    /// at the fold percentage of the last increment solved
+    NotConverged {
+        fold_percentage: f32,
+        /// The largest residual force at a free node, when the iterations stopped
+        residual: f64,
+    },
+}
+
+impl core::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotConverged {
+                fold_percentage,
+                residual,
+            } => write!(
+                f,
+                "no equilibrium found at a fold percentage of {fold_percentage} (residual force of {residual})"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for SolveError {}
+
+/// The elements of the model, gathered from the state for a solve
+pub(crate) struct Elements<A: Allocator> {
+    beams: Vec<Beam, A>,
+    creases: Vec<Crease, A>,
+    faces: Vec<Face, A>,
+}
+
+/// Finds the static equilibrium of the bar-and-hinge model with Newton-Raphson iterations,
+/// from the stiffness matrix assembled from the beams, creases and faces
+#[derive(Debug, Clone)]
+pub struct Solver<A: Allocator = alloc::alloc::Global> {
+    pub(crate) state: State<A>,
+    /// The displacement of each node from its nominal position
+    pub(crate) displacements: Vec<Vec3<f64>, A>,
+    /// The fold angle of each crease, which those computed are kept continuous with
+    crease_fold_angles: Vec<f64, A>,
+    /// The fold percentage the displacements are the equilibrium of
+    fold_percentage: f32,
+    face_stiffness: f32,
+    gravity: Vector3F,
+    pub(crate) allocator: A,
+}
+
+impl Solver {
+    pub fn with_size(size: &ModelSize) -> Self {
+        Self::with_size_in(size, alloc::alloc::Global)
+    }
+}
+
+impl<A: Allocator + Clone> Solver<A> {
+    pub fn with_size_in(size: &ModelSize, allocator: A) -> Self {
+        Self {
+            state: State::with_size_in(size, allocator.clone()),
+            displacements: filled(size.nodes, allocator.clone()),
+            crease_fold_angles: filled(size.creases, allocator.clone()),
+            fold_percentage: 0.0,
+            face_stiffness: 1.0,
+            gravity: Vector3F::default(),
+            allocator,
+        }
+    }
+
+    pub fn load(&mut self) -> crate::Loader<'_, A> {
+        crate::Loader::new(&mut self.state)
+    }
+
+    pub fn extract(&self) -> crate::Extractor<'_, A> {
+        crate::Extractor::new(&self.state)
+    }
+
+    pub fn size(&self) -> ModelSize {
+        self.state.size()
+    }
+
+    /// The fold percentage of the current equilibrium
+    pub fn fold_percentage(&self) -> f32 {
+        self.fold_percentage
+    }
+
+    /// The stiffness pulling each face's angles back towards their nominal values, scaling that of each face.
+    /// It is taken into account by the next solve.
+    pub fn face_stiffness(&self) -> f32 {
+        self.face_stiffness
+    }
+
+    pub fn set_face_stiffness(&mut self, face_stiffness: f32) {
+        self.face_stiffness = face_stiffness;
+    }
+
+    /// The acceleration applied to every node, scaled by its mass.
+    /// It is taken into account by the next solve.
+    pub fn gravity(&self) -> Vector3F {
+        self.gravity
+    }
+
+    pub fn set_gravity(&mut self, gravity: Vector3F) {
+        self.gravity = gravity;
+    }
+
+    /// The force the supports apply to each fixed node, zero for the other nodes
+    pub fn node_reaction(&self) -> &[Vector3F] {
+        &self.state.node_reaction
+    }
+
+    /// Brings the nodes back to their nominal positions, at a fold percentage of 0
+    pub fn reset(&mut self) {
+        self.displacements.fill([0.0; 3]);
+        self.crease_fold_angles.fill(0.0);
+        self.fold_percentage = 0.0;
+        self.update_results(None);
+    }
+
+    /// Finds the equilibrium at the given fold percentage, starting from the current one.
+    /// The change of fold percentage is split in increments (see [`SolveConfig::max_increment`]),
+    /// each solved starting from the equilibrium of the previous one.
+    pub fn solve(
+        &mut self,
+        fold_percentage: f32,
+        config: &SolveConfig,
+    ) -> Result<SolveReport, SolveError> {
+        let mut elements = self.elements();
+        let mut matrix = BlockMatrix::with_pattern_in(
+            self.displacements.len(),
+            elements.node_pairs(),
+            self.allocator.clone(),
+        );
+        let mut workspace =
+            Workspace::with_size_in(self.displacements.len(), self.allocator.clone());
+
+        let start = self.fold_percentage;
+        let increments = if config.max_increment > 0.0 {
+            libm::ceilf((fold_percentage - start).abs() / config.max_increment).max(1.0) as u32
+        } else {
+            1
+        };
+
+        let mut report = SolveReport {
+            increments,
+            iterations: 0,
+            residual: 0.0,
+        };
+        for increment in 1..=increments {
+            let increment_fold_percentage =
+                start + (fold_percentage - start) * (increment as f32 / increments as f32);
+            for (crease, parameters) in elements
+                .creases
+                .iter_mut()
+                .zip(&self.state.crease_parameters)
+            {
+                crease.rest_fold_angle =
+                    f64::from(parameters.target_fold_angle * increment_fold_percentage);
+            }
+
+            let result = self.solve_increment(&mut elements, &mut matrix, &mut workspace, config);
+            for (fold_angle, crease) in self.crease_fold_angles.iter_mut().zip(&elements.creases) {
+                *fold_angle = crease.reference_fold_angle;
+            }
+
+            match result {
+                Ok((iterations, residual)) => {
+                    self.fold_percentage = increment_fold_percentage;
+                    report.iterations += iterations;
+                    report.residual = residual;
+                }
+                Err(residual) => {
+                    self.update_results(Some(&elements));
+                    return Err(SolveError::NotConverged {
+                        fold_percentage: increment_fold_percentage,
+                        residual,
+                    });
+                }
+            }
+        }
+
+        self.update_results(Some(&elements));
+        Ok(report)
+    }
+
+    /// Runs Newton iterations until the residual is under the tolerance,
+    /// giving the number of iterations and the residual, or the residual when the iterations fail
+    fn solve_increment(
+        &mut self,
+        elements: &mut Elements<A>,
+        matrix: &mut BlockMatrix<A>,
+        workspace: &mut Workspace<A>,
+        config: &SolveConfig,
+    ) -> Result<(u32, f64), f64> {
+        /// The fraction of the decrease predicted by the slope that a step must achieve
+        const SUFFICIENT_DECREASE: f64 = 1e-4;
+        /// The relative tolerance of the linear solves
+        const LINEAR_TOLERANCE: f64 = 1e-10;
+        /// The smallest fraction of the Newton step tried by the line search
+        const MIN_STEP: f64 = 1e-10;
+
+        let node_count = self.displacements.len();
+        let mut gradient = filled::<Vec3<f64>, _>(node_count as u32, self.allocator.clone());
+        let mut step = filled::<Vec3<f64>, _>(node_count as u32, self.allocator.clone());
+        let mut trial = filled::<Vec3<f64>, _>(node_count as u32, self.allocator.clone());
+        let max_linear_iterations = (3 * node_count).max(100);
+
+        for iteration in 0..config.max_iterations {
+            let energy = self.assemble(elements, &self.displacements, &mut gradient, Some(matrix));
+            let residual = self.residual(&gradient);
+            if residual <= config.force_tolerance {
+                return Ok((iteration, residual));
+            }
+
+            // The fixed nodes stay where they are
+            let rhs = &mut trial;
+            for (node, (rhs, gradient)) in rhs.iter_mut().zip(gradient.iter()).enumerate() {
+                *rhs = if self.is_free(node) {
+                    gradient.map(|component| -component)
+                } else {
+                    [0.0; 3]
+                };
+            }
+            // However it stopped, what it found is checked to go down the energy below
+            sparse::solve_conjugate_gradient(
+                matrix,
+                rhs,
+                |node| self.is_free(node),
+                &mut step,
+                workspace,
+                LINEAR_TOLERANCE,
+                max_linear_iterations,
+            );
+            let mut slope = sparse::dot(&gradient, &step);
+            if slope >= 0.0 || slope.is_nan() {
+                // Not a descent direction: go down the gradient instead
+                for (step, rhs) in step.iter_mut().zip(rhs.iter()) {
+                    *step = *rhs;
+                }
+                slope = sparse::dot(&gradient, &step);
+            }
+
+            // Backtracks until the energy decreases enough, or as much as it can be measured
+            let mut fraction = 1.0;
+            loop {
+                for ((trial, displacement), step) in
+                    trial.iter_mut().zip(&self.displacements).zip(step.iter())
+                {
+                    *trial = [0, 1, 2].map(|i| displacement[i] + fraction * step[i]);
+                }
+                let trial_energy = self.energy(elements, &trial);
+                let roundoff = 1e-12 * energy.abs().max(1.0);
+                if trial_energy <= energy + SUFFICIENT_DECREASE * fraction * slope
+                    || (trial_energy - energy).abs() <= roundoff
+                {
+                    break;
+                }
+
+                fraction *= 0.5;
+                if fraction < MIN_STEP {
+                    return Err(residual);
+                }
+            }
+            self.displacements.copy_from_slice(&trial);
+
+            // Keeps the fold angles continuous
+            for crease in elements.creases.iter_mut() {
+                let positions = crease
+                    .nodes
+                    .map(|node| self.position(&self.displacements, node));
+                if let Some(fold_angle) = crease.fold_angle(positions) {
+                    crease.reference_fold_angle = fold_angle;
+                }
+            }
+        }
+
+        self.assemble(elements, &self.displacements, &mut gradient, None);
+        let residual = self.residual(&gradient);
+        if residual <= config.force_tolerance {
+            Ok((config.max_iterations, residual))
+        } else {
+            Err(residual)
+        }
+    }
+
+    pub(crate) fn is_free(&self, node: usize) -> bool {
+        self.state.node_config[node].fixed == 0
+    }
+
+    #[inline]
+    fn position(&self, displacements: &[Vec3<f64>], node: u32) -> Vec3<f64> {
+        let nominal = self.state.node_positions_unchanging[node as usize].0;
+        let displacement = displacements[node as usize];
+        [0, 1, 2].map(|i| f64::from(nominal[i]) + displacement[i])
+    }
+
+    /// The external force on each node: the static one and its weight
+    #[inline]
+    fn external_force(&self, node: usize) -> Vec3<f64> {
+        let force = self.state.node_external_forces[node].0;
+        let mass = self.state.node_config[node].mass;
+        [0, 1, 2].map(|i| f64::from(force[i]) + f64::from(mass * self.gravity.0[i]))
+    }
+
+    /// The largest residual force at a free node
+    fn residual(&self, gradient: &[Vec3<f64>]) -> f64 {
+        gradient
+            .iter()
+            .enumerate()
+            .filter(|(node, _)| self.is_free(*node))
+            .map(|(_, gradient)| libm::sqrt(gradient.iter().map(|g| g * g).sum()))
+            .fold(0.0, f64::max)
+    }
+
+    /// The total potential energy of the model, the external forces included
+    fn energy(&self, elements: &Elements<A>, displacements: &[Vec3<f64>]) -> f64 {
+        let position = |node| self.position(displacements, node);
+        let internal = elements
+            .beams
+            .iter()
+            .map(|beam| beam.energy(beam.nodes.map(position)))
+            .chain(
+                elements
+                    .creases
+                    .iter()
+                    .map(|crease| crease.energy(crease.nodes.map(position))),
+            )
+            .chain(
+                elements
+                    .faces
+                    .iter()
+                    .map(|face| face.energy(face.nodes.map(position))),
+            )
+            .sum::<f64>();
+
+        let external = displacements
+            .iter()
+            .enumerate()
+            .map(|(node, displacement)| {
+                let force = self.external_force(node);
+                force[0] * displacement[0] + force[1] * displacement[1] + force[2] * displacement[2]
+            })
+            .sum::<f64>();
+
+        internal - external
+    }
+
+    /// Computes the gradient of the energy (the opposite of the forces on the nodes),
+    /// and the stiffness matrix (its Hessian) if given one, and returns the energy
+    pub(crate) fn assemble(
+        &self,
+        elements: &Elements<A>,
+        displacements: &[Vec3<f64>],
+        gradient: &mut [Vec3<f64>],
+        mut matrix: Option<&mut BlockMatrix<A>>,
+    ) -> f64 {
+        for (node, gradient) in gradient.iter_mut().enumerate() {
+            *gradient = self.external_force(node).map(|component| -component);
+        }
+        if let Some(matrix) = matrix.as_deref_mut() {
+            matrix.clear();
+        }
+        let position = |node| self.position(displacements, node);
+
+        fn add<const N: usize>(
+            nodes: [u32; N],
+            derivatives: ([Vec3<f64>; N], [[sparse::Block; N]; N]),
+            gradient: &mut [Vec3<f64>],
+            matrix: Option<&mut BlockMatrix<impl Allocator>>,
+        ) {
+            let (element_gradient, hessian) = derivatives;
+            for (node, element_gradient) in nodes.iter().zip(element_gradient) {
+                for i in 0..3 {
+                    gradient[*node as usize][i] += element_gradient[i];
+                }
+            }
+            if let Some(matrix) = matrix {
+                for (row, blocks) in nodes.iter().zip(&hessian) {
+                    for (column, block) in nodes.iter().zip(blocks) {
+                        matrix.add(*row, *column, block);
+                    }
+                }
+            }
+        }
+
+        let mut energy = 0.0;
+        for beam in elements.beams.iter() {
+            let (value, element_gradient, hessian) =
+                energy::derivatives(beam.nodes.map(position), |positions| beam.energy(positions));
+            energy += value;
+            add(
+                beam.nodes,
+                (element_gradient, hessian),
+                gradient,
+                matrix.as_deref_mut(),
+            );
+        }
+        for crease in elements.creases.iter() {
+            let (value, element_gradient, hessian) =
+                energy::derivatives(crease.nodes.map(position), |positions| {
+                    crease.energy(positions)
+                });
+            energy += value;
+            add(
+                crease.nodes,
+                (element_gradient, hessian),
+                gradient,
+                matrix.as_deref_mut(),
+            );
+        }
+        for face in elements.faces.iter() {
+            let (value, element_gradient, hessian) =
+                energy::derivatives(face.nodes.map(position), |positions| face.energy(positions));
+            energy += value;
+            add(
+                face.nodes,
+                (element_gradient, hessian),
+                gradient,
+                matrix.as_deref_mut(),
+            );
+        }
+
+        energy
+            - displacements
+                .iter()
+                .enumerate()
+                .map(|(node, displacement)| {
+                    let force = self.external_force(node);
+                    force[0] * displacement[0]
+                        + force[1] * displacement[1]
+                        + force[2] * displacement[2]
+                })
+                .sum::<f64>()
+    }
+
+    /// Gathers the elements from the state
+    pub(crate) fn elements(&self) -> Elements<A> {
+        let state = &self.state;
+
+        let mut beams = Vec::new_in(self.allocator.clone());
+        // Each beam is given by both of its nodes
+        beams.extend(
+            state
+                .node_beam
+                .iter()
+                .filter(|spec| spec.node_index < spec.neighbour_index)
+                .map(|spec| Beam {
+                    nodes: [spec.node_index, spec.neighbour_index],
+                    k: f64::from(spec.k),
+                    length: f64::from(spec.length),
+                }),
+        );
+
+        let mut creases =
+            Vec::with_capacity_in(state.crease_geometry.len(), self.allocator.clone());
+        for ((geometry, parameters), fold_angle) in state
+            .crease_geometry
+            .iter()
+            .zip(&state.crease_parameters)
+            .zip(&self.crease_fold_angles)
+        {
+            let [complementary_a, complementary_b] = geometry.complementary_node_indices;
+            let [adjacent_a, adjacent_b] = geometry.adjacent_node_indices;
+            let nodes = [complementary_a, complementary_b, adjacent_a, adjacent_b];
+            let faces = geometry.face_indices.map(|face_index| {
+                state.face_indices[face_index as usize].0.map(|node| {
+                    nodes
+                        .iter()
+                        .position(|crease_node| *crease_node == node)
+                        .expect("the faces of a crease are made of its nodes")
+                        as u8
+                })
+            });
+
+            creases.push(Crease {
+                nodes,
+                faces,
+                k: f64::from(parameters.k),
+                rest_fold_angle: f64::from(parameters.target_fold_angle * self.fold_percentage),
+                reference_fold_angle: *fold_angle,
+            });
+        }
+
+        let mut faces = Vec::with_capacity_in(state.face_indices.len(), self.allocator.clone());
+        faces.extend(
+            state
+                .face_indices
+                .iter()
+                .zip(&state.face_nominal_angles)
+                .zip(&state.face_k)
+                .map(|((indices, nominal_angles), k)| Face {
+                    nodes: indices.0,
+                    nominal_angles: nominal_angles.0.map(f64::from),
+                    k: f64::from(self.face_stiffness * k),
+                }),
+        );
+
+        Elements {
+            beams,
+            creases,
+            faces,
+        }
+    }
+
+    /// Updates the positions, reactions and errors given by the extractor,
+    /// dropping the modes computed around the previous configuration
+    fn update_results(&mut self, elements: Option<&Elements<A>>) {
+        self.state.mode_eigenvalues.clear();
+        self.state.mode_shapes.clear();
+        for (offset, displacement) in self
+            .state
+            .node_position_offset
+            .iter_mut()
+            .zip(&self.displacements)
+        {
+            *offset = Vector3F(displacement.map(|component| component as f32));
+        }
+
+        let Some(elements) = elements else {
+            self.state.node_reaction.fill(Vector3F::default());
+            self.state.node_error.fill(0.0);
+            return;
+        };
+
+        // The supports balance the forces on the fixed nodes
+        let mut gradient =
+            filled::<Vec3<f64>, _>(self.displacements.len() as u32, self.allocator.clone());
+        self.assemble(elements, &self.displacements, &mut gradient, None);
+        for ((reaction, gradient), config) in self
+            .state
+            .node_reaction
+            .iter_mut()
+            .zip(gradient.iter())
+            .zip(&self.state.node_config)
+        {
+            *reaction = if config.fixed == 0 {
+                Vector3F::default()
+            } else {
+                Vector3F(gradient.map(|component| component as f32))
+            };
+        }
+
+        // As with the dynamic solvers, the error is the average strain of the beams around the node
+        let mut strains = filled::<f64, _>(self.displacements.len() as u32, self.allocator.clone());
+        let mut counts = filled::<u32, _>(self.displacements.len() as u32, self.allocator.clone());
+        for beam in elements.beams.iter() {
+            let strain = beam.strain(
+                beam.nodes
+                    .map(|node| self.position(&self.displacements, node)),
+            );
+            for node in beam.nodes {
+                strains[node as usize] += strain;
+                counts[node as usize] += 1;
+            }
+        }
+        for ((error, strain), count) in self.state.node_error.iter_mut().zip(&strains).zip(&counts)
+        {
+            *error = if *count != 0 {
+                (strain / f64::from(*count)) as f32
+            } else {
+                0.0
+            };
+        }
+    }
+}
+
+impl<A: Allocator> Elements<A> {
+    /// The pairs of nodes sharing an element, which make the pattern of the stiffness matrix
+    pub(crate) fn node_pairs(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        fn pairs<const N: usize>(nodes: [u32; N]) -> impl Iterator<Item = (u32, u32)> {
+            (0..N).flat_map(move |i| ((i + 1)..N).map(move |j| (nodes[i], nodes[j])))
+        }
+
+        self.beams
+            .iter()
+            .flat_map(|beam| pairs(beam.nodes))
+            .chain(self.creases.iter().flat_map(|crease| pairs(crease.nodes)))
+            .chain(self.faces.iter().flat_map(|face| pairs(face.nodes)))
+    }
+}
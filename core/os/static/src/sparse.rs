@@ -0,0 +1,275 @@
+//! A symmetric sparse matrix made of 3×3 blocks, one per pair of nodes sharing an element,
+//! and the conjugate gradient method to solve linear systems with it
+
+use alloc::vec::Vec;
+use core::alloc::Allocator;
+
+pub type Block = [[f64; 3]; 3];
+
+/// Stored by rows of blocks (as with CSR), with the columns of each row sorted
+#[derive(Debug, Clone)]
+pub struct BlockMatrix<A: Allocator> {
+    row_offsets: Vec<u32, A>,
+    columns: Vec<u32, A>,
+    blocks: Vec<Block, A>,
+}
+
+impl<A> BlockMatrix<A>
+where
+    A: Allocator + Clone,
+{
+    /// Creates a zeroed matrix of `size` rows of blocks,
+    /// with a block for each of the given pairs of nodes (in both orders) and on the diagonal
+    pub fn with_pattern_in(
+        size: usize,
+        pairs: impl Iterator<Item = (u32, u32)>,
+        allocator: A,
+    ) -> Self {
+        let mut entries = Vec::new_in(allocator.clone());
+        entries.extend((0..size as u32).map(|node| (node, node)));
+        for (a, b) in pairs {
+            entries.push((a, b));
+            entries.push((b, a));
+        }
+        entries.sort_unstable();
+        entries.dedup();
+
+        let mut row_offsets = Vec::with_capacity_in(size + 1, allocator.clone());
+        let mut columns = Vec::with_capacity_in(entries.len(), allocator.clone());
+        row_offsets.push(0);
+        for row in 0..size as u32 {
+            let start = columns.len();
+            columns.extend(
+                entries[start..]
+                    .iter()
+                    .take_while(|(entry_row, _)| *entry_row == row)
+                    .map(|(_, column)| *column),
+            );
+            row_offsets.push(columns.len() as u32);
+        }
+
+        let mut blocks = Vec::with_capacity_in(columns.len(), allocator);
+        blocks.resize(columns.len(), [[0.0; 3]; 3]);
+
+        Self {
+            row_offsets,
+            columns,
+            blocks,
+        }
+    }
+}
+
+impl<A: Allocator> BlockMatrix<A> {
+    pub fn clear(&mut self) {
+        self.blocks.fill([[0.0; 3]; 3]);
+    }
+
+    fn row_range(&self, row: u32) -> core::ops::Range<usize> {
+        let row = row as usize;
+        (self.row_offsets[row] as usize)..(self.row_offsets[row + 1] as usize)
+    }
+
+    fn position(&self, row: u32, column: u32) -> usize {
+        let range = self.row_range(row);
+        let start = range.start;
+        start
+            + self.columns[range]
+                .binary_search(&column)
+                .expect("the block is outside of the pattern of the matrix")
+    }
+
+    /// Adds to the block at the given row and column, which must be part of the pattern
+    pub fn add(&mut self, row: u32, column: u32, block: &Block) {
+        let position = self.position(row, column);
+        let target = &mut self.blocks[position];
+        for i in 0..3 {
+            for j in 0..3 {
+                target[i][j] += block[i][j];
+            }
+        }
+    }
+
+    /// Calls `f` with every block of the pattern, along with its row and column
+    pub fn for_each_block_mut(&mut self, mut f: impl FnMut(u32, u32, &mut Block)) {
+        for row in 0..(self.row_offsets.len() - 1) as u32 {
+            let range = self.row_range(row);
+            for (column, block) in self.columns[range.clone()]
+                .iter()
+                .zip(&mut self.blocks[range])
+            {
+                f(row, *column, block);
+            }
+        }
+    }
+
+    pub fn diagonal(&self, row: u32) -> &Block {
+        &self.blocks[self.position(row, row)]
+    }
+
+    /// The blocks of a row, with their columns
+    pub fn row(&self, row: u32) -> impl Iterator<Item = (u32, &Block)> {
+        let range = self.row_range(row);
+        self.columns[range.clone()]
+            .iter()
+            .copied()
+            .zip(&self.blocks[range])
+    }
+
+    /// `output = self · input`
+    pub fn multiply(&self, input: &[[f64; 3]], output: &mut [[f64; 3]]) {
+        for (row, output) in output.iter_mut().enumerate() {
+            *output = [0.0; 3];
+            for (column, block) in self.row(row as u32) {
+                let input = input[column as usize];
+                for i in 0..3 {
+                    output[i] +=
+                        block[i][0] * input[0] + block[i][1] * input[1] + block[i][2] * input[2];
+                }
+            }
+        }
+    }
+}
+
+pub fn dot(lhs: &[[f64; 3]], rhs: &[[f64; 3]]) -> f64 {
+    lhs.iter()
+        .zip(rhs)
+        .map(|(lhs, rhs)| lhs[0] * rhs[0] + lhs[1] * rhs[1] + lhs[2] * rhs[2])
+        .sum()
+}
+
+/// How the conjugate gradient method stopped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Termination {
+    /// The residual fell below the tolerance
+    Converged,
+    /// The matrix isn't positive along the search direction: the solution is where the iterations stopped,
+    /// or the right-hand side if that happened on the first one
+    NegativeCurvature,
+    /// The maximum number of iterations was reached
+    MaxIterations,
+}
+
+/// The vectors the conjugate gradient method works with, kept to avoid allocating at every solve
+#[derive(Debug, Clone)]
+pub struct Workspace<A: Allocator> {
+    residual: Vec<[f64; 3], A>,
+    preconditioned: Vec<[f64; 3], A>,
+    direction: Vec<[f64; 3], A>,
+    product: Vec<[f64; 3], A>,
+    inverse_diagonal: Vec<[f64; 3], A>,
+}
+
+impl<A: Allocator + Clone> Workspace<A> {
+    pub fn with_size_in(size: usize, allocator: A) -> Self {
+        let zeroed = || {
+            let mut vector = Vec::with_capacity_in(size, allocator.clone());
+            vector.resize(size, [0.0; 3]);
+            vector
+        };
+        Self {
+            residual: zeroed(),
+            preconditioned: zeroed(),
+            direction: zeroed(),
+            product: zeroed(),
+            inverse_diagonal: zeroed(),
+        }
+    }
+}
+
+/// Solves `matrix · solution = rhs` for the nodes where `free` is true (the others being left at zero),
+/// with the conjugate gradient method preconditioned by the diagonal of the matrix.
+///
+/// It stops once the residual is under `tolerance` times the right-hand side,
+/// or when finding a direction along which the matrix isn't positive (it is then not a minimization anymore).
+pub fn solve_conjugate_gradient<A: Allocator>(
+    matrix: &BlockMatrix<impl Allocator>,
+    rhs: &[[f64; 3]],
+    free: impl Fn(usize) -> bool,
+    solution: &mut [[f64; 3]],
+    workspace: &mut Workspace<A>,
+    tolerance: f64,
+    max_iterations: usize,
+) -> Termination {
+    let Workspace {
+        residual,
+        preconditioned,
+        direction,
+        product,
+        inverse_diagonal,
+    } = workspace;
+    let mask = |vector: &mut [[f64; 3]]| {
+        vector
+            .iter_mut()
+            .enumerate()
+            .filter(|(node, _)| !free(*node))
+            .for_each(|(_, value)| *value = [0.0; 3]);
+    };
+
+    for (node, inverse) in inverse_diagonal.iter_mut().enumerate() {
+        let diagonal = matrix.diagonal(node as u32);
+        *inverse = [0, 1, 2].map(|i| {
+            if diagonal[i][i] > 0.0 {
+                1.0 / diagonal[i][i]
+            } else {
+                1.0
+            }
+        });
+    }
+    let precondition = |from: &[[f64; 3]], to: &mut [[f64; 3]]| {
+        for ((to, from), inverse) in to.iter_mut().zip(from).zip(inverse_diagonal.iter()) {
+            *to = [0, 1, 2].map(|i| from[i] * inverse[i]);
+        }
+    };
+
+    solution.fill([0.0; 3]);
+    residual.copy_from_slice(rhs);
+    mask(residual);
+    let threshold = tolerance * tolerance * dot(residual, residual);
+    precondition(residual, preconditioned);
+    direction.copy_from_slice(preconditioned);
+    let mut alignment = dot(residual, preconditioned);
+
+    for iteration in 0..max_iterations {
+        if dot(residual, residual) <= threshold {
+            return Termination::Converged;
+        }
+
+        matrix.multiply(direction, product);
+        mask(product);
+        let curvature = dot(direction, product);
+        if curvature <= 0.0 {
+            if iteration == 0 {
+                solution.copy_from_slice(residual);
+            }
+            return Termination::NegativeCurvature;
+        }
+
+        let step = alignment / curvature;
+        for ((solution, residual), (direction, product)) in solution
+            .iter_mut()
+            .zip(residual.iter_mut())
+            .zip(direction.iter().zip(product.iter()))
+        {
+            for i in 0..3 {
+                solution[i] += step * direction[i];
+                residual[i] -= step * product[i];
+            }
+        }
+
+        precondition(residual, preconditioned);
+        let next_alignment = dot(residual, preconditioned);
+        let ratio = next_alignment / alignment;
+        alignment = next_alignment;
+        for (direction, preconditioned) in direction.iter_mut().zip(preconditioned.iter()) {
+            for i in 0..3 {
+                direction[i] = preconditioned[i] + ratio * direction[i];
+            }
+        }
+    }
+
+    if dot(residual, residual) <= threshold {
+        Termination::Converged
+    } else {
+        Termination::MaxIterations
+    }
+}
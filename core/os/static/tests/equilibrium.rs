@@ -0,0 +1,166 @@
+#![feature(allocator_api)]
+
+extern crate alloc;
+use rtori_os_fold_importer::supplement::transform_in;
+use rtori_os_model::ExtractorDyn;
+
+fn parse_path(path: &std::path::Path) -> (&str, f32) {
+    let stem = path.file_stem().unwrap();
+    let (name, percentage_str) = stem.to_str().unwrap().rsplit_once('_').unwrap();
+    let percentage: f32 = percentage_str.parse().unwrap();
+
+    (name, percentage / 100.0)
+}
+
+/// Loads the frame with the solver's loader, as preprocessed for Origami Simulator
+macro_rules! load {
+    ($frame:expr, $with_size:expr) => {{
+        let allocator = alloc::alloc::Global;
+        let transformed =
+            transform_in($frame, allocator).expect("Transformation into importation input failed");
+        let transformed_input = transformed.with_fold($frame);
+        let preprocessed =
+            rtori_os_fold_importer::InputWithCreaseGeometry::process(&transformed_input, allocator)
+                .expect("preprocessing failed");
+
+        let mut solver = $with_size(&preprocessed.compute_size());
+        preprocessed
+            .load(&mut solver.load(), Default::default(), allocator)
+            .expect("import failed");
+        solver
+    }};
+}
+
+fn positions(extractor: &dyn ExtractorDyn<'_>) -> Vec<rtori_os_model::Vector3F> {
+    let mut positions = vec![rtori_os_model::Vector3F::default(); extractor.count_nodes()];
+    assert!(extractor.copy_node_position(&mut positions[..], 0));
+    positions
+}
+
+/// The positions, from the offsets extracted
+fn absolute(frame: &fold::FrameCore, offsets: &[rtori_os_model::Vector3F]) -> Vec<[f32; 3]> {
+    let nominal_positions = frame.vertices.coords.as_ref().unwrap();
+    assert_eq!(nominal_positions.len(), offsets.len());
+    offsets
+        .iter()
+        .zip(nominal_positions.iter())
+        .map(|(offset, coords)| [0, 1, 2].map(|i| coords.get(i).unwrap_or(&0.0) + offset.0[i]))
+        .collect()
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f32>()
+        .sqrt()
+}
+
+/// test_reference checks that the equilibrium found is where the dynamic relaxation of the reference solver settles
+#[test]
+fn test_reference() {
+    for name in [
+        "simple/simple",
+        "simpleVertex/simpleVertex",
+        "diagonal/diagonal-cp",
+    ] {
+        // Fully folded, the creases reach the 180° where their angle flips, and the equilibrium isn't unique
+        for percentage in [-75, 25, 50] {
+            let path =
+                std::path::PathBuf::from(format!("../../core/testdata/{name}_{percentage}.fold"));
+            let (name, fold_ratio) = parse_path(&path);
+            let file = std::fs::read_to_string(&path).unwrap();
+            let parsed_input = serde_json::from_str::<fold::File>(&file)
+                .expect("source deserialization (json/fold file) failed");
+            let frame = &parsed_input.key_frame;
+
+            let mut solver = load!(frame, rtori_os_static::Solver::with_size);
+            let report = solver
+                .solve(fold_ratio, &Default::default())
+                .unwrap_or_else(|error| panic!("{name}: {error}"));
+            assert_eq!(solver.fold_percentage(), fold_ratio);
+            assert!(report.residual <= 1e-6, "{name}: {report:?}");
+
+            let mut reference = load!(frame, rtori_os_reference::Runner::with_size);
+            reference.set_fold_percentage(fold_ratio);
+            for _ in 0..40_000 {
+                reference.step();
+            }
+
+            // Without fixed nodes, the model is free to move as a whole: only its shape is compared
+            let got = absolute(frame, &positions(&solver.extract()));
+            let expected = absolute(
+                frame,
+                &positions(&reference.extract(rtori_os_model::ExtractFlags::POSITION)),
+            );
+            // The relaxation hasn't entirely settled after these steps: the shapes still differ by about a hundredth
+            const TOLERANCE: f32 = 2e-2;
+            for i in 0..got.len() {
+                for j in (i + 1)..got.len() {
+                    let (got, expected) =
+                        (distance(got[i], got[j]), distance(expected[i], expected[j]));
+                    assert!(
+                        (got - expected).abs() < TOLERANCE,
+                        "{name} ({fold_ratio}): vertices {i} and {j} are {got} apart, but {expected} with the reference solver"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// test_reactions checks that the supports balance the external forces, and that the fold is driven in increments
+#[test]
+fn test_reactions() {
+    // A square folded along its diagonal, held by the nodes of the crease and pulled down at a corner
+    let frame = serde_json::from_str::<fold::FrameCore>(
+        r#"{
+            "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [0, 2]],
+            "edges_assignment": ["B", "B", "B", "B", "V"],
+            "edges_foldAngle": [0, 0, 0, 0, 90],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3]],
+            "rtori:os:vertices_fixed": [true, false, true, false],
+            "rtori:os:vertices_externalForce": [[0, 0, 0], [0, 0, -0.01], [0, 0, 0], [0, 0, 0]]
+        }"#,
+    )
+    .expect("source deserialization (json/fold file) failed");
+
+    let mut solver = load!(&frame, rtori_os_static::Solver::with_size);
+    let config = rtori_os_static::SolveConfig {
+        max_increment: 0.25,
+        ..Default::default()
+    };
+    let report = solver.solve(0.5, &config).unwrap();
+    assert_eq!(report.increments, 2);
+
+    let extractor = solver.extract();
+    let offsets = positions(&extractor);
+    // The fixed nodes don't move
+    for node in [0, 2] {
+        assert_eq!(offsets[node], rtori_os_model::Vector3F::default());
+    }
+    // The free corner is pushed up by the valley fold, the other one pulled down by the force
+    assert!(offsets[3].0[2] > 0.1, "{offsets:?}");
+
+    // The reactions balance the external force: their sum is opposite
+    let reactions = extractor.node_reaction();
+    assert_eq!(reactions[1], rtori_os_model::Vector3F::default());
+    assert_eq!(reactions[3], rtori_os_model::Vector3F::default());
+    for axis in 0..3 {
+        let sum = reactions[0].0[axis] + reactions[2].0[axis];
+        let expected = [0.0, 0.0, -0.01][axis];
+        assert!((sum + expected).abs() < 1e-5, "{reactions:?}");
+    }
+
+    // Folding back brings the square flat again, hanging from the crease: the free corners face each other across it
+    solver.solve(0.0, &config).unwrap();
+    let offsets = positions(&solver.extract());
+    let [_, corner_a, _, corner_b] = absolute(&frame, &offsets)[..] else {
+        unreachable!()
+    };
+    for axis in 0..3 {
+        let sum = corner_a[axis] + corner_b[axis];
+        assert!((sum - [1.0, 1.0, 0.0][axis]).abs() < 1e-3, "{offsets:?}");
+    }
+}
@@ -0,0 +1,152 @@
+#![feature(allocator_api)]
+
+extern crate alloc;
+use rtori_os_fold_importer::supplement::transform_in;
+use rtori_os_model::LoaderDyn as _;
+use rtori_os_static::{ModalConfig, ModalError, Solver};
+
+/// A flat square with a crease along its diagonal, held by the nodes of the crease if `held`
+fn square(held: bool, mass: f32) -> fold::FrameCore {
+    serde_json::from_str::<fold::FrameCore>(&format!(
+        r#"{{
+            "vertices_coords": [[0, 0], [1, 0], [1, 1], [0, 1]],
+            "edges_vertices": [[0, 1], [1, 2], [2, 3], [3, 0], [0, 2]],
+            "edges_assignment": ["B", "B", "B", "B", "V"],
+            "edges_foldAngle": [0, 0, 0, 0, 90],
+            "faces_vertices": [[0, 1, 2], [0, 2, 3]],
+            "rtori:os:vertices_fixed": [{held}, false, {held}, false],
+            "rtori:os:vertices_mass": [{mass}, {mass}, {mass}, {mass}]
+        }}"#
+    ))
+    .expect("source deserialization (json/fold file) failed")
+}
+
+fn load(frame: &fold::FrameCore) -> Solver {
+    let allocator = alloc::alloc::Global;
+    let transformed =
+        transform_in(frame, allocator).expect("Transformation into importation input failed");
+    let transformed_input = transformed.with_fold(frame);
+    let preprocessed =
+        rtori_os_fold_importer::InputWithCreaseGeometry::process(&transformed_input, allocator)
+            .expect("preprocessing failed");
+
+    let mut solver = Solver::with_size(&preprocessed.compute_size());
+    preprocessed
+        .load(&mut solver.load(), Default::default(), allocator)
+        .expect("import failed");
+    solver
+}
+
+/// The stiffness of the folding mode of the held square: the crease (of stiffness `0.7·√2` by default)
+/// turns by `1/h` for a unit displacement of either free corner, at a distance `h = 1/√2` from it
+fn folding_eigenvalue(mass: f64) -> f64 {
+    let crease_k = 0.7 * core::f64::consts::SQRT_2;
+    let distance = core::f64::consts::FRAC_1_SQRT_2;
+    2.0 * crease_k / (distance * distance) / mass
+}
+
+/// test_held_square checks the modes of the square held by its crease against their analytical values
+#[test]
+fn test_held_square() {
+    let mut solver = load(&square(true, 1.0));
+    solver.solve(0.0, &Default::default()).unwrap();
+    solver
+        .compute_modes(&ModalConfig {
+            mode_count: 3,
+            ..Default::default()
+        })
+        .unwrap();
+
+    let extractor = solver.extract();
+    assert_eq!(extractor.mode_count(), 3);
+    // Turning about the crease is free, folding it isn't, and the beams are much stiffer still
+    let eigenvalues: Vec<f64> = (0..3)
+        .map(|mode| extractor.mode_eigenvalue(mode).unwrap())
+        .collect();
+    assert!(eigenvalues[0].abs() < 1e-6, "{eigenvalues:?}");
+    assert!(
+        (eigenvalues[1] - folding_eigenvalue(1.0)).abs() < 1e-6,
+        "{eigenvalues:?}"
+    );
+    assert!(eigenvalues[2] > 10.0, "{eigenvalues:?}");
+    let frequency = extractor.mode_frequency(1).unwrap();
+    assert!((frequency - eigenvalues[1].sqrt() / core::f64::consts::TAU).abs() < 1e-12);
+
+    // Folding lifts both free corners alike, while the crease stays in place
+    let shape = extractor.node_mode_shape(1).unwrap();
+    assert_eq!(shape.len(), 4);
+    for node in [0, 2] {
+        assert_eq!(shape[node], rtori_os_model::Vector3F::default());
+    }
+    for node in [1, 3] {
+        let [x, y, z] = shape[node].0;
+        assert!(x.abs() < 1e-5 && y.abs() < 1e-5, "{shape:?}");
+        assert!(
+            (z.abs() - core::f32::consts::FRAC_1_SQRT_2).abs() < 1e-5,
+            "{shape:?}"
+        );
+    }
+    assert!(shape[1].0[2] * shape[3].0[2] > 0.0, "{shape:?}");
+    assert!(extractor.node_mode_shape(3).is_none());
+
+    // The modes belong to the configuration they were computed around
+    solver.solve(0.5, &Default::default()).unwrap();
+    assert_eq!(solver.extract().mode_count(), 0);
+}
+
+/// test_masses checks that the modes are weighted by the masses
+#[test]
+fn test_masses() {
+    let mut solver = load(&square(true, 2.0));
+    solver.compute_modes(&Default::default()).unwrap();
+    let extractor = solver.extract();
+    assert!((extractor.mode_eigenvalue(1).unwrap() - folding_eigenvalue(2.0)).abs() < 1e-6);
+
+    // The shapes are orthonormal, with the masses
+    for a in 0..extractor.mode_count() {
+        for b in 0..extractor.mode_count() {
+            let product: f32 = extractor
+                .node_mode_shape(a)
+                .unwrap()
+                .iter()
+                .zip(extractor.node_mode_shape(b).unwrap())
+                .map(|(a, b)| 2.0 * (0..3).map(|i| a.0[i] * b.0[i]).sum::<f32>())
+                .sum();
+            let expected = if a == b { 1.0 } else { 0.0 };
+            assert!((product - expected).abs() < 1e-4, "{a}·{b} = {product}");
+        }
+    }
+
+    // The importer rejects massless nodes, but they can still be set afterwards
+    let mut massless = load(&square(true, 1.0));
+    massless.load().copy_node_mass(&[0.0; 4], 0);
+    assert_eq!(
+        massless.compute_modes(&Default::default()),
+        Err(ModalError::Massless { node_index: 1 })
+    );
+}
+
+/// test_rigid_body_modes checks that a free model has its six rigid-body modes first
+#[test]
+fn test_rigid_body_modes() {
+    let mut solver = load(&square(false, 1.0));
+    solver
+        .compute_modes(&ModalConfig {
+            mode_count: 8,
+            ..Default::default()
+        })
+        .unwrap();
+
+    let extractor = solver.extract();
+    let eigenvalues: Vec<f64> = (0..8)
+        .map(|mode| extractor.mode_eigenvalue(mode).unwrap())
+        .collect();
+    assert!(
+        eigenvalues[..6]
+            .iter()
+            .all(|eigenvalue| eigenvalue.abs() < 1e-6),
+        "{eigenvalues:?}"
+    );
+    assert!(eigenvalues[6] > 1e-1, "{eigenvalues:?}");
+    assert!(eigenvalues.windows(2).all(|pair| pair[0] <= pair[1]));
+}
@@ -1,48 +1,88 @@
-use std::{thread, time::Duration};
+//! Command-line tools working on FOLD files
+//!
+//! - `rtori-cli modes <file.fold> [--fold-percentage <p>] [--count <n>]` folds the pattern to its static equilibrium,
+//!   and prints the frequencies of its lowest vibration modes
+#![feature(allocator_api)]
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let instance = wgpu::Instance::default();
+use std::error::Error;
 
-    let adapter = instance
-        .request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
-            compatible_surface: None,
-            force_fallback_adapter: false,
+const USAGE: &str = "usage: rtori-cli modes <file.fold> [--fold-percentage <p>] [--count <n>]";
+
+struct ModesArguments {
+    path: String,
+    fold_percentage: f32,
+    mode_count: u32,
+}
+
+impl ModesArguments {
+    fn parse(mut arguments: impl Iterator<Item = String>) -> Result<Self, Box<dyn Error>> {
+        let mut path = None;
+        let mut fold_percentage = 0.0;
+        let mut mode_count = rtori_os_static::ModalConfig::default().mode_count;
+        while let Some(argument) = arguments.next() {
+            let mut value = || {
+                arguments
+                    .next()
+                    .ok_or_else(|| format!("missing the value of {argument}"))
+            };
+            match argument.as_str() {
+                "--fold-percentage" => fold_percentage = value()?.parse()?,
+                "--count" => mode_count = value()?.parse()?,
+                _ if path.is_none() && !argument.starts_with("--") => path = Some(argument),
+                _ => return Err(format!("unexpected argument {argument}\n{USAGE}").into()),
+            }
+        }
+
+        Ok(Self {
+            path: path.ok_or(USAGE)?,
+            fold_percentage,
+            mode_count,
         })
-        .await
-        .unwrap();
-
-    let minimum_limits = {
-        let mut default = wgpu::Limits::default();
-        default.max_storage_buffers_per_shader_stage = 9;
-        default
-    };
-
-    let (device, queue) = adapter
-        .request_device(
-            &wgpu::DeviceDescriptor {
-                required_limits: minimum_limits,
-                ..Default::default()
-            },
-            None,
-        )
-        .await
-        .unwrap();
-
-    let mut runner = rtori_core_wgpu::Runner::create(&device);
-    runner.prepare(
-        &device,
-        &rtori_core_wgpu::ModelSize {
-            node_count: 6,
-            crease_count: 12,
-            face_count: 10,
-            node_beam_count: 16,
-            node_crease_count: 14,
-            node_face_count: 19,
-        },
+    }
+}
+
+fn modes(arguments: ModesArguments) -> Result<(), Box<dyn Error>> {
+    let file = std::fs::read_to_string(&arguments.path)?;
+    let parsed = serde_json::from_str::<fold::File>(&file)?;
+    let frame = &parsed.key_frame;
+
+    let allocator = std::alloc::Global;
+    let transformed = rtori_os_fold_importer::supplement::transform_in(frame, allocator)?;
+    let transformed_input = transformed.with_fold(frame);
+    let preprocessed =
+        rtori_os_fold_importer::InputWithCreaseGeometry::process(&transformed_input, allocator)?;
+
+    let mut solver = rtori_os_static::Solver::with_size(&preprocessed.compute_size());
+    preprocessed.load(&mut solver.load(), Default::default(), allocator)?;
+
+    let report = solver.solve(arguments.fold_percentage, &Default::default())?;
+    println!(
+        "Equilibrium at a fold percentage of {} ({} increments, {} iterations, residual force of {:e})",
+        arguments.fold_percentage, report.increments, report.iterations, report.residual
     );
 
-    thread::sleep(Duration::from_secs(15));
+    solver.compute_modes(&rtori_os_static::ModalConfig {
+        mode_count: arguments.mode_count,
+        ..Default::default()
+    })?;
+    let extractor = solver.extract();
+    println!("{:>6} {:>16} {:>16}", "mode", "frequency", "eigenvalue");
+    for mode in 0..extractor.mode_count() {
+        println!(
+            "{:>6} {:>16.6e} {:>16.6e}",
+            mode + 1,
+            extractor.mode_frequency(mode).unwrap(),
+            extractor.mode_eigenvalue(mode).unwrap()
+        );
+    }
+
     Ok(())
 }
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut arguments = std::env::args().skip(1);
+    match arguments.next().as_deref() {
+        Some("modes") => modes(ModesArguments::parse(arguments)?),
+        _ => Err(USAGE.into()),
+    }
+}
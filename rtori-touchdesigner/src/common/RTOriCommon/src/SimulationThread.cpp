@@ -424,18 +424,26 @@ this->m_output.indices.data()),
 					extractVelocity = input.extractVelocity.value;
 					extractError = input.extractError.value;
 
-					if (input.resetFlag) {
+					// Once a model is loaded, resetting doesn't require importing it again
+					bool const reimport = input.resetFlag && !loaded;
+					if (input.resetFlag && loaded) {
 						std::cout << "Resetting..." << std::endl;
+
+						rtori::SolverOperationResult const resetResult = solver.reset();
+						if (resetResult != rtori::SolverOperationResult::Success) {
+							std::cout << "ERROR: Solver reset failed: "
+									  << format_SolverOperationResult(resetResult) << std::endl;
+						}
 					}
 
 					SolverImportResult result =
-					  solver.update((input.resetFlag || input.foldFileSource.changed)
+					  solver.update((reimport || input.foldFileSource.changed)
 									  ? std::optional(input.foldFileSource.value)
 									  : std::nullopt,
-									(input.resetFlag || input.frameIndex.changed)
+									(reimport || input.frameIndex.changed)
 									  ? std::optional(input.frameIndex.value)
 									  : std::nullopt,
-									(input.resetFlag || input.foldPercentage.changed)
+									(reimport || input.foldPercentage.changed)
 									  ? std::optional(input.foldPercentage.value)
 									  : std::nullopt);
 
@@ -145,4 +145,10 @@ SolverImportResult Solver::update(std::optional<std::string_view> fold,
 		// Done
 		return SolverImportResult{.kind = SolverImportResultKind::Success};
 	}
-}
\ No newline at end of file
+}
+
+rtori::SolverOperationResult Solver::reset() {
+	assert(this->solver != nullptr);
+
+	return rtori::rtori_solver_reset(solver);
+}
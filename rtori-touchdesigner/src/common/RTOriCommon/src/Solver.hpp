@@ -46,6 +46,9 @@ class Solver final {
 	SolverImportResult update(std::optional<std::string_view> fold,
 							  std::optional<uint16_t> frameIndex,
 							  std::optional<float> foldPercentage);
+
+	// Brings the loaded model back to its rest state, without importing it again
+	rtori::SolverOperationResult reset();
 };
 
 } // namespace rtori::rtori_td
\ No newline at end of file